@@ -0,0 +1,40 @@
+//! build - Embeds build details shown by `treeclip version --verbose`.
+
+use std::env;
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs/heads");
+
+    let commit = command_output("git", &["rev-parse", "--short", "HEAD"]);
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let rustc_version = command_output(&rustc, &["--version"]);
+    let target = env::var("TARGET").unwrap_or_default();
+
+    println!(
+        "cargo:rustc-env=TREECLIP_GIT_COMMIT={}",
+        commit.unwrap_or_else(unknown)
+    );
+    println!(
+        "cargo:rustc-env=TREECLIP_RUSTC_VERSION={}",
+        rustc_version.unwrap_or_else(unknown)
+    );
+    println!("cargo:rustc-env=TREECLIP_TARGET={target}");
+}
+
+/// Runs a command and returns its trimmed stdout, if it succeeded.
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let text = String::from_utf8(output.stdout).ok()?;
+    Some(text.trim().to_string())
+}
+
+/// Placeholder for details that could not be determined (e.g. building from a tarball).
+fn unknown() -> String {
+    "unknown".to_string()
+}
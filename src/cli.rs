@@ -44,6 +44,8 @@ pub struct Cli {
 }
 
 /// Available subcommands for TreeClip.
+// Parsed once per process, so the size gap between `RunArgs` and the rest doesn't matter
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand)]
 pub enum Commands {
     /// Run TreeClip to extract and bundle code files
@@ -64,6 +66,36 @@ pub enum Commands {
 TIP: Create a .treeclipignore file (like .gitignore) for permanent exclusions!"
     )]
     Run(args::RunArgs),
+
+    /// Unpack treeclip output back into individual files
+    ///
+    /// Reads a treeclip dump (from a file or the clipboard) and
+    /// recreates every '==> path' block as a file on disk.
+    #[command(
+        verbatim_doc_comment,
+        after_help = "QUICK EXAMPLES:
+    treeclip unpack bundle.txt -d ./restored   # Restore files from a dump
+    treeclip unpack --from-clipboard           # Restore a pasted dump here"
+    )]
+    Unpack(args::UnpackArgs),
+
+    /// Show the version, and with --verbose build and platform details
+    #[command(
+        verbatim_doc_comment,
+        after_help = "QUICK EXAMPLES:
+    treeclip version             # Just the version
+    treeclip version --verbose   # Details for a bug report"
+    )]
+    Version(args::VersionArgs),
+
+    /// Check that clipboard, editor and terminal features will work
+    ///
+    /// Tries the clipboard with a test string (restoring what was
+    /// there), looks for the editor launcher and $VISUAL/$EDITOR,
+    /// and checks color and Unicode support. Every problem comes
+    /// with a hint on how to fix it.
+    #[command(verbatim_doc_comment)]
+    Doctor,
 }
 
 // -------------------------------------------- Private Helper Functions --------------------------------------------
@@ -114,17 +146,18 @@ mod cli_tests {
 
     #[test]
     fn test_cli_parse_run_command() {
-        let cli = Cli::parse_from(&["treeclip", "run", "test_dir"]);
+        let cli = Cli::parse_from(["treeclip", "run", "test_dir"]);
         match cli.command {
             Commands::Run(args) => {
                 assert_eq!(args.input_paths, vec![PathBuf::from("test_dir")]);
             }
+            _ => panic!("expected run command"),
         }
     }
 
     #[test]
     fn test_cli_parse_multiple_input_paths() {
-        let cli = Cli::parse_from(&["treeclip", "run", "dir1", "dir2", "dir3"]);
+        let cli = Cli::parse_from(["treeclip", "run", "dir1", "dir2", "dir3"]);
         match cli.command {
             Commands::Run(args) => {
                 assert_eq!(args.input_paths.len(), 3);
@@ -132,12 +165,13 @@ mod cli_tests {
                 assert_eq!(args.input_paths[1], PathBuf::from("dir2"));
                 assert_eq!(args.input_paths[2], PathBuf::from("dir3"));
             }
+            _ => panic!("expected run command"),
         }
     }
 
     #[test]
     fn test_cli_parse_run_with_exclude() {
-        let cli = Cli::parse_from(&[
+        let cli = Cli::parse_from([
             "treeclip",
             "run",
             ".",
@@ -152,12 +186,13 @@ mod cli_tests {
                 assert_eq!(args.exclude, vec!["node_modules", ".git"]);
                 assert_eq!(args.input_paths, vec![PathBuf::from(".")]);
             }
+            _ => panic!("expected run command"),
         }
     }
 
     #[test]
     fn test_cli_parse_run_with_flags() {
-        let cli = Cli::parse_from(&[
+        let cli = Cli::parse_from([
             "treeclip",
             "run",
             ".",
@@ -172,23 +207,53 @@ mod cli_tests {
                 assert!(args.editor);
                 assert!(args.verbose);
             }
+            _ => panic!("expected run command"),
         }
     }
 
     #[test]
     fn test_cli_parse_with_fast_mode() {
-        let cli = Cli::parse_from(&["treeclip", "run", ".", "--fast-mode"]);
+        let cli = Cli::parse_from(["treeclip", "run", ".", "--fast-mode"]);
 
         match cli.command {
             Commands::Run(args) => {
                 assert!(args.fast_mode);
             }
+            _ => panic!("expected run command"),
         }
     }
 
+    #[test]
+    fn test_cli_parse_unpack_command() {
+        let cli = Cli::parse_from(["treeclip", "unpack", "dump.txt", "-d", "out"]);
+        match cli.command {
+            Commands::Unpack(args) => {
+                assert_eq!(args.input, Some(PathBuf::from("dump.txt")));
+                assert_eq!(args.output_dir, PathBuf::from("out"));
+                assert!(!args.from_clipboard);
+            }
+            _ => panic!("expected unpack command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_version_command() {
+        let cli = Cli::parse_from(["treeclip", "version", "--verbose"]);
+        match cli.command {
+            Commands::Version(args) => assert!(args.verbose),
+            _ => panic!("expected version command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_doctor_command() {
+        let cli = Cli::parse_from(["treeclip", "doctor"]);
+        assert!(matches!(cli.command, Commands::Doctor));
+    }
+
     #[test]
     fn test_cli_requires_subcommand() {
-        let result = Cli::try_parse_from(&["treeclip"]);
+        let result = Cli::try_parse_from(["treeclip"]);
         // Should fail because arg_required_else_help = true
         assert!(result.is_err());
     }
@@ -196,7 +261,7 @@ mod cli_tests {
     #[test]
     fn test_cli_version_flag() {
         // Just ensure it doesn't panic
-        let result = Cli::try_parse_from(&["treeclip", "--version"]);
+        let result = Cli::try_parse_from(["treeclip", "--version"]);
         // Will fail in test but shouldn't panic
         let _ = result;
     }
@@ -204,7 +269,7 @@ mod cli_tests {
     #[test]
     fn test_cli_help_flag() {
         // Just ensure it doesn't panic
-        let result = Cli::try_parse_from(&["treeclip", "--help"]);
+        let result = Cli::try_parse_from(["treeclip", "--help"]);
         // Will fail in test but shouldn't panic
         let _ = result;
     }
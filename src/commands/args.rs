@@ -1,10 +1,21 @@
 //! args - Defines command-line arguments and their validation logic.
 
+use crate::core::config;
+use crate::core::output::format::OutputFormat;
+use crate::core::output::listing::ListFormat;
+use crate::core::traversal::sort::{self, SortOrder};
+use crate::core::traversal::transform;
+use crate::core::traversal::walker::SymlinkHeaders;
+use crate::core::ui::banner::BannerChoice;
+use crate::core::ui::formatter::{StatsAlign, StatsField};
 use clap::{ArgAction, ValueHint};
-use std::path::PathBuf;
+use regex::Regex;
+use std::path::{Path, PathBuf};
 
 /// Arguments for the `run` command.
-#[derive(clap::Args)]
+#[derive(clap::Args, serde::Serialize)]
+#[command(group(clap::ArgGroup::new("statistics").args(["stats", "footer_stats"]).multiple(true)))]
+#[serde(rename_all = "kebab-case")]
 pub struct RunArgs {
     /// Paths to traverse (defaults to current directory)
     ///
@@ -29,11 +40,17 @@ pub struct RunArgs {
     /// Output file path for the extracted content
     ///
     /// Where to save the bundled output. If not specified,
-    /// creates 'treeclip_temp.txt' in the current directory.
+    /// creates 'treeclip_temp.txt' in the current directory;
+    /// given a directory, creates 'treeclip_temp.txt' in it.
+    ///
+    /// Use - to write to stdout instead of a file; clipboard,
+    /// editor and hash sidecar are then turned off.
     ///
     /// Examples:
     ///   -o output.txt
     ///   --output-path ./exports/bundle.txt
+    ///   -o ./exports/
+    ///   -o - | less
     #[arg(
         short,
         long,
@@ -45,9 +62,37 @@ pub struct RunArgs {
     )]
     pub output_path: Option<PathBuf>,
 
-    /// Root directory for .treeclipignore file lookup
+    /// Name the output after the input instead of treeclip_temp.txt
+    ///
+    /// Walking my-project/ writes ./my-project.txt, so saved
+    /// outputs say what they contain. With several inputs the
+    /// first one names the output.
+    #[arg(
+        long,
+        default_value_t = false,
+        conflicts_with = "output_path",
+        verbatim_doc_comment
+    )]
+    pub auto_name: bool,
+
+    /// Overwrite an existing output file without asking
+    ///
+    /// An existing --output-path other than treeclip_temp.txt,
+    /// or earlier treeclip output, is only replaced after you
+    /// confirm; without a terminal to ask on, the run aborts.
+    #[arg(
+        short = 'y',
+        long,
+        visible_alias = "yes",
+        default_value_t = false,
+        verbatim_doc_comment
+    )]
+    pub force: bool,
+
+    /// Root directory for ignore file lookup
     ///
-    /// Specifies where to search for the .treeclipignore file.
+    /// Specifies where to search for .treeclipignore, and for
+    /// the ripgrep-style .ignore and .rgignore files.
     /// Usually you don't need to change this.
     #[arg(
         long,
@@ -61,14 +106,22 @@ pub struct RunArgs {
 
     /// Exclude files/folders matching these glob patterns
     ///
-    /// Can be specified multiple times. Supports glob patterns
-    /// like '*.log', 'node_modules', 'target/**', etc.
+    /// Can be specified multiple times, or as a comma-separated list.
+    /// Supports glob patterns like '*.log', 'node_modules', 'target/**', etc.
     ///
     /// Common patterns:
     ///   -e node_modules      (exclude directory)
     ///   -e '*.log'           (exclude all .log files)
     ///   -e 'target'          (exclude Rust build dir)
     ///   -e '__pycache__'     (exclude Python cache)
+    ///   -e 'target,*.lock'   (several at once)
+    ///
+    /// Commas inside '{a,b}' stay part of the pattern; write '\,'
+    /// for a literal comma in a file name.
+    ///
+    /// Patterns in the TREECLIP_EXCLUDE environment variable (one
+    /// per line or comma-separated) are added too; -e wins when
+    /// they conflict.
     ///
     /// Tip: Use .treeclipignore file for permanent exclusions!
     #[arg(
@@ -80,243 +133,2425 @@ pub struct RunArgs {
     )]
     pub exclude: Vec<String>,
 
-    /// Copy the output to system clipboard
+    /// Exclude directories with this name (never files)
     ///
-    /// After extraction, automatically copies the entire
-    /// output to your clipboard for easy pasting into AI chats.
+    /// Prunes the whole directory. A plain name matches at any
+    /// depth; a name with a slash ('src/gen') is relative to the
+    /// root. A file with the same name is kept.
     ///
-    /// Platform notes:
-    ///   • Windows/macOS: Works out of the box
-    ///   • Linux: Requires xclip or wl-clipboard
-    #[arg(short, long, default_value_t = false, verbatim_doc_comment)]
-    pub clipboard: bool,
+    /// Example:
+    ///   --exclude-dir build --exclude-dir node_modules
+    #[arg(
+        long,
+        value_name = "NAME",
+        action = ArgAction::Append,
+        verbatim_doc_comment
+    )]
+    pub exclude_dir: Vec<String>,
 
-    /// Show detailed statistics about the extracted content
+    /// Exclude files matching this glob (never directories)
     ///
-    /// Displays:
-    ///   • Total lines, words, and characters
-    ///   • File size in human-readable format
-    ///   • Fun emoji feedback based on size 🐣🐘🐋
-    #[arg(long, default_value_t = false, verbatim_doc_comment)]
-    pub stats: bool,
+    /// A directory that matches the glob is still traversed.
+    ///
+    /// Example:
+    ///   --exclude-file '*.snap' --exclude-file docs
+    #[arg(
+        long,
+        value_name = "GLOB",
+        action = ArgAction::Append,
+        verbatim_doc_comment
+    )]
+    pub exclude_file: Vec<String>,
 
-    /// Open the output file in your default text editor
+    /// Also read exclusion patterns from FILE (gitignore syntax)
     ///
-    /// After extraction, opens the file for review/editing.
-    /// Respects $EDITOR environment variable on Unix systems.
+    /// Repeatable; applied after the ignore files in the root
+    /// and before --exclude. A file that is missing or invalid
+    /// stops the run, unlike the optional ignore files.
     ///
-    /// Combine with --delete to auto-cleanup after closing.
-    #[arg(long, default_value_t = false, verbatim_doc_comment)]
-    pub editor: bool,
+    /// Example:
+    ///   --exclude-from ci/treeclip.ignore
+    #[arg(
+        long,
+        value_name = "FILE",
+        action = ArgAction::Append,
+        value_hint = ValueHint::FilePath,
+        verbatim_doc_comment
+    )]
+    pub exclude_from: Vec<PathBuf>,
 
-    /// Delete the output file after closing the editor
+    /// Warn instead of failing when an --exclude-from file can't be used
+    #[arg(
+        long,
+        default_value_t = false,
+        requires = "exclude_from",
+        verbatim_doc_comment
+    )]
+    pub ignore_missing: bool,
+
+    /// Leave out files already in an earlier text output
     ///
-    /// Only works when used with --editor flag.
-    /// Perfect for temporary reviews without leaving files behind.
+    /// Parses the '==> path' headers of FILE, as unpack does,
+    /// and skips files shown under the same path now, so only
+    /// the delta is sent. Compare like with like: paths are
+    /// matched as shown, before --collapse-paths.
+    ///
+    /// Skipped files are never sorted, capped by
+    /// --max-files-per-dir or counted in --stats; the stats
+    /// note how many were left out.
     ///
     /// Example:
-    ///   treeclip run --editor --delete  (review then cleanup)
+    ///   --exclude-from-output shared-yesterday.txt
     #[arg(
         long,
-        default_value_t = false,
-        requires = "editor",
+        value_name = "FILE",
+        value_hint = ValueHint::FilePath,
         verbatim_doc_comment
     )]
-    pub delete: bool,
+    pub exclude_from_output: Option<PathBuf>,
 
-    /// Enable verbose output with detailed progress information
+    /// Only include files that differ from a reference directory
     ///
-    /// Shows:
-    ///   • File-by-file processing updates
-    ///   • Progress counters with cute emojis
-    ///   • Detailed operation logging
+    /// Each file is compared with the file at the same path
+    /// relative to --root under DIR (relative to the input
+    /// path, for inputs outside --root), by SHA-256 of the
+    /// content.
+    /// Files that are identical there are left out; files that
+    /// are new or changed are included, and their counts are
+    /// reported. For two checkouts of a project side by side,
+    /// without going through git.
     ///
-    /// Useful for debugging or understanding what's included.
-    #[arg(short, long, default_value_t = false, verbatim_doc_comment)]
-    pub verbose: bool,
+    /// Example:
+    ///   treeclip run --baseline ../project-main
+    #[arg(
+        long,
+        value_name = "DIR",
+        value_hint = ValueHint::DirPath,
+        verbatim_doc_comment
+    )]
+    pub baseline: Option<PathBuf>,
 
-    /// Skip hidden files and folders (starting with '.')
+    /// Apply .gitignore files from the root up to the git root
     ///
-    /// Enabled by default. Use --no-skip-hidden to include
-    /// hidden files like .env.example, .editorconfig, etc.
+    /// Matches git when run from a subdirectory: each file
+    /// applies relative to its own directory and deeper ones
+    /// win. Ranked below the ignore files and --exclude, so
+    /// those can re-include a path with '!pattern'.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub resolve_gitignore_in_parent_dirs: bool,
+
+    /// Exclude files that follow common test conventions
     ///
-    /// Examples of skipped files:
-    ///   • .git/
-    ///   • .env
-    ///   • .DS_Store
-    ///   • .vscode/
-    #[arg(short = 'H', long, default_value_t = true, verbatim_doc_comment)]
-    pub skip_hidden: bool,
+    /// Skips files matching these built-in patterns:
+    ///   *_test.rs  *_test.go  test_*.py  *_test.py
+    ///   *.test.{js,jsx,ts,tsx}  *.spec.{js,jsx,ts,tsx}
+    ///   tests/  __tests__/
+    ///
+    /// Presets have the lowest precedence, so re-include a match
+    /// with a negated pattern:
+    ///   --exclude-test-files -e '!fixtures_test.rs'
+    ///
+    /// A skipped directory is never entered, so files inside
+    /// `tests/` come back only with the whole directory:
+    ///   --exclude-test-files -e '!tests/'
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub exclude_test_files: bool,
 
-    /// Extract raw content without additional metadata
+    /// Exclude dependency lockfiles
     ///
-    /// Currently always enabled. Future versions may add
-    /// metadata like file timestamps, sizes, or checksums.
+    /// Skips these well-known lockfiles wherever they appear:
+    ///   Cargo.lock  package-lock.json  yarn.lock  pnpm-lock.yaml
+    ///   poetry.lock  Pipfile.lock  Gemfile.lock  composer.lock  go.sum
+    ///
+    /// Re-include one with a negated pattern:
+    ///   --exclude-lockfiles -e '!Cargo.lock'
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub exclude_lockfiles: bool,
+
+    /// Exclude files that look machine-generated
+    ///
+    /// Scans the first few lines of each file for markers such as:
+    ///   Code generated by ... DO NOT EDIT   @generated
+    ///   auto-generated   autogenerated
+    ///
+    /// Handy for protobuf/codegen output without listing paths.
+    /// Skipped files are counted in --stats.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub exclude_generated: bool,
+
+    /// Exclude files that look minified
+    ///
+    /// Samples the start of each file of 1 KiB or more, and skips
+    /// it when its lines are very long on average or it has very
+    /// few newlines for its size. Catches bundled JS/CSS that
+    /// extension filters miss. Skipped files are counted in --stats.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub exclude_minified: bool,
+
+    /// Average line length above which a file counts as minified
+    ///
+    /// Measured in characters over non-blank lines.
     #[arg(
-        short,
         long,
-        default_value_t = true,
-        hide = true  // Hide until we implement non-raw mode
+        value_name = "CHARS",
+        default_value_t = 300,
+        requires = "exclude_minified",
+        verbatim_doc_comment
     )]
-    pub raw: bool,
+    pub minified_line_length: usize,
 
-    /// Fast mode: skip animations and execute instantly
+    /// Newlines per byte below which a file counts as minified
     ///
-    /// Disables:
-    ///   • Welcome banner
-    ///   • Progress spinners and animations
-    ///   • Cute tree emojis 🌳 (sorry!)
+    /// The default of 0.001 means fewer than one newline per KB.
+    #[arg(
+        long,
+        value_name = "RATIO",
+        default_value_t = 0.001,
+        value_parser = parse_ratio,
+        requires = "exclude_minified",
+        verbatim_doc_comment
+    )]
+    pub minified_newline_ratio: f64,
+
+    /// Only include files whose content matches REGEX
     ///
-    /// Perfect for:
-    ///   • CI/CD pipelines
-    ///   • Shell scripts
-    ///   • Large projects where speed matters
-    ///   • When you're in a hurry!
-    #[arg(short, long, default_value_t = false, verbatim_doc_comment)]
-    pub fast_mode: bool,
-}
+    /// Uses Rust regex syntax, matched anywhere in the file:
+    ///   --content-match 'fn parse_\w+'
+    ///
+    /// Every candidate file has to be read to test it, so this
+    /// is slower than path filtering on large trees. With
+    /// --toc, --tree, --no-content or --content-max-depth the
+    /// file list is needed up front, so files are read twice.
+    /// Matched and skipped files are counted in --stats.
+    #[arg(
+        long,
+        value_name = "REGEX",
+        value_parser = regex::Regex::new,
+        verbatim_doc_comment
+    )]
+    #[serde(serialize_with = "config::regex")]
+    pub content_match: Option<Regex>,
 
-// -------------------------------------------- Private Helper Functions --------------------------------------------
+    /// Leave out files whose content matches REGEX
+    ///
+    /// Applied after --content-match, with the same cost:
+    ///   --content-exclude '#\[cfg\(test\)\]'
+    #[arg(
+        long,
+        value_name = "REGEX",
+        value_parser = regex::Regex::new,
+        verbatim_doc_comment
+    )]
+    #[serde(serialize_with = "config::regex")]
+    pub content_exclude: Option<Regex>,
 
-/// Validates that a path string is not empty.
-///
-/// Returns an error message if validation fails.
-fn validate_path(s: &str) -> Result<PathBuf, String> {
-    if s.trim().is_empty() {
-        return Err("Path cannot be empty".to_string());
-    }
-    Ok(PathBuf::from(s))
-}
+    /// Order in which files appear in the output
+    ///
+    /// Options:
+    ///   none      (filesystem walk order, default)
+    ///   path      (alphabetical)
+    ///   size      (smallest first)
+    ///   modified  (oldest first, alias: mtime)
+    ///
+    /// Files that tie on size or modification time are ordered
+    /// by path, so output is reproducible across runs. Files
+    /// without a modification time come last, by path.
+    #[arg(
+        long,
+        value_enum,
+        value_name = "ORDER",
+        default_value_t = SortOrder::None,
+        hide_possible_values = true,
+        verbatim_doc_comment
+    )]
+    pub sort: SortOrder,
 
-#[cfg(test)]
-mod args_tests {
-    use super::*;
-    use crate::cli::{Cli, Commands};
-    use clap::Parser;
+    /// Reverse the --sort order
+    ///
+    /// Z to A, largest first, or newest first:
+    ///   treeclip run --sort mtime --reverse-sort
+    #[arg(long, default_value_t = false, requires = "sort", verbatim_doc_comment)]
+    pub reverse_sort: bool,
 
-    #[test]
-    fn test_validate_path_valid() {
-        let result = validate_path(".");
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), PathBuf::from("."));
-    }
+    /// Reverse the final file order
+    ///
+    /// Flips the whole list once --sort has ordered it, so it
+    /// also reverses the plain traversal order when no --sort
+    /// is given. Unlike --reverse-sort, files without a
+    /// modification time come first under '--sort mtime'.
+    /// Context files still lead with --context-first.
+    #[arg(
+        long,
+        default_value_t = false,
+        conflicts_with = "reverse_sort",
+        verbatim_doc_comment
+    )]
+    pub reverse: bool,
 
-    #[test]
-    fn test_validate_path_empty() {
-        let result = validate_path("");
-        assert!(result.is_err());
-        assert!(result.unwrap_err().contains("cannot be empty"));
-    }
+    /// Put README and manifest files before everything else
+    ///
+    /// Gives whoever reads the dump its bearings first:
+    /// README.md, Cargo.toml, package.json and similar files,
+    /// in any directory, lead the output. The other files
+    /// follow in --sort order.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub context_first: bool,
 
-    #[test]
-    fn test_validate_path_whitespace() {
-        let result = validate_path("   ");
-        assert!(result.is_err());
-    }
+    /// File names --context-first moves forward, in priority order
+    ///
+    /// Comma-separated; matched against file names, ignoring
+    /// case. Replaces the built-in list.
+    ///
+    /// Example:
+    ///   --context-first --context-files README.md,ARCHITECTURE.md
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_name = "NAMES",
+        default_values_t = sort::CONTEXT_FILES.iter().map(|name| name.to_string()),
+        hide_default_value = true,
+        requires = "context_first",
+        verbatim_doc_comment
+    )]
+    pub context_files: Vec<String>,
 
-    #[test]
-    fn test_run_args_default_values() {
-        let cli = Cli::parse_from(&["treeclip", "run"]);
-        match cli.command {
-            Commands::Run(args) => {
-                assert_eq!(args.input_paths, vec![PathBuf::from(".")]);
-                assert!(args.output_path.is_some());
-                assert!(!args.clipboard);
-                assert!(!args.stats);
-                assert!(!args.editor);
-                assert!(!args.delete);
-                assert!(!args.verbose);
-                assert!(!args.fast_mode);
-                assert!(args.skip_hidden);
-                assert!(args.exclude.is_empty());
-            }
-        }
-    }
+    /// Compare and show paths in Unicode NFC form
+    ///
+    /// macOS often stores names decomposed (NFD), so 'café'
+    /// can be spelled two ways that look identical but sort
+    /// and compare differently. This normalizes paths for
+    /// --sort and in headers, the TOC and the tree.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub normalize_unicode: bool,
 
-    #[test]
-    fn test_multiple_input_paths() {
-        let cli = Cli::parse_from(&["treeclip", "run", ".", "src", "some/other/input/path"]);
-        match cli.command {
-            Commands::Run(args) => {
-                assert_eq!(args.input_paths.len(), 3);
-                assert_eq!(args.input_paths[0], PathBuf::from("."));
-                assert_eq!(args.input_paths[1], PathBuf::from("src"));
-                assert_eq!(args.input_paths[2], PathBuf::from("some/other/input/path"));
-            }
-        }
-    }
+    /// Write a table of contents before the file contents
+    ///
+    /// Lists every included file with its size, numbered in
+    /// output order. With several input paths, each one gets
+    /// its own table ahead of its files.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub toc: bool,
 
-    #[test]
-    fn test_fast_mode_flag() {
-        let cli = Cli::parse_from(&["treeclip", "run", ".", "--fast-mode"]);
-        match cli.command {
-            Commands::Run(args) => {
-                assert!(args.fast_mode);
-            }
-        }
-    }
+    /// Write a directory tree of the included files first
+    ///
+    /// Lists every included file under its directories,
+    /// before the table of contents (--toc) and the file
+    /// contents, which are still written in full. Only
+    /// --no-content leaves the contents out. Left out of
+    /// --format json.
+    #[arg(
+        long,
+        visible_alias = "prepend-tree",
+        default_value_t = false,
+        verbatim_doc_comment
+    )]
+    pub tree: bool,
 
-    #[test]
-    fn test_multiple_exclude_patterns() {
-        let cli = Cli::parse_from(&[
-            "treeclip",
-            "run",
-            ".",
+    /// Write only the directory tree, without file contents
+    ///
+    /// Handy for sharing a project's layout. Implies --tree
+    /// and honors every exclude option. With --stats, the
+    /// size and line counts are those of the tree alone.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub no_content: bool,
+
+    /// Write one 'path: first line' entry per file instead of contents
+    ///
+    /// A quick inventory with a preview of each file: its first
+    /// non-blank line, read from the file's first 8 KiB. Lines
+    /// are cut to the terminal width. Like --tree, left out of
+    /// --format json.
+    #[arg(
+        long,
+        default_value_t = false,
+        conflicts_with = "no_content",
+        verbatim_doc_comment
+    )]
+    pub oneline: bool,
+
+    /// Show at most N levels of the directory tree
+    ///
+    /// Only the tree (--tree, --no-content) is cut: deeper
+    /// directories show a '... (K files)' line, while their
+    /// files still have their content in the output. Use
+    /// --content-max-depth to limit the content instead.
+    ///
+    /// Example:
+    ///   --tree --tree-depth 2
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..),
+        verbatim_doc_comment
+    )]
+    pub tree_depth: Option<usize>,
+
+    /// Show directories left empty by filtering in the tree
+    ///
+    /// By default the tree (--tree, --no-content) only shows
+    /// directories with an included file below them. This also
+    /// lists directories whose files were all excluded or that
+    /// were empty to begin with, marked with a trailing '/'.
+    /// Whichever of --include-empty-dirs-in-tree and
+    /// --prune-empty-dirs comes last wins.
+    #[arg(
+        long,
+        default_value_t = false,
+        overrides_with = "prune_empty_dirs",
+        verbatim_doc_comment
+    )]
+    pub include_empty_dirs_in_tree: bool,
+
+    /// Leave directories without included files out of the tree (default)
+    ///
+    /// Turns --include-empty-dirs-in-tree back off, e.g. after
+    /// a shell alias set it.
+    #[arg(
+        long,
+        default_value_t = false,
+        overrides_with = "include_empty_dirs_in_tree",
+        verbatim_doc_comment
+    )]
+    pub prune_empty_dirs: bool,
+
+    /// Follow symbolic links while traversing
+    ///
+    /// By default symlinked directories are not entered, while
+    /// symlinked files are still read. A link back to one of its
+    /// own ancestors is skipped with a warning.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub follow_symlinks: bool,
+
+    /// Follow symlinked directories but skip symlinked files
+    ///
+    /// For monorepos that link shared modules into several
+    /// packages: linked directories are entered like real ones,
+    /// while file links, whose content also appears where they
+    /// point, are left out. Skipped links are counted in
+    /// --stats. Symlink loops are skipped with a warning.
+    #[arg(
+        long,
+        default_value_t = false,
+        conflicts_with_all = ["follow_symlinks", "exclude_symlinks"],
+        verbatim_doc_comment
+    )]
+    pub follow_symlink_dirs: bool,
+
+    /// Skip every symlink, file or directory
+    ///
+    /// Avoids including content twice when links point inside
+    /// the tree. Takes precedence over --follow-symlinks.
+    /// Skipped links are counted in --stats.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub exclude_symlinks: bool,
+
+    /// Skip directories with too many entries to be source code
+    ///
+    /// Before descending into a directory, counts its direct
+    /// entries and prunes it when there are more than
+    /// --prune-dir-over. Guards against caches, vendored
+    /// dependencies and build output in unfamiliar trees. The
+    /// input paths themselves are never pruned, and each pruned
+    /// directory is named in a warning.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub exclude_large_dirs: bool,
+
+    /// Entry count above which --exclude-large-dirs prunes a directory
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 1000,
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..),
+        requires = "exclude_large_dirs"
+    )]
+    pub prune_dir_over: usize,
+
+    /// Path shown in headers for files reached through a symlink
+    ///
+    /// Options:
+    ///   link    (where the file appears in the tree, default)
+    ///   target  (where the symlink resolves to)
+    #[arg(
+        long,
+        value_enum,
+        value_name = "MODE",
+        default_value_t = SymlinkHeaders::Link,
+        requires = "follow_symlinks",
+        hide_possible_values = true,
+        verbatim_doc_comment
+    )]
+    pub symlink_headers: SymlinkHeaders,
+
+    /// Copy the output to system clipboard
+    ///
+    /// After extraction, automatically copies the entire
+    /// output to your clipboard for easy pasting into AI chats.
+    ///
+    /// Platform notes:
+    ///   • Windows/macOS: Works out of the box
+    ///   • Linux: Requires xclip or wl-clipboard
+    #[arg(short, long, default_value_t = false, verbatim_doc_comment)]
+    pub clipboard: bool,
+
+    /// Cap how many bytes go to the clipboard in one piece
+    ///
+    /// Larger output is split at a line break: the clipboard gets
+    /// the first part plus a note, and the remainder is saved as
+    /// <output>.rest.txt. Helps where big copies fail silently,
+    /// e.g. X11 clipboard managers, RDP/VNC or VM clipboard sharing,
+    /// and OSC 52 forwarding through tmux or SSH.
+    ///
+    /// Example:
+    ///   --clipboard --clipboard-limit 1000000
+    #[arg(
+        long,
+        value_name = "BYTES",
+        requires = "clipboard",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1024..),
+        verbatim_doc_comment
+    )]
+    pub clipboard_limit: Option<usize>,
+
+    /// Add the output after the clipboard's current text
+    ///
+    /// Builds up context from several runs in one clipboard
+    /// buffer without an intermediate file. The output starts on
+    /// a new line; a clipboard holding no text counts as empty.
+    /// --clipboard-limit applies to the combined text.
+    ///
+    /// Example:
+    ///   treeclip run src/api -c && treeclip run src/db -c --clipboard-append
+    #[arg(
+        long,
+        default_value_t = false,
+        requires = "clipboard",
+        verbatim_doc_comment
+    )]
+    pub clipboard_append: bool,
+
+    /// Also copy an HTML rendering of markdown output
+    ///
+    /// Rich-text targets such as Slack, email clients and
+    /// document editors then paste real headings and code
+    /// blocks; plain-text targets still get the markdown.
+    /// Needs markdown on the clipboard (--format markdown or
+    /// --clipboard-format markdown); otherwise plain text is
+    /// copied with a warning.
+    ///
+    /// Example:
+    ///   treeclip run --format markdown -c --clipboard-html
+    #[arg(
+        long,
+        default_value_t = false,
+        requires = "clipboard",
+        verbatim_doc_comment
+    )]
+    pub clipboard_html: bool,
+
+    /// Show detailed statistics about the extracted content
+    ///
+    /// Displays:
+    ///   • Total lines, words, and characters
+    ///   • File size in human-readable format
+    ///   • Fun emoji feedback based on size 🐣🐘🐋
+    ///
+    /// Counts cover the file contents only, not headers.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub stats: bool,
+
+    /// Choose which rows appear in the statistics box
+    ///
+    /// Comma-separated list of: chars, lines, words, size.
+    /// Defaults to all of them. Applies to --stats and
+    /// --footer-stats, one of which it needs.
+    ///
+    /// Examples:
+    ///   --stats --stats-fields size,lines
+    ///   --footer-stats --stats-fields size
+    #[arg(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        value_name = "FIELDS",
+        default_values_t = StatsField::ALL,
+        requires = "statistics",
+        verbatim_doc_comment
+    )]
+    pub stats_fields: Vec<StatsField>,
+
+    /// Align the values in the statistics box: left or right
+    ///
+    /// Applies to --stats and --footer-stats. The value
+    /// column grows to fit the longest value either way.
+    #[arg(
+        long,
+        value_enum,
+        value_name = "ALIGN",
+        default_value_t = StatsAlign::Right,
+        verbatim_doc_comment
+    )]
+    pub stats_align: StatsAlign,
+
+    /// Also count source lines of code per language
+    ///
+    /// Lines that are neither blank nor comments, shown in
+    /// a second box after the statistics. Comments are found
+    /// by each language's comment syntax at the start of a
+    /// line, so treat the numbers as an approximation.
+    #[arg(
+        long,
+        default_value_t = false,
+        requires = "stats",
+        verbatim_doc_comment
+    )]
+    pub sloc: bool,
+
+    /// Chart each language's share of the content bytes
+    ///
+    /// Horizontal bars shown after the statistics, sized
+    /// to the terminal width. Bars are plain '#' when
+    /// colors are off (NO_COLOR, or not a terminal).
+    #[arg(
+        long,
+        default_value_t = false,
+        requires = "stats",
+        verbatim_doc_comment
+    )]
+    pub language_chart: bool,
+
+    /// Append the statistics box to the end of the output
+    ///
+    /// Written as a final '==> (stats)' block in plain text,
+    /// so whoever receives the dump (or clipboard) sees its
+    /// size inline. The numbers are the --stats counts of the
+    /// file contents; --stats-fields picks the rows.
+    /// Text and markdown only; unpack drops the block.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub footer_stats: bool,
+
+    /// Open the output file in your default text editor
+    ///
+    /// After extraction, opens the file for review/editing.
+    /// Respects $EDITOR environment variable on Unix systems.
+    ///
+    /// Combine with --delete to auto-cleanup after closing.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub editor: bool,
+
+    /// Delete the output file after closing the editor
+    ///
+    /// Only works when used with --editor flag.
+    /// Perfect for temporary reviews without leaving files behind.
+    ///
+    /// Example:
+    ///   treeclip run --editor --delete  (review then cleanup)
+    #[arg(
+        long,
+        default_value_t = false,
+        requires = "editor",
+        verbatim_doc_comment
+    )]
+    pub delete: bool,
+
+    /// Remove the output file once it is on the clipboard
+    ///
+    /// For "copy and leave no trace" runs without an editor.
+    /// Only the default treeclip_temp.txt is ever removed; an
+    /// output you named with --output-path is kept.
+    ///
+    /// Example:
+    ///   treeclip run --clipboard --cleanup
+    #[arg(
+        long,
+        default_value_t = false,
+        requires = "clipboard",
+        conflicts_with_all = ["editor", "split_size"],
+        verbatim_doc_comment
+    )]
+    pub cleanup: bool,
+
+    /// Keep the clipboard in sync with the inputs, writing no file
+    ///
+    /// Copies once, then polls the inputs and copies again
+    /// after each change has settled, until Ctrl-C. The
+    /// content only passes through a temp file that is
+    /// removed right after each copy.
+    ///
+    /// Example:
+    ///   treeclip run src --watch-clipboard-only
+    #[arg(
+        long,
+        default_value_t = false,
+        conflicts_with_all = [
+            "output_path", "auto_name", "editor", "delete", "split_size",
+            "clipboard_limit", "clipboard_append", "hash_sidecar", "input_list_output",
+        ],
+        verbatim_doc_comment
+    )]
+    pub watch_clipboard_only: bool,
+
+    /// Show the output in $PAGER (default: less)
+    ///
+    /// A lighter review than --editor: the output is piped to
+    /// the pager and the run continues once it exits. Does
+    /// nothing when stdout is not a terminal or the output
+    /// goes to stdout; a missing pager is only a warning.
+    #[arg(
+        long,
+        default_value_t = false,
+        conflicts_with_all = ["editor", "watch_clipboard_only"],
+        verbatim_doc_comment
+    )]
+    pub page: bool,
+
+    /// Wait for the editor to exit before continuing
+    ///
+    /// Opens the output in $VISUAL or $EDITOR and blocks until
+    /// it exits, so --delete never removes the file from under
+    /// an editor that is still loading it. The desktop launcher
+    /// (xdg-open/open/start) returns immediately and is skipped.
+    /// For GUI editors, use a blocking command:
+    ///   VISUAL="code --wait" treeclip run --editor --wait --delete
+    #[arg(
+        long,
+        default_value_t = false,
+        requires = "editor",
+        verbatim_doc_comment
+    )]
+    pub wait: bool,
+
+    /// Enable verbose output with detailed progress information
+    ///
+    /// Shows:
+    ///   • File-by-file processing updates
+    ///   • Progress counters with cute emojis
+    ///   • Detailed operation logging
+    ///
+    /// Useful for debugging or understanding what's included.
+    #[arg(short, long, default_value_t = false, verbatim_doc_comment)]
+    pub verbose: bool,
+
+    /// List extensions that got a bare markdown code fence
+    ///
+    /// With markdown output (--format or --clipboard-format),
+    /// each file's extension picks the fence language. This
+    /// reports the extensions treeclip could not map, with
+    /// file counts, so you know where highlighting is missing.
+    #[arg(
+        long,
+        default_value_t = false,
+        requires = "verbose",
+        verbatim_doc_comment
+    )]
+    pub extensions_report: bool,
+
+    /// Skip hidden files and folders (starting with '.')
+    ///
+    /// Enabled by default. Use --hidden to include
+    /// hidden files like .env.example, .editorconfig, etc.
+    ///
+    /// Examples of skipped files:
+    ///   • .git/
+    ///   • .env
+    ///   • .DS_Store
+    ///   • .vscode/
+    #[arg(
+        short = 'H',
+        long,
+        default_value_t = true,
+        overrides_with = "hidden",
+        verbatim_doc_comment
+    )]
+    pub skip_hidden: bool,
+
+    /// Include hidden files and folders (starting with '.')
+    ///
+    /// Hidden entries are skipped by default; this turns that off.
+    /// Whichever of --hidden and --skip-hidden comes last wins.
+    ///
+    /// Tip: combine with '-e .git' to keep the repository out.
+    #[arg(
+        long,
+        visible_alias = "no-skip-hidden",
+        default_value_t = false,
+        overrides_with = "skip_hidden",
+        verbatim_doc_comment
+    )]
+    pub hidden: bool,
+
+    /// Extract raw content without additional metadata
+    ///
+    /// Currently always enabled. Future versions may add
+    /// metadata like file timestamps, sizes, or checksums.
+    #[arg(
+        short,
+        long,
+        default_value_t = true,
+        hide = true  // Hide until we implement non-raw mode
+    )]
+    pub raw: bool,
+
+    /// Fast mode: skip animations and execute instantly
+    ///
+    /// Disables:
+    ///   • Welcome banner
+    ///   • Progress spinners and animations
+    ///   • Cute tree emojis 🌳 (sorry!)
+    ///
+    /// Perfect for:
+    ///   • CI/CD pipelines
+    ///   • Shell scripts
+    ///   • Large projects where speed matters
+    ///   • When you're in a hurry!
+    #[arg(short, long, default_value_t = false, verbatim_doc_comment)]
+    pub fast_mode: bool,
+
+    /// Send banners, progress and messages to stderr
+    ///
+    /// Keeps stdout free of everything treeclip prints for
+    /// you, so only output you pipe on purpose ends up there.
+    /// Errors and warnings always go to stderr.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub progress_to_stderr: bool,
+
+    /// Print the resolved options as TOML and exit
+    ///
+    /// One 'flag = value' line per option, defaults included,
+    /// keyed by the long flag name. Patterns from
+    /// TREECLIP_EXCLUDE are listed under exclude. Nothing is
+    /// traversed or written. treeclip cannot load the file back
+    /// yet; it records the options for reading and sharing.
+    ///
+    /// Example:
+    ///   treeclip run src --stats -e target --print-config > treeclip.toml
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    #[serde(skip)]
+    pub print_config: bool,
+
+    /// Welcome banner to show
+    ///
+    /// Possible values:
+    ///   double, rounded, sharp: always show that design
+    ///   random: a different design each run (default)
+    ///   none:   no banner
+    ///
+    /// Pin one for reproducible demos and screenshots.
+    /// --fast-mode still hides the banner entirely.
+    ///
+    /// Example:
+    ///   --banner rounded
+    #[arg(
+        long,
+        value_enum,
+        value_name = "NAME",
+        default_value_t = BannerChoice::Random,
+        hide_possible_values = true,
+        verbatim_doc_comment
+    )]
+    pub banner: BannerChoice,
+
+    /// Write file contents verbatim without stripping trailing whitespace
+    ///
+    /// By default trailing whitespace at the end of each file is trimmed.
+    /// Use this when it is significant, e.g. Markdown hard breaks or
+    /// test fixtures. A single newline still separates files.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub no_trim: bool,
+
+    /// Number of blank lines between files
+    ///
+    /// Applies to text and markdown output. unpack expects
+    /// the default of 1; with more, restored files keep the
+    /// extra blank lines at their end.
+    ///
+    /// Example:
+    ///   --blank-lines-between 2
+    #[arg(long, value_name = "N", default_value_t = 1, verbatim_doc_comment)]
+    pub blank_lines_between: usize,
+
+    /// Output only declarations, eliding function bodies (best-effort)
+    ///
+    /// Keeps imports and type/function signatures so the API shape
+    /// survives at a fraction of the size. Supported languages:
+    ///   Rust (.rs)   Python (.py, .pyi)
+    ///
+    /// Other files are written in full. Extraction is line-based,
+    /// not a real parser, so unusual formatting may confuse it.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub context_only: bool,
+
+    /// Expand tabs to spaces, with tab stops every N columns
+    ///
+    /// For paste targets that render tabs inconsistently.
+    /// Tabs align to the next stop rather than becoming a
+    /// fixed number of spaces. 0 keeps tabs (default).
+    ///
+    /// Example:
+    ///   --tab-width 4
+    #[arg(long, value_name = "N", default_value_t = 0, verbatim_doc_comment)]
+    pub tab_width: usize,
+
+    /// Normalize each file as its .editorconfig says
+    ///
+    /// Files whose indent_style is 'space' get their tabs
+    /// expanded to tab_width (or indent_size) columns, and
+    /// end_of_line sets the line endings of any file. A
+    /// --tab-width other than 0 takes precedence over the
+    /// editorconfig width for every file.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub respect_editorconfig: bool,
+
+    /// Write control characters in file contents as visible escapes
+    ///
+    /// For paste targets that strip or act on invisible bytes:
+    /// NUL becomes '\x00', ESC '\x1b', and so on. Tabs,
+    /// newlines and CRLF line endings are kept.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub escape_control: bool,
+
+    /// Collapse runs of spaces and tabs in file contents
+    ///
+    /// Squeezes every run inside a line to one space and drops
+    /// indentation and trailing whitespace, for the densest
+    /// output in token-limited contexts. This is lossy: Python,
+    /// YAML, Haskell and Makefiles keep their indentation, but
+    /// spacing inside their lines (and string literals
+    /// everywhere) still changes, so a warning names them.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub collapse_whitespace_runs: bool,
+
+    /// Truncate lines longer than N characters
+    ///
+    /// Lines over the limit are cut to N characters and marked
+    /// with '…(truncated)', or --truncate-marker. A guard
+    /// against minified files whose single multi-megabyte line
+    /// would swamp the output. Affected lines are counted in
+    /// --stats.
+    ///
+    /// Example:
+    ///   --max-line-length 500
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..),
+        verbatim_doc_comment
+    )]
+    pub max_line_length: Option<usize>,
+
+    /// Text put where --max-line-length cut a line
+    ///
+    /// '{n}' is replaced with the number of characters cut.
+    ///
+    /// Example:
+    ///   --max-line-length 200 --truncate-marker ' [… {n} more]'
+    #[arg(
+        long,
+        value_name = "STR",
+        default_value = transform::TRUNCATION_MARKER,
+        requires = "max_line_length",
+        verbatim_doc_comment
+    )]
+    pub truncate_marker: String,
+
+    /// Hard-wrap lines wider than N columns
+    ///
+    /// For pasting into email or chat that would soft-wrap long
+    /// lines unpredictably. Each broken segment ends with '↩',
+    /// so no line is wider than N; columns are display widths.
+    /// Whitespace is kept as is, so dropping the markers and
+    /// their newlines restores the original lines. Combine with
+    /// --tab-width so tabs are counted correctly.
+    ///
+    /// Example:
+    ///   --wrap 72 --tab-width 4
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(2..),
+        verbatim_doc_comment
+    )]
+    pub wrap: Option<usize>,
+
+    /// Replace FIND with REPLACE in file contents (repeatable)
+    ///
+    /// For stripping absolute paths, internal hostnames and the
+    /// like before sharing. Replacements run in the order given,
+    /// each on the result of the previous one, after
+    /// --context-only and --as-diff and before the formatting
+    /// options (--tab-width, --max-line-length, ...). FIND is
+    /// literal and ends at the first '='. Verbose mode reports
+    /// how many substitutions were made.
+    ///
+    /// Example:
+    ///   --content-replace '/home/alice=~' --content-replace 'acme.internal=example.com'
+    #[arg(
+        long,
+        value_name = "FIND=REPLACE",
+        value_parser = parse_content_replace,
+        action = ArgAction::Append,
+        verbatim_doc_comment
+    )]
+    pub content_replace: Vec<ContentReplace>,
+
+    /// Treat each --content-replace FIND as a regular expression
+    ///
+    /// REPLACE may then use $1 or ${name} for groups; write a
+    /// literal '=' in FIND as \x3D.
+    ///
+    /// Example:
+    ///   --content-replace-regex --content-replace 'token-\w+=token-XXX'
+    #[arg(long, requires = "content_replace", verbatim_doc_comment)]
+    pub content_replace_regex: bool,
+
+    /// Include content only for files at most N levels deep
+    ///
+    /// Deeper files are still traversed and listed in the
+    /// table of contents (--toc) and --input-list-output,
+    /// but their content is left out. Files directly inside
+    /// an input path are at depth 1.
+    ///
+    /// Example:
+    ///   --content-max-depth 2 --toc
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..),
+        verbatim_doc_comment
+    )]
+    pub content_max_depth: Option<usize>,
+
+    /// Include at most N files from each directory
+    ///
+    /// Keeps dumps of data-heavy trees (fixtures, samples)
+    /// representative but bounded. Each directory keeps its
+    /// first N files in output order (see --sort); how many
+    /// were omitted per directory is noted on stderr.
+    ///
+    /// Example:
+    ///   --max-files-per-dir 5 --sort path
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..),
+        verbatim_doc_comment
+    )]
+    pub max_files_per_dir: Option<usize>,
+
+    /// Group files smaller than BYTES into one block
+    ///
+    /// Instead of a full '==> path' header each, small files
+    /// are written together at the end of the output under a
+    /// single '==> (small files)' header, each introduced by
+    /// a '--- path' line. Cuts header noise in directories
+    /// full of one-liners; unpack splits the block back up.
+    /// Only text output is grouped, and a file with a line
+    /// starting with '--- ' keeps its own header.
+    ///
+    /// Example:
+    ///   --min-significant-size 200
+    #[arg(
+        long,
+        value_name = "BYTES",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..),
+        verbatim_doc_comment
+    )]
+    pub min_significant_size: Option<usize>,
+
+    /// Skip files over a size that depends on their extension
+    ///
+    /// Comma-separated EXT=SIZE pairs, repeatable; SIZE is in
+    /// bytes or with a unit (b, kb, mb, gb; powers of 1024).
+    /// Extensions match ignoring case, and files with other
+    /// extensions have no limit. A later pair for the same
+    /// extension wins.
+    ///
+    /// Example:
+    ///   --max-file-size-by-ext 'json=50kb,lock=10kb,rs=1mb'
+    #[arg(
+        long,
+        value_name = "EXT=SIZE",
+        value_delimiter = ',',
+        value_parser = parse_ext_size,
+        action = ArgAction::Append,
+        verbatim_doc_comment
+    )]
+    #[serde(serialize_with = "config::ext_sizes")]
+    pub max_file_size_by_ext: Vec<(String, u64)>,
+
+    /// Output document format
+    ///
+    /// Possible values:
+    ///   text:     '==> path' headers followed by the file content (default)
+    ///   markdown: a '## path' heading and a fenced code block per file
+    ///   json:     an array of {"path", "content"} objects
+    ///   dot:      a Graphviz graph of the directory structure,
+    ///             without content (render with 'dot -Tpng')
+    ///
+    /// Example:
+    ///   --format markdown
+    #[arg(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        default_value_t = OutputFormat::Text,
+        hide_possible_values = true,
+        verbatim_doc_comment
+    )]
+    pub format: OutputFormat,
+
+    /// Format of the clipboard copy, if different from --format
+    ///
+    /// Lets one run save, say, markdown to the file while
+    /// copying plain text. Takes the same values as --format
+    /// and defaults to it.
+    ///
+    /// Example:
+    ///   --format markdown --clipboard-format text
+    #[arg(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        requires = "clipboard",
+        hide_possible_values = true,
+        verbatim_doc_comment
+    )]
+    pub clipboard_format: Option<OutputFormat>,
+
+    /// Maximum number of file contents held in memory at once
+    ///
+    /// Files are read in parallel, but never more than N are
+    /// buffered before being written, keeping memory bounded on
+    /// trees with many large files. Use 1 for sequential reads.
+    ///
+    /// Example:
+    ///   --read-buffer 64
+    #[arg(
+        long,
+        default_value_t = 16,
+        value_name = "N",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..),
+        verbatim_doc_comment
+    )]
+    pub read_buffer: usize,
+
+    /// Stop traversing after SECONDS and keep what was collected
+    ///
+    /// A safety valve for slow network mounts or unexpectedly
+    /// huge trees. Files written before the deadline stay in
+    /// the output, a warning is printed, and treeclip exits
+    /// with status 124.
+    ///
+    /// Example:
+    ///   --timeout 30
+    #[arg(
+        long,
+        value_name = "SECONDS",
+        value_parser = clap::value_parser!(u64).range(1..),
+        verbatim_doc_comment
+    )]
+    pub timeout: Option<u64>,
+
+    /// Fail if fewer than N files were included
+    ///
+    /// A guard for CI: catches an exclude pattern or filter
+    /// that accidentally left out (almost) everything. The
+    /// output is still written for inspection (to stdout with
+    /// -o -), but nothing is copied or opened, and treeclip
+    /// exits with status 3.
+    /// Also applies to --dry-run.
+    ///
+    /// Example:
+    ///   --min-files 10
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..),
+        verbatim_doc_comment
+    )]
+    pub min_files: Option<usize>,
+
+    /// Print the first N lines of the output (default 20)
+    ///
+    /// A quick sanity check of what is about to be copied,
+    /// without opening an editor. File headers are
+    /// highlighted unless colors are disabled (NO_COLOR).
+    ///
+    /// Use '--preview=N' when input paths follow the flag.
+    ///
+    /// Examples:
+    ///   --preview          # first 20 lines
+    ///   --preview=50       # first 50 lines
+    #[arg(
+        long,
+        value_name = "N",
+        num_args = 0..=1,
+        default_missing_value = "20",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..),
+        verbatim_doc_comment
+    )]
+    pub preview: Option<usize>,
+
+    /// Start the output file with a UTF-8 byte-order mark
+    ///
+    /// For Windows tools that only detect UTF-8 by its BOM
+    /// (EF BB BF). Off by default, as many Unix tools show it
+    /// as stray bytes. With --split-size every part gets one;
+    /// the clipboard copy never does.
+    #[arg(
+        long,
+        visible_alias = "output-bom",
+        default_value_t = false,
+        verbatim_doc_comment
+    )]
+    pub bom: bool,
+
+    /// Print a SHA-256 digest of the complete output
+    ///
+    /// The hash covers the output exactly as written, so two runs
+    /// with identical digests produced byte-identical output.
+    /// Handy for spotting drift in CI; pair with --sort for
+    /// reproducible ordering.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub output_hash: bool,
+
+    /// Also write the digest to <output>.sha256
+    ///
+    /// Uses the sha256sum format, so the output can be checked with:
+    ///   sha256sum -c treeclip_temp.txt.sha256
+    #[arg(
+        long,
+        default_value_t = false,
+        requires = "output_hash",
+        verbatim_doc_comment
+    )]
+    pub hash_sidecar: bool,
+
+    /// Write the output as numbered parts of at most BYTES each
+    ///
+    /// For tools with an input size limit: out.txt becomes
+    /// out.001.txt, out.002.txt, ... Parts end at file
+    /// boundaries, so a file only crosses the cap when it is
+    /// bigger than the cap on its own. Text and markdown only;
+    /// --clipboard copies the first part.
+    ///
+    /// Example:
+    ///   --split-size 100000
+    #[arg(
+        long,
+        value_name = "BYTES",
+        value_parser = clap::builder::RangedU64ValueParser::<u64>::new().range(1..),
+        conflicts_with_all = ["footer_stats", "clipboard_format", "hash_sidecar", "delete"],
+        verbatim_doc_comment
+    )]
+    pub split_size: Option<u64>,
+
+    /// Save the list of included files to FILE
+    ///
+    /// Writes one path per line, relative to the root and in
+    /// output order, so the exact selection can be reviewed,
+    /// versioned, or replayed later.
+    ///
+    /// Example:
+    ///   --input-list-output selection.txt
+    #[arg(
+        long,
+        value_name = "FILE",
+        value_hint = ValueHint::FilePath,
+        verbatim_doc_comment
+    )]
+    pub input_list_output: Option<PathBuf>,
+
+    /// List the files that would be included, without writing output
+    ///
+    /// Runs the traversal and every filter, but reads no file
+    /// content (content filters aside), writes no output file
+    /// and leaves the clipboard alone. The listing goes to
+    /// stdout and progress messages to stderr, so it can be
+    /// piped straight into other tools.
+    ///
+    /// Examples:
+    ///   --dry-run
+    ///   --dry-run --list-format json
+    ///   --dry-run --null-separated | xargs -0 wc -l
+    #[arg(
+        long,
+        conflicts_with_all = ["clipboard", "editor", "page", "preview", "watch_clipboard_only", "split_size"],
+        verbatim_doc_comment
+    )]
+    pub dry_run: bool,
+
+    /// Structure of the --dry-run listing
+    ///
+    /// Plain paths one per line, a JSON array of
+    /// {"path", "bytes"} objects, or CSV rows under a
+    /// 'path,bytes' header.
+    #[arg(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        default_value_t = ListFormat::Plain,
+        requires = "dry_run",
+        verbatim_doc_comment
+    )]
+    pub list_format: ListFormat,
+
+    /// Report the included size of each directory, like du
+    ///
+    /// A --dry-run that sums file sizes per directory instead of
+    /// listing files: every filter and ignore file applies, so
+    /// it shows how big each part of the tree treeclip would
+    /// extract is. Directories are listed largest first, with
+    /// '.' holding the total.
+    ///
+    /// Example:
+    ///   --du -e target | head
+    #[arg(
+        long,
+        conflicts_with_all = ["clipboard", "editor", "page", "preview", "watch_clipboard_only", "split_size", "list_format", "null_separated"],
+        verbatim_doc_comment
+    )]
+    pub du: bool,
+
+    /// End each --dry-run path with NUL instead of a newline
+    ///
+    /// Keeps paths with spaces or newlines intact for
+    /// 'xargs -0'. Only for the plain list format.
+    #[arg(long, requires = "dry_run", verbatim_doc_comment)]
+    pub null_separated: bool,
+
+    /// Text to put before each '==> path' header
+    ///
+    /// Lets headers double as comments when the output is saved
+    /// as a single source file.
+    ///
+    /// Example:
+    ///   --header-prefix '// '   (yields '// ==> src/main.rs')
+    #[arg(
+        long,
+        value_name = "TEXT",
+        allow_hyphen_values = true,
+        verbatim_doc_comment
+    )]
+    pub header_prefix: Option<String>,
+
+    /// Text to put after each '==> path' header
+    ///
+    /// Example:
+    ///   --header-prefix '/* ' --header-suffix ' */'
+    #[arg(
+        long,
+        value_name = "TEXT",
+        allow_hyphen_values = true,
+        verbatim_doc_comment
+    )]
+    pub header_suffix: Option<String>,
+
+    /// Rewrite shown paths with REGEX=REPLACEMENT (repeatable)
+    ///
+    /// Hides project or client names when sharing the output.
+    /// Applies to headers, the table of contents and the tree,
+    /// never to file contents. Masks run in the order given;
+    /// the replacement may use $1 or ${name} for groups.
+    /// Write a literal '=' in REGEX as \x3D.
+    ///
+    /// Example:
+    ///   --mask-paths 'acme-[a-z]+=client' --mask-paths 'v\d+=vN'
+    #[arg(
+        long,
+        value_name = "REGEX=REPLACEMENT",
+        value_parser = parse_path_mask,
+        action = ArgAction::Append,
+        verbatim_doc_comment
+    )]
+    pub mask_paths: Vec<PathMask>,
+
+    /// Wrap each header in the comment syntax of its file's language
+    ///
+    /// Picks '// ', '# ', '<!-- ... -->', etc. from the file extension.
+    /// Files in unknown languages get a plain header.
+    ///
+    /// Note: commented headers are not recognised by 'treeclip unpack'.
+    #[arg(
+        long,
+        default_value_t = false,
+        conflicts_with_all = ["header_prefix", "header_suffix"],
+        verbatim_doc_comment
+    )]
+    pub comment_headers: bool,
+
+    /// Note the last commit that touched each file
+    ///
+    /// Prepends a 'git: <hash> <author>' line to each file's
+    /// content, from 'git log -1'. Files outside a git
+    /// repository, or untracked ones, get no line.
+    ///
+    /// Runs one git process per file, so expect large trees
+    /// to take noticeably longer.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub git_context: bool,
+
+    /// Show changed files as diffs against REV
+    ///
+    /// Each file that differs from REV (default HEAD) gets its
+    /// 'git diff' in place of its content; new untracked files
+    /// show as added. Unchanged files keep their full content.
+    /// Deleted files are not on disk, so they are not shown.
+    ///
+    /// Example:
+    ///   --as-diff=main --format markdown
+    #[arg(
+        long,
+        value_name = "REV",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "HEAD",
+        verbatim_doc_comment
+    )]
+    pub as_diff: Option<String>,
+
+    /// Show paths relative to the enclosing git repository
+    ///
+    /// Walks up from the root to the nearest directory with
+    /// a '.git', so headers stay the same wherever in the
+    /// repository you run treeclip. Outside a repository the
+    /// root is used, with a warning. Ignore files are still
+    /// read from the root.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub relative_to_git_root: bool,
+
+    /// Strip the directory all files share from their headers
+    ///
+    /// For deeply nested projects, e.g. src/main/java/com/example/,
+    /// headers, the TOC and the tree show paths below the
+    /// shared directory, which is written once at the top:
+    ///   Path prefix: src/main/java/com/example/
+    ///
+    /// 'treeclip unpack' restores the full paths. JSON and DOT
+    /// output keep full paths.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub collapse_paths: bool,
+}
+
+impl RunArgs {
+    /// Returns whether hidden files and folders should be left out.
+    pub fn skips_hidden(&self) -> bool {
+        self.skip_hidden && !self.hidden
+    }
+
+    /// Returns whether the output goes to stdout (`--output-path -`).
+    pub fn writes_to_stdout(&self) -> bool {
+        self.output_path.as_deref() == Some(Path::new("-"))
+    }
+}
+
+impl Default for RunArgs {
+    /// Mirrors the clap defaults, before path normalization.
+    fn default() -> Self {
+        Self {
+            input_paths: vec![PathBuf::from(".")],
+            output_path: Some(PathBuf::from(".")),
+            auto_name: false,
+            root: Some(PathBuf::from(".")),
+            exclude: vec![],
+            exclude_dir: vec![],
+            exclude_file: vec![],
+            exclude_from: vec![],
+            ignore_missing: false,
+            exclude_from_output: None,
+            baseline: None,
+            resolve_gitignore_in_parent_dirs: false,
+            exclude_test_files: false,
+            exclude_lockfiles: false,
+            exclude_generated: false,
+            exclude_minified: false,
+            minified_line_length: 300,
+            minified_newline_ratio: 0.001,
+            sort: SortOrder::None,
+            reverse_sort: false,
+            reverse: false,
+            context_first: false,
+            context_files: sort::CONTEXT_FILES
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+            normalize_unicode: false,
+            content_match: None,
+            content_exclude: None,
+            toc: false,
+            tree: false,
+            no_content: false,
+            oneline: false,
+            tree_depth: None,
+            include_empty_dirs_in_tree: false,
+            prune_empty_dirs: false,
+            follow_symlinks: false,
+            follow_symlink_dirs: false,
+            exclude_symlinks: false,
+            exclude_large_dirs: false,
+            prune_dir_over: 1000,
+            symlink_headers: SymlinkHeaders::Link,
+            clipboard: false,
+            clipboard_limit: None,
+            clipboard_append: false,
+            clipboard_html: false,
+            stats: false,
+            stats_fields: StatsField::ALL.to_vec(),
+            stats_align: StatsAlign::Right,
+            sloc: false,
+            language_chart: false,
+            footer_stats: false,
+            editor: false,
+            delete: false,
+            cleanup: false,
+            watch_clipboard_only: false,
+            page: false,
+            wait: false,
+            verbose: false,
+            extensions_report: false,
+            skip_hidden: true,
+            hidden: false,
+            raw: true,
+            fast_mode: false,
+            progress_to_stderr: false,
+            print_config: false,
+            banner: BannerChoice::Random,
+            no_trim: false,
+            blank_lines_between: 1,
+            context_only: false,
+            tab_width: 0,
+            respect_editorconfig: false,
+            escape_control: false,
+            collapse_whitespace_runs: false,
+            max_line_length: None,
+            truncate_marker: transform::TRUNCATION_MARKER.to_string(),
+            wrap: None,
+            content_replace: Vec::new(),
+            content_replace_regex: false,
+            content_max_depth: None,
+            max_files_per_dir: None,
+            min_significant_size: None,
+            max_file_size_by_ext: Vec::new(),
+            format: OutputFormat::Text,
+            clipboard_format: None,
+            read_buffer: 16,
+            timeout: None,
+            min_files: None,
+            preview: None,
+            bom: false,
+            output_hash: false,
+            hash_sidecar: false,
+            split_size: None,
+            input_list_output: None,
+            dry_run: false,
+            list_format: ListFormat::Plain,
+            null_separated: false,
+            du: false,
+            header_prefix: None,
+            header_suffix: None,
+            mask_paths: Vec::new(),
+            comment_headers: false,
+            git_context: false,
+            as_diff: None,
+            relative_to_git_root: false,
+            force: false,
+            collapse_paths: false,
+        }
+    }
+}
+
+/// Arguments for the `unpack` command.
+#[derive(clap::Args)]
+pub struct UnpackArgs {
+    /// treeclip output file to unpack
+    ///
+    /// Not needed when reading from the clipboard.
+    ///
+    /// Example:
+    ///   treeclip unpack treeclip_temp.txt -d ./restored
+    #[arg(
+        value_parser = validate_path,
+        value_name = "FILE",
+        value_hint = ValueHint::FilePath,
+        required_unless_present = "from_clipboard",
+        conflicts_with = "from_clipboard",
+        verbatim_doc_comment
+    )]
+    pub input: Option<PathBuf>,
+
+    /// Read the treeclip dump from the system clipboard
+    ///
+    /// Handy when someone pastes you a treeclip dump: copy it,
+    /// then run:
+    ///   treeclip unpack --from-clipboard --output-dir ./
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub from_clipboard: bool,
+
+    /// Directory to recreate the files in
+    ///
+    /// Missing directories are created. Existing files with the
+    /// same relative path are overwritten.
+    #[arg(
+        short = 'd',
+        long,
+        default_value = ".",
+        value_parser = validate_path,
+        value_name = "DIR",
+        value_hint = ValueHint::DirPath,
+        verbatim_doc_comment
+    )]
+    pub output_dir: PathBuf,
+}
+
+/// Arguments for the `version` command.
+#[derive(clap::Args)]
+pub struct VersionArgs {
+    /// Also show build and platform details
+    ///
+    /// Prints the git commit, rustc version, target triple and
+    /// clipboard backend; paste it into bug reports.
+    #[arg(short, long, default_value_t = false, verbatim_doc_comment)]
+    pub verbose: bool,
+}
+
+/// A find/replace pair applied to file contents (`--content-replace`).
+#[derive(Clone, Debug)]
+pub struct ContentReplace {
+    pub find: String,
+    pub replacement: String,
+}
+
+/// A substitution applied to shown paths (`--mask-paths`).
+#[derive(Clone, Debug)]
+pub struct PathMask {
+    pub pattern: Regex,
+    pub replacement: String,
+}
+
+impl serde::Serialize for ContentReplace {
+    /// Serializes as the `FIND=REPLACE` argument it was parsed from.
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_args!("{}={}", self.find, self.replacement))
+    }
+}
+
+impl serde::Serialize for PathMask {
+    /// Serializes as the `REGEX=REPLACEMENT` argument it was parsed from.
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_args!("{}={}", self.pattern, self.replacement))
+    }
+}
+
+// -------------------------------------------- Private Helper Functions --------------------------------------------
+
+/// Validates that a path string is not empty.
+///
+/// Returns an error message if validation fails.
+fn validate_path(s: &str) -> Result<PathBuf, String> {
+    if s.trim().is_empty() {
+        return Err("Path cannot be empty".to_string());
+    }
+    Ok(PathBuf::from(s))
+}
+
+/// Parses a size in bytes, optionally with a unit: `512`, `50kb`, `1MB`, `2g`.
+fn parse_size(s: &str) -> Result<u64, String> {
+    let s = s.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let number: u64 = number
+        .parse()
+        .map_err(|_| format!("`{s}` is not a size like 512, 50kb or 1mb"))?;
+    let multiplier: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" => 1024,
+        "m" | "mb" => 1024 * 1024,
+        "g" | "gb" => 1024 * 1024 * 1024,
+        other => return Err(format!("unknown size unit `{other}`; use b, kb, mb or gb")),
+    };
+    number
+        .checked_mul(multiplier)
+        .ok_or_else(|| format!("`{s}` is too large"))
+}
+
+/// Parses one `EXT=SIZE` pair of `--max-file-size-by-ext`; a leading `.` is dropped.
+fn parse_ext_size(s: &str) -> Result<(String, u64), String> {
+    let (extension, size) = s
+        .split_once('=')
+        .ok_or_else(|| format!("`{s}` is not in EXT=SIZE form"))?;
+    let extension = extension.trim().trim_start_matches('.');
+    if extension.is_empty() {
+        return Err(format!("`{s}` has no extension before `=`"));
+    }
+    Ok((extension.to_string(), parse_size(size)?))
+}
+
+/// Parses a `REGEX=REPLACEMENT` path mask, split at the first `=`.
+fn parse_path_mask(s: &str) -> Result<PathMask, String> {
+    let (pattern, replacement) = s
+        .split_once('=')
+        .ok_or_else(|| format!("`{s}` is not in REGEX=REPLACEMENT form"))?;
+    if pattern.is_empty() {
+        return Err("the REGEX of a path mask cannot be empty".to_string());
+    }
+    let pattern = Regex::new(pattern).map_err(|e| e.to_string())?;
+    Ok(PathMask {
+        pattern,
+        replacement: replacement.to_string(),
+    })
+}
+
+/// Parses a `FIND=REPLACE` content replacement, split at the first `=`.
+fn parse_content_replace(s: &str) -> Result<ContentReplace, String> {
+    let (find, replacement) = s
+        .split_once('=')
+        .ok_or_else(|| format!("`{s}` is not in FIND=REPLACE form"))?;
+    if find.is_empty() {
+        return Err("the FIND of a content replacement cannot be empty".to_string());
+    }
+    Ok(ContentReplace {
+        find: find.to_string(),
+        replacement: replacement.to_string(),
+    })
+}
+
+/// Parses a ratio between 0 and 1.
+fn parse_ratio(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(ratio) if (0.0..=1.0).contains(&ratio) => Ok(ratio),
+        _ => Err(format!("`{s}` is not a ratio between 0 and 1")),
+    }
+}
+
+#[cfg(test)]
+mod args_tests {
+    use super::*;
+    use crate::cli::{Cli, Commands};
+    use clap::Parser;
+
+    #[test]
+    fn test_validate_path_valid() {
+        let result = validate_path(".");
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), PathBuf::from("."));
+    }
+
+    #[test]
+    fn test_validate_path_empty() {
+        let result = validate_path("");
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("cannot be empty"));
+    }
+
+    #[test]
+    fn test_validate_path_whitespace() {
+        let result = validate_path("   ");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_writes_to_stdout() {
+        let cli = Cli::parse_from(["treeclip", "run", "-o", "-"]);
+        match cli.command {
+            Commands::Run(args) => assert!(args.writes_to_stdout()),
+            _ => panic!("expected run command"),
+        }
+        assert!(!RunArgs::default().writes_to_stdout());
+    }
+
+    #[test]
+    fn test_run_args_default_values() {
+        let cli = Cli::parse_from(["treeclip", "run"]);
+        match cli.command {
+            Commands::Run(args) => {
+                assert_eq!(args.input_paths, vec![PathBuf::from(".")]);
+                assert!(args.output_path.is_some());
+                assert!(!args.clipboard);
+                assert!(!args.stats);
+                assert!(!args.editor);
+                assert!(!args.delete);
+                assert!(!args.verbose);
+                assert!(!args.fast_mode);
+                assert!(args.skip_hidden);
+                assert!(args.exclude.is_empty());
+                assert!(!args.no_trim);
+            }
+            _ => panic!("expected run command"),
+        }
+    }
+
+    #[test]
+    fn test_default_matches_clap_defaults() {
+        let cli = Cli::parse_from(["treeclip", "run"]);
+        let default = RunArgs::default();
+        match cli.command {
+            Commands::Run(args) => {
+                assert_eq!(args.input_paths, default.input_paths);
+                assert_eq!(args.output_path, default.output_path);
+                assert_eq!(args.root, default.root);
+                assert_eq!(args.skip_hidden, default.skip_hidden);
+                assert_eq!(args.raw, default.raw);
+                assert_eq!(args.no_trim, default.no_trim);
+                assert_eq!(args.read_buffer, default.read_buffer);
+                assert_eq!(args.stats_fields, default.stats_fields);
+                assert_eq!(args.stats_align, default.stats_align);
+                assert_eq!(args.blank_lines_between, default.blank_lines_between);
+            }
+            _ => panic!("expected run command"),
+        }
+    }
+
+    #[test]
+    fn test_multiple_input_paths() {
+        let cli = Cli::parse_from(["treeclip", "run", ".", "src", "some/other/input/path"]);
+        match cli.command {
+            Commands::Run(args) => {
+                assert_eq!(args.input_paths.len(), 3);
+                assert_eq!(args.input_paths[0], PathBuf::from("."));
+                assert_eq!(args.input_paths[1], PathBuf::from("src"));
+                assert_eq!(args.input_paths[2], PathBuf::from("some/other/input/path"));
+            }
+            _ => panic!("expected run command"),
+        }
+    }
+
+    #[test]
+    fn test_fast_mode_flag() {
+        let cli = Cli::parse_from(["treeclip", "run", ".", "--fast-mode"]);
+        match cli.command {
+            Commands::Run(args) => {
+                assert!(args.fast_mode);
+            }
+            _ => panic!("expected run command"),
+        }
+    }
+
+    #[test]
+    fn test_multiple_exclude_patterns() {
+        let cli = Cli::parse_from([
+            "treeclip",
+            "run",
+            ".",
             "-e",
             "node_modules",
             "-e",
             "target",
-            "-e",
-            "*.log",
+            "-e",
+            "*.log",
+        ]);
+        match cli.command {
+            Commands::Run(args) => {
+                assert_eq!(args.exclude.len(), 3);
+                assert!(args.exclude.contains(&"node_modules".to_string()));
+                assert!(args.exclude.contains(&"target".to_string()));
+                assert!(args.exclude.contains(&"*.log".to_string()));
+            }
+            _ => panic!("expected run command"),
+        }
+    }
+
+    #[test]
+    fn test_read_buffer_rejects_zero() {
+        let result = Cli::try_parse_from(["treeclip", "run", ".", "--read-buffer", "0"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_comment_headers_conflicts_with_prefix() {
+        let cli = Cli::parse_from(["treeclip", "run", "--header-prefix", "// "]);
+        match cli.command {
+            Commands::Run(args) => assert_eq!(args.header_prefix.as_deref(), Some("// ")),
+            _ => panic!("expected run command"),
+        }
+
+        let result = Cli::try_parse_from([
+            "treeclip",
+            "run",
+            "--comment-headers",
+            "--header-prefix",
+            "# ",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_minified_thresholds() {
+        let cli = Cli::parse_from([
+            "treeclip",
+            "run",
+            "--exclude-minified",
+            "--minified-newline-ratio",
+            "0.01",
+        ]);
+        match cli.command {
+            Commands::Run(args) => {
+                assert_eq!(args.minified_line_length, 300);
+                assert_eq!(args.minified_newline_ratio, 0.01);
+            }
+            _ => panic!("expected run command"),
+        }
+
+        let result = Cli::try_parse_from([
+            "treeclip",
+            "run",
+            "--exclude-minified",
+            "--minified-newline-ratio",
+            "2",
+        ]);
+        assert!(result.is_err());
+        let result = Cli::try_parse_from(["treeclip", "run", "--minified-line-length", "80"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_split_size_conflicts() {
+        let result = Cli::try_parse_from(["treeclip", "run", "--split-size", "0"]);
+        assert!(result.is_err());
+
+        let result =
+            Cli::try_parse_from(["treeclip", "run", "--split-size", "1000", "--footer-stats"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_clipboard_limit_requires_clipboard() {
+        let result = Cli::try_parse_from(["treeclip", "run", "--clipboard-limit", "4096"]);
+        assert!(result.is_err());
+
+        let result = Cli::try_parse_from(["treeclip", "run", "-c", "--clipboard-limit", "10"]);
+        assert!(result.is_err());
+
+        let cli = Cli::parse_from(["treeclip", "run", "-c", "--clipboard-limit", "4096"]);
+        match cli.command {
+            Commands::Run(args) => assert_eq!(args.clipboard_limit, Some(4096)),
+            _ => panic!("expected run command"),
+        }
+    }
+
+    #[test]
+    fn test_symlink_headers_requires_follow_symlinks() {
+        let result = Cli::try_parse_from(["treeclip", "run", "--symlink-headers", "target"]);
+        assert!(result.is_err());
+
+        let cli = Cli::parse_from([
+            "treeclip",
+            "run",
+            "--follow-symlinks",
+            "--symlink-headers",
+            "target",
+        ]);
+        match cli.command {
+            Commands::Run(args) => assert_eq!(args.symlink_headers, SymlinkHeaders::Target),
+            _ => panic!("expected run command"),
+        }
+    }
+
+    #[test]
+    fn test_preview_default_line_count() {
+        let parse = |args: &[&str]| match Cli::parse_from(args).command {
+            Commands::Run(args) => args.preview,
+            _ => panic!("expected run command"),
+        };
+
+        assert_eq!(parse(&["treeclip", "run"]), None);
+        assert_eq!(parse(&["treeclip", "run", "--preview"]), Some(20));
+        assert_eq!(parse(&["treeclip", "run", "--preview", "5"]), Some(5));
+    }
+
+    #[test]
+    fn test_banner_defaults_to_random() {
+        let parse = |args: &[&str]| match Cli::parse_from(args).command {
+            Commands::Run(args) => args.banner,
+            _ => panic!("expected run command"),
+        };
+
+        assert_eq!(parse(&["treeclip", "run"]), BannerChoice::Random);
+        assert_eq!(
+            parse(&["treeclip", "run", "--banner", "none"]),
+            BannerChoice::None
+        );
+    }
+
+    #[test]
+    fn test_clipboard_format_requires_clipboard() {
+        let result = Cli::try_parse_from(["treeclip", "run", "--clipboard-format", "json"]);
+        assert!(result.is_err());
+
+        let cli = Cli::parse_from([
+            "treeclip",
+            "run",
+            "--format",
+            "markdown",
+            "-c",
+            "--clipboard-format",
+            "text",
         ]);
         match cli.command {
             Commands::Run(args) => {
-                assert_eq!(args.exclude.len(), 3);
-                assert!(args.exclude.contains(&"node_modules".to_string()));
-                assert!(args.exclude.contains(&"target".to_string()));
-                assert!(args.exclude.contains(&"*.log".to_string()));
+                assert_eq!(args.format, OutputFormat::Markdown);
+                assert_eq!(args.clipboard_format, Some(OutputFormat::Text));
+            }
+            _ => panic!("expected run command"),
+        }
+    }
+
+    #[test]
+    fn test_hidden_flag_includes_hidden_files() {
+        let parse = |args: &[&str]| match Cli::parse_from(args).command {
+            Commands::Run(args) => args.skips_hidden(),
+            _ => panic!("expected run command"),
+        };
+
+        assert!(parse(&["treeclip", "run"]));
+        assert!(!parse(&["treeclip", "run", "--hidden"]));
+        assert!(!parse(&["treeclip", "run", "--no-skip-hidden"]));
+        assert!(!parse(&["treeclip", "run", "-H", "--hidden"]));
+        assert!(parse(&["treeclip", "run", "--hidden", "-H"]));
+    }
+
+    #[test]
+    fn test_empty_dirs_in_tree_last_flag_wins() {
+        let parse = |args: &[&str]| match Cli::parse_from(args).command {
+            Commands::Run(args) => args.include_empty_dirs_in_tree,
+            _ => panic!("expected run command"),
+        };
+
+        assert!(!parse(&["treeclip", "run", "--tree"]));
+        assert!(parse(&["treeclip", "run", "--include-empty-dirs-in-tree"]));
+        assert!(!parse(&[
+            "treeclip",
+            "run",
+            "--include-empty-dirs-in-tree",
+            "--prune-empty-dirs"
+        ]));
+        assert!(parse(&[
+            "treeclip",
+            "run",
+            "--prune-empty-dirs",
+            "--include-empty-dirs-in-tree"
+        ]));
+    }
+
+    #[test]
+    fn test_content_filter_parsing() {
+        let cli = Cli::parse_from([
+            "treeclip",
+            "run",
+            "--content-match",
+            r"fn \w+",
+            "--content-exclude",
+            "TODO",
+        ]);
+        match cli.command {
+            Commands::Run(args) => {
+                assert!(args.content_match.is_some_and(|re| re.is_match("fn main")));
+                assert!(args.content_exclude.is_some_and(|re| re.is_match("TODO")));
+            }
+            _ => panic!("expected run command"),
+        }
+
+        let result = Cli::try_parse_from(["treeclip", "run", "--content-match", "("]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_force_aliases() {
+        for flag in ["--force", "--yes", "-y"] {
+            let cli = Cli::parse_from(["treeclip", "run", flag]);
+            match cli.command {
+                Commands::Run(args) => assert!(args.force),
+                _ => panic!("expected run command"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_prepend_tree_is_tree() {
+        let cli = Cli::parse_from(["treeclip", "run", "--prepend-tree"]);
+        match cli.command {
+            Commands::Run(args) => assert!(args.tree && !args.no_content),
+            _ => panic!("expected run command"),
+        }
+    }
+
+    #[test]
+    fn test_sort_parsing() {
+        let cli = Cli::parse_from(["treeclip", "run", "--sort", "size"]);
+        match cli.command {
+            Commands::Run(args) => assert_eq!(args.sort, SortOrder::Size),
+            _ => panic!("expected run command"),
+        }
+
+        let cli = Cli::parse_from(["treeclip", "run", "--sort", "mtime", "--reverse-sort"]);
+        match cli.command {
+            Commands::Run(args) => {
+                assert_eq!(args.sort, SortOrder::Modified);
+                assert!(args.reverse_sort);
+            }
+            _ => panic!("expected run command"),
+        }
+
+        let cli = Cli::parse_from(["treeclip", "run", "--reverse"]);
+        match cli.command {
+            Commands::Run(args) => assert!(args.reverse && args.sort == SortOrder::None),
+            _ => panic!("expected run command"),
+        }
+        assert!(
+            Cli::try_parse_from([
+                "treeclip",
+                "run",
+                "--sort",
+                "path",
+                "--reverse",
+                "--reverse-sort"
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_stats_fields_parsing() {
+        let cli = Cli::parse_from(["treeclip", "run", "--stats", "--stats-fields", "size,lines"]);
+        match cli.command {
+            Commands::Run(args) => {
+                assert_eq!(args.stats_fields, vec![StatsField::Size, StatsField::Lines]);
+            }
+            _ => panic!("expected run command"),
+        }
+    }
+
+    #[test]
+    fn test_stats_fields_applies_to_footer_stats() {
+        let cli = Cli::parse_from([
+            "treeclip",
+            "run",
+            "--footer-stats",
+            "--stats-fields",
+            "size",
+        ]);
+        match cli.command {
+            Commands::Run(args) => assert_eq!(args.stats_fields, vec![StatsField::Size]),
+            _ => panic!("expected run command"),
+        }
+        let both = [
+            "treeclip",
+            "run",
+            "--stats",
+            "--footer-stats",
+            "--stats-fields",
+            "lines",
+        ];
+        assert!(Cli::try_parse_from(both).is_ok());
+        assert!(Cli::try_parse_from(["treeclip", "run", "--stats-fields", "size"]).is_err());
+    }
+
+    #[test]
+    fn test_stats_align_parsing() {
+        let cli = Cli::parse_from(["treeclip", "run", "--stats", "--stats-align", "left"]);
+        match cli.command {
+            Commands::Run(args) => assert_eq!(args.stats_align, StatsAlign::Left),
+            _ => panic!("expected run command"),
+        }
+    }
+
+    #[test]
+    fn test_mask_paths_parsing() {
+        let cli = Cli::parse_from([
+            "treeclip",
+            "run",
+            "--mask-paths",
+            "acme-(\\w+)=client-$1",
+            "--mask-paths",
+            "secret=",
+        ]);
+        match cli.command {
+            Commands::Run(args) => {
+                let masks: Vec<(&str, &str)> = args
+                    .mask_paths
+                    .iter()
+                    .map(|mask| (mask.pattern.as_str(), mask.replacement.as_str()))
+                    .collect();
+                assert_eq!(masks, [("acme-(\\w+)", "client-$1"), ("secret", "")]);
+            }
+            _ => panic!("expected run command"),
+        }
+
+        assert!(Cli::try_parse_from(["treeclip", "run", "--mask-paths", "no-separator"]).is_err());
+        assert!(Cli::try_parse_from(["treeclip", "run", "--mask-paths", "=x"]).is_err());
+        assert!(Cli::try_parse_from(["treeclip", "run", "--mask-paths", "(=x"]).is_err());
+    }
+
+    #[test]
+    fn test_cleanup_requires_clipboard_without_editor() {
+        assert!(Cli::try_parse_from(["treeclip", "run", "--cleanup"]).is_err());
+        assert!(Cli::try_parse_from(["treeclip", "run", "-c", "--cleanup", "--editor"]).is_err());
+        assert!(Cli::try_parse_from(["treeclip", "run", "-c", "--cleanup"]).is_ok());
+    }
+
+    #[test]
+    fn test_watch_clipboard_only_writes_no_file() {
+        let watch = ["treeclip", "run", "--watch-clipboard-only"];
+        assert!(Cli::try_parse_from(watch).is_ok());
+        assert!(Cli::try_parse_from([&watch[..], &["-o", "out.txt"]].concat()).is_err());
+        assert!(Cli::try_parse_from([&watch[..], &["--auto-name"]].concat()).is_err());
+    }
+
+    #[test]
+    fn test_context_files_replace_the_built_in_list() {
+        let cli = Cli::parse_from(["treeclip", "run", "--context-first"]);
+        match cli.command {
+            Commands::Run(args) => assert_eq!(args.context_files, RunArgs::default().context_files),
+            _ => panic!("expected run command"),
+        }
+
+        let cli = Cli::parse_from([
+            "treeclip",
+            "run",
+            "--context-first",
+            "--context-files",
+            "README.md,ARCHITECTURE.md",
+        ]);
+        match cli.command {
+            Commands::Run(args) => assert_eq!(args.context_files, ["README.md", "ARCHITECTURE.md"]),
+            _ => panic!("expected run command"),
+        }
+
+        assert!(Cli::try_parse_from(["treeclip", "run", "--context-files", "a"]).is_err());
+    }
+
+    #[test]
+    fn test_as_diff_defaults_to_head() {
+        let cli = Cli::parse_from(["treeclip", "run", "--as-diff", "src"]);
+        match cli.command {
+            Commands::Run(args) => {
+                assert_eq!(args.as_diff.as_deref(), Some("HEAD"));
+                assert_eq!(args.input_paths, [PathBuf::from("src")]);
+            }
+            _ => panic!("expected run command"),
+        }
+
+        let cli = Cli::parse_from(["treeclip", "run", "--as-diff=main"]);
+        match cli.command {
+            Commands::Run(args) => assert_eq!(args.as_diff.as_deref(), Some("main")),
+            _ => panic!("expected run command"),
+        }
+    }
+
+    #[test]
+    fn test_max_file_size_by_ext_parses_units() {
+        let cli = Cli::parse_from([
+            "treeclip",
+            "run",
+            "--max-file-size-by-ext",
+            "json=50kb,.RS=1MB",
+            "--max-file-size-by-ext",
+            "lock=300",
+        ]);
+        match cli.command {
+            Commands::Run(args) => assert_eq!(
+                args.max_file_size_by_ext,
+                [
+                    ("json".to_string(), 50 * 1024),
+                    ("RS".to_string(), 1024 * 1024),
+                    ("lock".to_string(), 300),
+                ]
+            ),
+            _ => panic!("expected run command"),
+        }
+
+        assert!(parse_ext_size("json").is_err());
+        assert!(parse_ext_size("=1kb").is_err());
+        assert!(parse_ext_size("json=1tb").is_err());
+        assert!(parse_ext_size("json=kb").is_err());
+    }
+
+    #[test]
+    fn test_prune_dir_over_requires_exclude_large_dirs() {
+        assert!(Cli::try_parse_from(["treeclip", "run", "--prune-dir-over", "50"]).is_err());
+        assert!(
+            Cli::try_parse_from([
+                "treeclip",
+                "run",
+                "--exclude-large-dirs",
+                "--prune-dir-over",
+                "0"
+            ])
+            .is_err()
+        );
+
+        let cli = Cli::parse_from(["treeclip", "run", "--exclude-large-dirs"]);
+        match cli.command {
+            Commands::Run(args) => assert_eq!(args.prune_dir_over, 1000),
+            _ => panic!("expected run command"),
+        }
+    }
+
+    #[test]
+    fn test_dry_run_list_format_parsing() {
+        let cli = Cli::parse_from([
+            "treeclip",
+            "run",
+            "--dry-run",
+            "--list-format",
+            "csv",
+            "--null-separated",
+        ]);
+        match cli.command {
+            Commands::Run(args) => {
+                assert!(args.dry_run);
+                assert_eq!(args.list_format, ListFormat::Csv);
+                assert!(args.null_separated);
+            }
+            _ => panic!("expected run command"),
+        }
+
+        // The listing options only shape --dry-run output
+        let result = Cli::try_parse_from(["treeclip", "run", "--list-format", "json"]);
+        assert!(result.is_err());
+        let result = Cli::try_parse_from(["treeclip", "run", "--dry-run", "--clipboard"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_du_conflicts_with_listing_options() {
+        let cli = Cli::parse_from(["treeclip", "run", "--du"]);
+        match cli.command {
+            Commands::Run(args) => assert!(args.du),
+            _ => panic!("expected run command"),
+        }
+
+        let result = Cli::try_parse_from(["treeclip", "run", "--du", "--null-separated"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_min_files_parsing() {
+        let cli = Cli::parse_from(["treeclip", "run", "--min-files", "10"]);
+        match cli.command {
+            Commands::Run(args) => assert_eq!(args.min_files, Some(10)),
+            _ => panic!("expected run command"),
+        }
+
+        let result = Cli::try_parse_from(["treeclip", "run", "--min-files", "0"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_content_replace_parsing() {
+        let cli = Cli::parse_from([
+            "treeclip",
+            "run",
+            "--content-replace",
+            "a=b=c",
+            "--content-replace",
+            "tmp=",
+        ]);
+        match cli.command {
+            Commands::Run(args) => {
+                let pairs: Vec<(&str, &str)> = args
+                    .content_replace
+                    .iter()
+                    .map(|pair| (pair.find.as_str(), pair.replacement.as_str()))
+                    .collect();
+                assert_eq!(pairs, [("a", "b=c"), ("tmp", "")]);
+                assert!(!args.content_replace_regex);
             }
+            _ => panic!("expected run command"),
         }
+
+        assert!(Cli::try_parse_from(["treeclip", "run", "--content-replace", "=x"]).is_err());
+        assert!(Cli::try_parse_from(["treeclip", "run", "--content-replace-regex"]).is_err());
+    }
+
+    #[test]
+    fn test_sloc_requires_stats() {
+        assert!(Cli::try_parse_from(["treeclip", "run", "--sloc"]).is_err());
+        assert!(Cli::try_parse_from(["treeclip", "run", "--stats", "--sloc"]).is_ok());
+        assert!(Cli::try_parse_from(["treeclip", "run", "--language-chart"]).is_err());
+        assert!(Cli::try_parse_from(["treeclip", "run", "--stats", "--language-chart"]).is_ok());
+    }
+
+    #[test]
+    fn test_stats_fields_rejects_unknown() {
+        let result =
+            Cli::try_parse_from(["treeclip", "run", "--stats", "--stats-fields", "size,bogus"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_unpack_requires_input_or_clipboard() {
+        assert!(Cli::try_parse_from(["treeclip", "unpack"]).is_err());
+        assert!(Cli::try_parse_from(["treeclip", "unpack", "dump.txt"]).is_ok());
+        assert!(Cli::try_parse_from(["treeclip", "unpack", "--from-clipboard"]).is_ok());
+        assert!(
+            Cli::try_parse_from(["treeclip", "unpack", "dump.txt", "--from-clipboard"]).is_err()
+        );
     }
 
     #[test]
     fn test_delete_requires_editor() {
         // This should fail because --delete requires --editor
-        let result = Cli::try_parse_from(&["treeclip", "run", ".", "--delete"]);
+        let result = Cli::try_parse_from(["treeclip", "run", ".", "--delete"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_wait_requires_editor() {
+        let result = Cli::try_parse_from(["treeclip", "run", "--wait"]);
         assert!(result.is_err());
+
+        let cli = Cli::parse_from(["treeclip", "run", "--editor", "--wait", "--delete"]);
+        match cli.command {
+            Commands::Run(args) => assert!(args.wait && args.delete),
+            _ => panic!("expected run command"),
+        }
     }
 
     #[test]
     fn test_delete_with_editor_works() {
-        let cli = Cli::parse_from(&["treeclip", "run", ".", "--editor", "--delete"]);
+        let cli = Cli::parse_from(["treeclip", "run", ".", "--editor", "--delete"]);
         match cli.command {
             Commands::Run(args) => {
                 assert!(args.editor);
                 assert!(args.delete);
             }
+            _ => panic!("expected run command"),
+        }
+    }
+
+    #[test]
+    fn test_extensions_report_requires_verbose() {
+        let result = Cli::try_parse_from(["treeclip", "run", "--extensions-report"]);
+        assert!(result.is_err());
+
+        let cli = Cli::parse_from(["treeclip", "run", "-v", "--extensions-report"]);
+        match cli.command {
+            Commands::Run(args) => assert!(args.extensions_report),
+            _ => panic!("expected run command"),
         }
     }
 
     #[test]
     fn test_verbose_and_fast_mode_combination() {
         // These can both be enabled (verbose will be ignored in fast mode)
-        let cli = Cli::parse_from(&["treeclip", "run", ".", "--verbose", "--fast-mode"]);
+        let cli = Cli::parse_from(["treeclip", "run", ".", "--verbose", "--fast-mode"]);
         match cli.command {
             Commands::Run(args) => {
                 assert!(args.verbose);
                 assert!(args.fast_mode);
             }
+            _ => panic!("expected run command"),
         }
     }
 
     #[test]
     fn test_clipboard_and_stats_combination() {
-        let cli = Cli::parse_from(&["treeclip", "run", ".", "--clipboard", "--stats"]);
+        let cli = Cli::parse_from(["treeclip", "run", ".", "--clipboard", "--stats"]);
         match cli.command {
             Commands::Run(args) => {
                 assert!(args.clipboard);
                 assert!(args.stats);
             }
+            _ => panic!("expected run command"),
         }
     }
 }
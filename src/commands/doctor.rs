@@ -0,0 +1,25 @@
+//! doctor - Execution logic for the doctor command, reporting on platform integrations.
+
+use crate::core::doctor::{self, Status};
+
+/// Executes the doctor command: runs every check and prints the report.
+///
+/// # Errors
+///
+/// Returns an error if any check failed, so scripts can rely on the exit status.
+pub fn execute() -> anyhow::Result<()> {
+    let checks = doctor::run_checks();
+    println!("{}", doctor::render(&checks));
+
+    let failed = checks
+        .iter()
+        .filter(|check| check.status == Status::Fail)
+        .count();
+    if failed > 0 {
+        return Err(anyhow::anyhow!(
+            "{failed} of {} checks failed",
+            checks.len()
+        ));
+    }
+    Ok(())
+}
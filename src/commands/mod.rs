@@ -1,2 +1,5 @@
 pub mod args;
+pub mod doctor;
 pub mod run;
+pub mod unpack;
+pub mod version;
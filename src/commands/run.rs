@@ -1,38 +1,200 @@
 //! run - Main execution logic for the run command, orchestrating all operations.
 
 use super::args::RunArgs;
+use crate::core::output::{
+    self, OutputSink, format, format::OutputFormat, listing, listing::ListFormat,
+};
+use crate::core::ui::animations::statusln;
 use crate::core::ui::{animations, banner, formatter, messages};
-use crate::core::{clipboard, editor, traversal::walker};
+use crate::core::{
+    clipboard, config, editor, errors, git, traversal::walker, unpack, utils, watch,
+};
+use anyhow::Context;
+use colored::Colorize;
+use std::collections::{BTreeMap, HashSet};
+use std::io::{self, IsTerminal, Write};
 use std::path::{Path, PathBuf};
-use std::{env, fs};
+use std::time::{Duration, Instant};
+use std::{env, fs, process};
+
+/// Output file name used when no `--output-path` is given; always safe to overwrite.
+const DEFAULT_OUTPUT_NAME: &str = "treeclip_temp.txt";
+
+/// Exit status when `--timeout` cut the run short (the same as `timeout(1)`).
+const TIMEOUT_EXIT_CODE: i32 = 124;
+
+/// Exit status when fewer files matched than `--min-files` asks for.
+const MIN_FILES_EXIT_CODE: i32 = 3;
+
+/// Environment variable holding project-wide exclude patterns, one per line or
+/// comma-separated.
+const EXCLUDE_ENV: &str = "TREECLIP_EXCLUDE";
+
+/// Emojis cycled through by the verbose progress counter.
+const TREE_EMOJIS: [&str; 6] = ["🌱", "🌿", "🍃", "🌳", "🌲", "🎄"];
 
 /// Executes the main treeclip run command with the provided arguments.
 ///
 /// This orchestrates the entire flow: configuration, traversal, clipboard, stats, and editor.
 pub fn execute(mut args: RunArgs) -> anyhow::Result<()> {
+    if args.print_config {
+        add_env_excludes(&mut args);
+        print!("{}", config::to_toml(&args)?);
+        return Ok(());
+    }
+
+    // A size report is a dry run that sums the listing by directory
+    if args.du {
+        args.dry_run = true;
+    }
+
+    // With `-o -` stdout carries the output, and with `--dry-run` the listing, so
+    // nothing else may be printed there
+    let to_stdout = args.writes_to_stdout();
+    animations::set_progress_to_stderr(args.progress_to_stderr || to_stdout || args.dry_run);
+
+    if args.split_size.is_some()
+        && !matches!(args.format, OutputFormat::Text | OutputFormat::Markdown)
+    {
+        return Err(anyhow::anyhow!(
+            "--split-size only works with text and markdown output"
+        ));
+    }
+
+    if args.null_separated && args.list_format != ListFormat::Plain {
+        return Err(anyhow::anyhow!(
+            "--null-separated only works with --list-format plain"
+        ));
+    }
+
+    // Only the selection is listed, so no content needs to be read
+    if args.dry_run {
+        args.no_content = true;
+        args.oneline = false;
+    }
+
     // Display welcome banner (respects fast mode)
     if !args.fast_mode {
-        banner::print_welcome();
+        banner::print_welcome(args.banner);
     }
 
     // Normalize paths to absolute paths
     normalize_paths(&mut args)?;
 
-    let root = args.root.as_ref().unwrap();
-    let inputs = &args.input_paths;
-    let output = args.output_path.as_ref().unwrap();
+    // Report every missing input up front, before anything is written
+    utils::validate_paths(&args.input_paths)?;
+
+    add_env_excludes(&mut args);
 
     // Log configuration
     log_config(&args)?;
 
+    // Written to a spool file first, so stats and preview can still read the output
+    let stdout_spool = if to_stdout {
+        Some(spool_stdout_output(&mut args)?)
+    } else {
+        None
+    };
+
+    let root = args.root.as_ref().unwrap();
+    let inputs = &args.input_paths;
+    let mut output = args.output_path.as_ref().unwrap();
+
+    // The deadline covers the whole run, not each input path
+    let deadline = args
+        .timeout
+        .map(|seconds| Instant::now() + Duration::from_secs(seconds));
+
+    // Shared by every input path, so looked up once
+    let header_root = if args.relative_to_git_root {
+        let found = git::find_root(root);
+        if found.is_none() {
+            eprintln!(
+                "{}",
+                messages::Messages::not_in_git_repo(&root.display().to_string())
+            );
+        }
+        found
+    } else {
+        None
+    };
+
+    if let Some(rev) = &args.as_diff
+        && !git::is_commit(root, rev)
+    {
+        return Err(anyhow::anyhow!(
+            "--as-diff: '{rev}' is not a commit in the repository at {}",
+            root.display()
+        ));
+    }
+
+    if let Some(baseline) = &args.baseline
+        && !baseline.is_dir()
+    {
+        return Err(anyhow::anyhow!(
+            "--baseline: {} is not a directory",
+            baseline.display()
+        ));
+    }
+
+    // Read before the output is opened, which may truncate this very file
+    let shared = match &args.exclude_from_output {
+        Some(previous) => read_shared_paths(previous)?,
+        None => HashSet::new(),
+    };
+
+    if args.dry_run {
+        return list_selection(&args, root, header_root.as_deref(), &shared);
+    }
+
+    if args.watch_clipboard_only {
+        return watch_clipboard(&args, root, header_root.as_deref(), &shared);
+    }
+
+    if stdout_spool.is_none() {
+        match args.split_size {
+            Some(_) => confirm_overwrite(&args, &output::part_path(output, 1))?,
+            None => confirm_overwrite(&args, output)?,
+        }
+    }
+
+    // Every input path appends to the same output, opened once for the whole run
+    let retargeted;
+    let mut sink = match open_sink(&args, output) {
+        Ok(sink) => sink,
+        // Created as a directory after the check in `normalize_paths`
+        Err(_) if output.is_dir() => {
+            retargeted = output.join(DEFAULT_OUTPUT_NAME);
+            output = &retargeted;
+            open_sink(&args, output)?
+        }
+        Err(e) => return Err(e),
+    };
+    sink.write_marker()?;
+
     // Execute traversal for each input path
     let mut any_success = false;
+    let mut summary = walker::TraversalSummary::default();
     for input in inputs {
-        match execute_traversal(&args, root, input, output) {
-            Ok(()) => any_success = true,
+        match execute_traversal(
+            &args,
+            root,
+            header_root.as_deref(),
+            &shared,
+            input,
+            &mut sink,
+            deadline,
+        ) {
+            Ok(input_summary) => {
+                any_success = true;
+                summary += input_summary;
+                if summary.timed_out {
+                    break;
+                }
+            }
             Err(e) => {
                 // If it's a "No files found" error, continue to next path
-                if e.to_string().contains("No files found") {
+                if is_no_files_found(&e) {
                     eprintln!("Warning: No files found in directory: {}", input.display());
                     continue;
                 } else {
@@ -42,6 +204,17 @@ pub fn execute(mut args: RunArgs) -> anyhow::Result<()> {
         }
     }
 
+    // Checked before the empty-run error, so none and too few get the same status
+    if too_few_files(&args, summary.files.len()) {
+        let mut stdout = io::stdout().lock();
+        let copy_to = stdout_spool
+            .is_some()
+            .then_some(&mut stdout as &mut dyn Write);
+        finish_too_few(sink, output, copy_to)?;
+        drop(stdout_spool);
+        process::exit(MIN_FILES_EXIT_CODE);
+    }
+
     // If no directories had any files, return an error
     if !any_success {
         return Err(anyhow::anyhow!(
@@ -49,12 +222,78 @@ pub fn execute(mut args: RunArgs) -> anyhow::Result<()> {
         ));
     }
 
+    if args.footer_stats {
+        write_footer_stats(&args, &summary.content_stats, &mut sink)?;
+    }
+
+    let finished = sink.finish()?;
+    if stdout_spool.is_some() {
+        copy_to_stdout(output)?;
+    }
+    report_read_errors(&summary.read_errors);
+    report_symlink_loops(&summary.symlink_loops);
+    report_dangling_symlinks(&summary.dangling_symlinks);
+    report_pruned_dirs(&args, &summary.pruned_dirs);
+    report_omitted_per_dir(&summary.omitted_per_dir);
+    if args.collapse_whitespace_runs {
+        report_whitespace_collapse(&summary);
+    }
+    if args.verbose && !args.content_replace.is_empty() {
+        statusln!(
+            "{}",
+            messages::Messages::content_replaced(summary.content_replacements)
+        );
+    }
+    if let Some(baseline) = &args.baseline {
+        statusln!(
+            "{}",
+            messages::Messages::baseline_compared(
+                summary.baseline_added,
+                summary.baseline_changed,
+                summary.baseline_unchanged,
+                &baseline.display().to_string()
+            )
+        );
+    }
+    if let (true, Some(seconds)) = (summary.timed_out, args.timeout) {
+        eprintln!("{}", messages::Messages::timeout_reached(seconds));
+    }
+    if args.extensions_report {
+        report_unrecognized_extensions(&finished.unrecognized_extensions);
+    }
+    if let Some(digest) = &finished.digest {
+        report_output_hash(&args, output, digest)?;
+    }
+
+    // Later steps look at the first part
+    if let Some(first) = finished.parts.first() {
+        report_parts(&args, &finished.parts);
+        output = first;
+    }
+
+    if let Some(list_path) = &args.input_list_output {
+        write_input_list(list_path, &summary.files)?;
+    }
+
+    if let Some(lines) = args.preview {
+        show_preview(output, lines)?;
+    }
+
     // Handle clipboard operations
-    handle_clipboard(&args, output)?;
+    handle_clipboard(&args, output, finished.mirror)?;
+
+    if args.page {
+        page_output(&args, output);
+    }
+
+    // Reached only once the copy succeeded
+    if args.cleanup {
+        cleanup_output(&args, output)?;
+    }
 
     // Show statistics if requested
     if args.stats {
-        show_stats_section(&args, output)?;
+        show_stats_section(&args, &summary);
     }
 
     // Handle editor operations
@@ -65,11 +304,34 @@ pub fn execute(mut args: RunArgs) -> anyhow::Result<()> {
         banner::print_goodbye();
     }
 
+    if summary.timed_out {
+        process::exit(TIMEOUT_EXIT_CODE);
+    }
+
     Ok(())
 }
 
 // -------------------------------------------- Private Helper Functions --------------------------------------------
 
+/// Adds the patterns of [`EXCLUDE_ENV`] to the excludes.
+fn add_env_excludes(args: &mut RunArgs) {
+    // Placed before the `-e` patterns, which come later and so win any conflict
+    if let Ok(value) = env::var(EXCLUDE_ENV) {
+        args.exclude.splice(0..0, env_exclude_patterns(&value));
+    }
+}
+
+/// Splits the value of [`EXCLUDE_ENV`] into one entry per non-blank line; commas
+/// within a line are split later, as for `-e`.
+fn env_exclude_patterns(value: &str) -> Vec<String> {
+    value
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
 /// Normalizes all path arguments to absolute paths.
 fn normalize_paths(args: &mut RunArgs) -> anyhow::Result<()> {
     // Normalize input paths
@@ -85,10 +347,18 @@ fn normalize_paths(args: &mut RunArgs) -> anyhow::Result<()> {
     args.input_paths = normalized_input_paths;
 
     // Normalize output path
+    let default_name = if args.auto_name {
+        auto_output_name(&args.input_paths)
+    } else {
+        None
+    }
+    .unwrap_or_else(|| DEFAULT_OUTPUT_NAME.to_string());
     args.output_path = match &args.output_path {
-        Some(path) if path == Path::new(".") => Some(PathBuf::from("./treeclip_temp.txt")),
+        Some(path) if path == Path::new(".") => Some(Path::new(".").join(default_name)),
+        // Opening a directory as a file fails, so write the default file inside it
+        Some(path) if path.is_dir() => Some(path.join(DEFAULT_OUTPUT_NAME)),
         Some(path) => Some(path.clone()),
-        None => Some(PathBuf::from("./treeclip_temp.txt")),
+        None => Some(Path::new(".").join(default_name)),
     };
 
     // Normalize root path
@@ -101,85 +371,753 @@ fn normalize_paths(args: &mut RunArgs) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Returns the output name for `--auto-name`, e.g. `my-project.txt` for `my-project/`.
+///
+/// `None` when the first input has no name of its own, such as `/`.
+fn auto_output_name(input_paths: &[PathBuf]) -> Option<String> {
+    let input = std::path::absolute(input_paths.first()?).ok()?;
+    let name = input.file_name()?.to_string_lossy();
+    Some(format!("{name}.txt"))
+}
+
+/// Opens the output sink with the options that shape its content.
+fn open_sink(args: &RunArgs, output: &Path) -> anyhow::Result<OutputSink> {
+    let sink = match args.split_size {
+        Some(cap) => OutputSink::create_split(output, cap)?,
+        None => OutputSink::create(output)?,
+    };
+    Ok(sink
+        .with_hash(args.output_hash)
+        .with_bom(args.bom)
+        .with_format(args.format)
+        .with_mirror(clipboard_mirror_format(args))
+        .with_blank_lines(args.blank_lines_between))
+}
+
+/// Copies the inputs to the clipboard, then again after every change until the
+/// process is interrupted (`--watch-clipboard-only`).
+///
+/// One clipboard handle is kept for the whole session, so on Linux the content stays
+/// available without a clipboard manager. A failed first copy ends the run; later
+/// failures, such as a file removed mid-read, only warn.
+fn watch_clipboard(
+    args: &RunArgs,
+    root: &Path,
+    header_root: Option<&Path>,
+    shared: &HashSet<PathBuf>,
+) -> anyhow::Result<()> {
+    // Only read for a split copy, which --clipboard-limit would need
+    let output = args.output_path.as_deref().unwrap();
+    let mut clip = clipboard::Clipboard::new(output)?;
+
+    let walkers: Vec<walker::Walker> = args
+        .input_paths
+        .iter()
+        .map(|input| walker::Walker::new(root, input, Path::new(""), &args.exclude))
+        .collect();
+    let mut last = watch::snapshot(&walkers, args);
+    let (content, files) = render_snapshot(args, root, header_root, shared)?;
+    clip = clip.with_text(Some(content));
+    clip.set_clipboard()?;
+    statusln!("{}", messages::Messages::watch_copied(files));
+
+    loop {
+        if !args.fast_mode {
+            statusln!("{}", messages::Messages::watching_for_changes());
+        }
+        last = watch::wait_for_change(&walkers, args, last);
+
+        let copied = match render_snapshot(args, root, header_root, shared) {
+            Ok((content, files)) => {
+                clip = clip.with_text(Some(content));
+                clip.set_clipboard().map(|_| files)
+            }
+            Err(e) => Err(e),
+        };
+        match copied {
+            Ok(files) => statusln!("{}", messages::Messages::watch_copied(files)),
+            Err(e) => eprintln!(
+                "{}",
+                messages::Messages::watch_refresh_failed(&format!("{e:#}"))
+            ),
+        }
+    }
+}
+
+/// Renders every input into memory, and returns the clipboard text and the number of
+/// files in it.
+fn render_snapshot(
+    args: &RunArgs,
+    root: &Path,
+    header_root: Option<&Path>,
+    shared: &HashSet<PathBuf>,
+) -> anyhow::Result<(String, usize)> {
+    let deadline = args
+        .timeout
+        .map(|seconds| Instant::now() + Duration::from_secs(seconds));
+
+    // The clipboard copy is all there is, so it is rendered in the clipboard format
+    let mut sink = OutputSink::in_memory()
+        .with_format(clipboard_mirror_format(args).unwrap_or(args.format))
+        .with_blank_lines(args.blank_lines_between);
+    let mut summary = walker::TraversalSummary::default();
+    for input in &args.input_paths {
+        match execute_traversal(args, root, header_root, shared, input, &mut sink, deadline) {
+            Ok(input_summary) => summary += input_summary,
+            Err(e) if is_no_files_found(&e) => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    if args.footer_stats {
+        write_footer_stats(args, &summary.content_stats, &mut sink)?;
+    }
+
+    let finished = sink.finish()?;
+    report_read_errors(&summary.read_errors);
+    report_symlink_loops(&summary.symlink_loops);
+    report_dangling_symlinks(&summary.dangling_symlinks);
+    report_pruned_dirs(args, &summary.pruned_dirs);
+    report_omitted_per_dir(&summary.omitted_per_dir);
+    let content = finished.contents.unwrap_or_default();
+    Ok((content, summary.files.len()))
+}
+
+/// Traverses every input into a discarded temp file and prints the files it would
+/// include to stdout (`--dry-run`), or their sizes by directory (`--du`).
+fn list_selection(
+    args: &RunArgs,
+    root: &Path,
+    header_root: Option<&Path>,
+    shared: &HashSet<PathBuf>,
+) -> anyhow::Result<()> {
+    let spool = tempfile::Builder::new()
+        .prefix("treeclip-dry-run-")
+        .suffix(".txt")
+        .tempfile()
+        .with_context(|| "Failed to create a temp file for the dry run")?
+        .into_temp_path();
+    let deadline = args
+        .timeout
+        .map(|seconds| Instant::now() + Duration::from_secs(seconds));
+
+    let mut sink = open_sink(args, &spool)?;
+    let mut summary = walker::TraversalSummary::default();
+    for input in &args.input_paths {
+        match execute_traversal(args, root, header_root, shared, input, &mut sink, deadline) {
+            Ok(input_summary) => summary += input_summary,
+            Err(e) if is_no_files_found(&e) => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    sink.finish()?;
+    report_read_errors(&summary.read_errors);
+    report_symlink_loops(&summary.symlink_loops);
+    report_dangling_symlinks(&summary.dangling_symlinks);
+    report_pruned_dirs(args, &summary.pruned_dirs);
+    report_omitted_per_dir(&summary.omitted_per_dir);
+    if let (true, Some(seconds)) = (summary.timed_out, args.timeout) {
+        eprintln!("{}", messages::Messages::timeout_reached(seconds));
+    }
+
+    let entries: Vec<(&Path, u64)> = summary
+        .files
+        .iter()
+        .map(PathBuf::as_path)
+        .zip(summary.file_sizes.iter().copied())
+        .collect();
+    let rendered = if args.du {
+        listing::render_du(&entries)
+    } else {
+        listing::render(&entries, args.list_format, args.null_separated)
+    };
+    let mut stdout = io::stdout().lock();
+    stdout
+        .write_all(rendered.as_bytes())
+        .and_then(|_| stdout.flush())
+        .with_context(|| "Failed to write the file listing to stdout")?;
+
+    if let Some(list_path) = &args.input_list_output {
+        write_input_list(list_path, &summary.files)?;
+    }
+
+    if too_few_files(args, summary.files.len()) {
+        drop(spool);
+        process::exit(MIN_FILES_EXIT_CODE);
+    }
+    Ok(())
+}
+
+/// Returns whether `e` is, or was caused by, an input path with no files in it.
+///
+/// The walker wraps the error in context, so the whole chain is checked.
+fn is_no_files_found(e: &anyhow::Error) -> bool {
+    e.chain().any(|cause| {
+        matches!(
+            cause.downcast_ref::<errors::TraversalError>(),
+            Some(errors::TraversalError::NoFilesFound(_))
+        )
+    })
+}
+
+/// Reports and returns whether fewer than `--min-files` files were included.
+fn too_few_files(args: &RunArgs, found: usize) -> bool {
+    match args.min_files {
+        Some(min_files) if found < min_files => {
+            eprintln!("{}", messages::Messages::too_few_files(found, min_files));
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Points the output at a temporary spool file for `-o -`, turning off the options that
+/// need a real output file.
+///
+/// The spool is removed when the returned handle is dropped.
+fn spool_stdout_output(args: &mut RunArgs) -> anyhow::Result<tempfile::TempPath> {
+    for (enabled, flag) in [
+        (&mut args.clipboard, "--clipboard"),
+        (&mut args.editor, "--editor"),
+        (&mut args.delete, "--delete"),
+        (&mut args.hash_sidecar, "--hash-sidecar"),
+    ] {
+        if std::mem::take(enabled) {
+            eprintln!("{}", messages::Messages::ignored_for_stdout(flag));
+        }
+    }
+    if args.split_size.take().is_some() {
+        eprintln!("{}", messages::Messages::ignored_for_stdout("--split-size"));
+    }
+
+    let spool = tempfile::Builder::new()
+        .prefix("treeclip-stdout-")
+        .tempfile()?
+        .into_temp_path();
+    args.output_path = Some(spool.to_path_buf());
+    Ok(spool)
+}
+
+/// Finishes the output of a run that included too few files (`--min-files`).
+///
+/// The output is kept for inspection, so it is still copied to `stdout` when given
+/// (`-o -`).
+fn finish_too_few(
+    sink: OutputSink,
+    output: &Path,
+    stdout: Option<&mut dyn Write>,
+) -> anyhow::Result<()> {
+    sink.finish()?;
+    if let Some(stdout) = stdout {
+        copy_output(output, stdout)?;
+    }
+    Ok(())
+}
+
+/// Streams the finished output file to stdout.
+fn copy_to_stdout(output: &Path) -> anyhow::Result<()> {
+    copy_output(output, &mut io::stdout().lock())
+}
+
+/// Streams the finished output file to `to`.
+///
+/// A reader that stops early (`treeclip run -o - | head`) is not an error.
+fn copy_output(output: &Path, to: &mut dyn Write) -> anyhow::Result<()> {
+    let mut file = fs::File::open(output).map_err(|e| errors::FileSystemError::ReadFailed {
+        path: output.to_path_buf(),
+        source: e,
+    })?;
+
+    match io::copy(&mut file, to).and_then(|_| to.flush()) {
+        Err(e) if e.kind() != io::ErrorKind::BrokenPipe => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+/// Reads the paths shown in an earlier output's headers (`--exclude-from-output`).
+fn read_shared_paths(previous: &Path) -> anyhow::Result<HashSet<PathBuf>> {
+    let dump = fs::read_to_string(previous)
+        .map_err(|e| errors::FileSystemError::ReadFailed {
+            path: previous.to_path_buf(),
+            source: e,
+        })
+        .with_context(|| format!("Failed to read earlier output: {}", previous.display()))?;
+    let files = unpack::parse(&dump).with_context(|| {
+        format!(
+            "--exclude-from-output file is not treeclip text output: {}",
+            previous.display()
+        )
+    })?;
+
+    Ok(files.into_iter().map(|file| file.path).collect())
+}
+
 /// Executes the directory traversal operation.
 fn execute_traversal(
     args: &RunArgs,
     root: &Path,
+    header_root: Option<&Path>,
+    shared: &HashSet<PathBuf>,
     input: &Path,
-    output: &Path,
-) -> anyhow::Result<()> {
-    println!("\n{}", messages::Messages::starting_adventure());
+    sink: &mut OutputSink,
+    deadline: Option<Instant>,
+) -> anyhow::Result<walker::TraversalSummary> {
+    statusln!("\n{}", messages::Messages::starting_adventure());
 
     if !args.fast_mode {
         animations::animated_dots(&messages::Messages::scanning_files(), 3, 300);
     }
 
-    let walker = walker::Walker::new(root, input, output, &args.exclude);
+    let mut walker = walker::Walker::new(root, input, sink.path(), &args.exclude)
+        .with_deadline(deadline)
+        .with_header_root(header_root.map(Path::to_path_buf))
+        .with_excluded_paths(shared.clone());
+
+    // The verbose counter is just one consumer of the walker's progress events
+    if args.verbose && !args.fast_mode {
+        walker = walker.with_progress(|event| {
+            if let Some(msg) = animations::progress_counter(&TREE_EMOJIS, event.files_processed, 5)
+            {
+                let size = utils::format_bytes(event.bytes_written as usize);
+                animations::print_flush(&format!("\r{msg} ({size})"));
+            }
+        });
+    }
+
+    // Verbose mode already shows the file counter on the same line
+    let summary = if args.fast_mode || args.verbose {
+        walker.process_dir(args, sink)?
+    } else {
+        animations::Spinner::new_tree().run_while(&messages::Messages::traversing_tree(), || {
+            walker.process_dir(args, sink)
+        })?
+    };
+
+    statusln!("\n{}", messages::Messages::gathering_leaves());
+
+    Ok(summary)
+}
+
+/// Prints the output digest and writes the `.sha256` sidecar if requested.
+fn report_output_hash(args: &RunArgs, output: &Path, digest: &str) -> anyhow::Result<()> {
+    statusln!("{}", messages::Messages::output_hash(digest));
+
+    if args.hash_sidecar {
+        let sidecar = output::write_hash_sidecar(output, digest)?;
+        statusln!(
+            "{}",
+            messages::Messages::hash_sidecar_written(&sidecar.display().to_string())
+        );
+    }
+
+    Ok(())
+}
+
+/// Reports the parts written with `--split-size`, and that the clipboard gets only the first.
+fn report_parts(args: &RunArgs, parts: &[PathBuf]) {
+    statusln!(
+        "{}",
+        messages::Messages::split_parts(parts.len(), &parts[0].display().to_string())
+    );
+    if args.clipboard && parts.len() > 1 {
+        statusln!("{}", messages::Messages::clipboard_first_part(parts.len()));
+    }
+}
+
+/// Writes the included files, one relative path per line, for replaying the selection.
+fn write_input_list(list_path: &Path, files: &[PathBuf]) -> anyhow::Result<()> {
+    let list: String = files
+        .iter()
+        .map(|file| format!("{}\n", file.display()))
+        .collect();
+
+    fs::write(list_path, list)
+        .map_err(|e| errors::FileSystemError::WriteFailed {
+            path: list_path.to_path_buf(),
+            source: e,
+        })
+        .with_context(|| format!("Failed to write file list to: {}", list_path.display()))?;
+
+    statusln!(
+        "{}",
+        messages::Messages::input_list_written(files.len(), &list_path.display().to_string())
+    );
+    Ok(())
+}
 
+/// Asks before truncating an existing output file that may hold something else.
+///
+/// # Errors
+///
+/// Returns `FileSystemError::OutputExists` if the user declines, or if there is no
+/// terminal to ask on and `--force` was not given.
+fn confirm_overwrite(args: &RunArgs, output: &Path) -> anyhow::Result<()> {
+    if args.force || !needs_overwrite_confirmation(output) {
+        return Ok(());
+    }
+
+    let refuse = || errors::FileSystemError::OutputExists(output.to_path_buf());
+    if !io::stdin().is_terminal() {
+        return Err(refuse().into());
+    }
+
+    eprint!(
+        "{}",
+        messages::Messages::confirm_overwrite(&output.display().to_string())
+    );
+    io::stderr().flush()?;
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+
+    if matches!(answer.trim().to_lowercase().as_str(), "y" | "yes") {
+        Ok(())
+    } else {
+        Err(refuse().into())
+    }
+}
+
+/// Returns `true` if `output` is the default temp file, named by treeclip rather than
+/// the user.
+fn is_default_output(output: &Path) -> bool {
+    output.file_name() == Some(DEFAULT_OUTPUT_NAME.as_ref())
+}
+
+/// Removes the default temp file after the clipboard copy (`--cleanup`); an output
+/// the user named is kept.
+fn cleanup_output(args: &RunArgs, output: &Path) -> anyhow::Result<()> {
+    if !is_default_output(output) {
+        eprintln!(
+            "{}",
+            messages::Messages::cleanup_kept_named_output(&output.display().to_string())
+        );
+        return Ok(());
+    }
+
+    editor::delete(output)?;
     if !args.fast_mode {
-        let spinner = animations::Spinner::new_tree();
-        spinner.spin(&messages::Messages::traversing_tree(), 1200);
+        statusln!("{}", messages::Messages::cleaned_up());
+    }
+    Ok(())
+}
+
+/// Returns `true` if `output` exists and is neither the default temp file nor
+/// earlier treeclip output.
+fn needs_overwrite_confirmation(output: &Path) -> bool {
+    if !output.exists() || is_default_output(output) {
+        return false;
+    }
+
+    let previous_output = output::read_head(output, 1).is_ok_and(|(head, _)| {
+        head.first()
+            .is_some_and(|line| format::is_marker_line(line))
+    });
+    !previous_output
+}
+
+/// Prints one consolidated report of every file that could not be read.
+fn report_read_errors(errors: &[(PathBuf, anyhow::Error)]) {
+    if errors.is_empty() {
+        return;
+    }
+
+    eprintln!("\n{}", messages::Messages::read_errors(errors.len()));
+    for (path, error) in errors {
+        eprintln!("  {} {}: {:#}", "▸".red(), path.display(), error);
+    }
+}
+
+/// Warns about each symlink loop skipped while following symlinks.
+fn report_symlink_loops(loops: &[PathBuf]) {
+    for path in loops {
+        eprintln!(
+            "{}",
+            messages::Messages::symlink_loop(&path.display().to_string())
+        );
+    }
+}
+
+/// Warns about each followed symlink skipped because its target is missing.
+fn report_dangling_symlinks(links: &[PathBuf]) {
+    for path in links {
+        eprintln!(
+            "{}",
+            messages::Messages::dangling_symlink(&path.display().to_string())
+        );
+    }
+}
+
+/// Warns about the directories `--exclude-large-dirs` left out, so a pruned source
+/// directory is noticed and can be re-included with a higher `--prune-dir-over`.
+fn report_pruned_dirs(args: &RunArgs, pruned: &[PathBuf]) {
+    if pruned.is_empty() {
+        return;
+    }
+
+    eprintln!(
+        "\n{}",
+        messages::Messages::pruned_dirs(pruned.len(), args.prune_dir_over)
+    );
+    for dir in pruned {
+        eprintln!("  {} {}", "▸".yellow(), dir.display());
+    }
+}
+
+/// Notes each directory `--max-files-per-dir` capped, so a sampled directory is never
+/// mistaken for a complete one.
+fn report_omitted_per_dir(omitted_per_dir: &[(PathBuf, usize)]) {
+    for (dir, omitted) in omitted_per_dir {
+        eprintln!(
+            "{}",
+            messages::Messages::omitted_in_dir(&dir.display().to_string(), *omitted)
+        );
     }
+}
+
+/// Reports the bytes `--collapse-whitespace-runs` saved, and warns about the
+/// indentation-sensitive files it changed.
+fn report_whitespace_collapse(summary: &walker::TraversalSummary) {
+    statusln!(
+        "{}",
+        messages::Messages::whitespace_collapsed(&utils::format_bytes(
+            summary.whitespace_bytes_saved
+        ))
+    );
+    let sensitive = &summary.whitespace_collapsed_sensitive;
+    if sensitive.is_empty() {
+        return;
+    }
+
+    eprintln!(
+        "\n{}",
+        messages::Messages::whitespace_collapsed_sensitive(sensitive.len())
+    );
+    for path in sensitive {
+        eprintln!("  {} {}", "▸".yellow(), path.display());
+    }
+}
+
+/// Prints the extensions that fell back to a bare markdown fence (`--extensions-report`).
+fn report_unrecognized_extensions(extensions: &BTreeMap<String, usize>) {
+    statusln!(
+        "\n{}",
+        messages::Messages::unrecognized_extensions(extensions.len())
+    );
+    for (extension, count) in extensions {
+        statusln!("  {} .{extension} ({count} files)", "▸".bright_yellow());
+    }
+}
 
-    walker.process_dir(args)?;
+/// Prints the first `lines` lines of the output, highlighting file headers.
+fn show_preview(output: &Path, lines: usize) -> anyhow::Result<()> {
+    let (head, truncated) = output::read_head(output, lines)?;
 
-    println!("\n{}", messages::Messages::gathering_leaves());
+    statusln!("\n{}", messages::Messages::preview_header(head.len()));
+    for line in &head {
+        if line.starts_with(unpack::HEADER_PREFIX) {
+            statusln!("{}", line.bright_cyan().bold());
+        } else {
+            statusln!("{line}");
+        }
+    }
+    if truncated {
+        statusln!("{}", messages::Messages::preview_truncated());
+    }
+    statusln!();
 
     Ok(())
 }
 
+/// Returns the format the clipboard needs rendering in separately, if it differs from
+/// the output file's.
+fn clipboard_mirror_format(args: &RunArgs) -> Option<OutputFormat> {
+    args.clipboard_format
+        .filter(|format| args.clipboard && *format != args.format)
+}
+
+/// Shows the output in the pager (`--page`), when stdout is an interactive terminal.
+///
+/// A pager that is missing or fails is only a warning; the output file still exists.
+fn page_output(args: &RunArgs, output: &Path) {
+    if args.writes_to_stdout() || !io::stdout().is_terminal() {
+        return;
+    }
+    if let Err(e) = editor::page(output) {
+        eprintln!("{}", messages::Messages::pager_failed(&format!("{e:#}")));
+    }
+}
+
 /// Handles clipboard copy operations.
-fn handle_clipboard(args: &RunArgs, output: &Path) -> anyhow::Result<()> {
-    let mut clip = clipboard::Clipboard::new(output)?;
+///
+/// `mirror` is the output rendered in `--clipboard-format`, copied instead of the
+/// output file when present.
+fn handle_clipboard(args: &RunArgs, output: &Path, mirror: Option<String>) -> anyhow::Result<()> {
+    let size = mirror.as_ref().map(|text| text.len() as u64);
+    let html = args.clipboard_html
+        && args.clipboard_format.unwrap_or(args.format) == OutputFormat::Markdown;
+    if args.clipboard_html && !html {
+        eprintln!("{}", messages::Messages::clipboard_html_needs_markdown());
+    }
+    let mut clip = clipboard::Clipboard::new(output)?
+        .with_chunk_limit(args.clipboard_limit)
+        .with_append(args.clipboard_append)
+        .with_html(html)
+        .with_text(mirror);
 
     if args.clipboard {
-        if !args.fast_mode {
-            let spinner = animations::Spinner::new_loading();
-            spinner.spin(&messages::Messages::copying_clipboard(), 800);
-        }
+        warn_large_wayland_clipboard(args, output, size);
 
-        clip.set_clipboard()?;
-        println!("{}", messages::Messages::clipboard_ready());
+        let remainder = if args.fast_mode {
+            clip.set_clipboard()?
+        } else {
+            animations::Spinner::new_loading()
+                .run_while(&messages::Messages::copying_clipboard(), || {
+                    clip.set_clipboard()
+                })?
+        };
+        statusln!("{}", messages::Messages::clipboard_ready());
+        if clipboard::platform::may_not_persist() {
+            eprintln!(
+                "{}",
+                messages::Messages::clipboard_may_not_persist(&output.display().to_string())
+            );
+        }
+        if let Some(rest) = remainder {
+            statusln!(
+                "{}",
+                messages::Messages::clipboard_split(&rest.display().to_string())
+            );
+        }
     } else {
-        println!("{}", messages::Messages::clipboard_skipped());
+        statusln!("{}", messages::Messages::clipboard_skipped());
     }
 
     Ok(())
 }
 
+/// Warns before copying output that is likely too large for the Wayland compositor.
+///
+/// Skipped when `--clipboard-limit` already keeps each copy small enough. `size` is
+/// the clipboard text's length when it is not the output file itself.
+fn warn_large_wayland_clipboard(args: &RunArgs, output: &Path, size: Option<u64>) {
+    let limit = args.clipboard_limit.map_or(u64::MAX, |limit| limit as u64);
+    let Some(size) = size.or_else(|| fs::metadata(output).ok().map(|meta| meta.len())) else {
+        return;
+    };
+    let size = size.min(limit);
+
+    if clipboard::platform::wayland_size_warning(size) {
+        eprintln!(
+            "{}",
+            messages::Messages::wayland_clipboard_warning(
+                &utils::format_bytes(size as usize),
+                &output.display().to_string()
+            )
+        );
+    }
+}
+
 /// Shows statistics section with formatted output.
-fn show_stats_section(args: &RunArgs, output: &Path) -> anyhow::Result<()> {
+fn show_stats_section(args: &RunArgs, summary: &walker::TraversalSummary) {
     if !args.fast_mode {
-        println!("\n{}", messages::Messages::showing_stats());
+        statusln!("\n{}", messages::Messages::showing_stats());
     }
 
-    show_stats(output)?;
-    Ok(())
+    show_stats(&summary.content_stats, &args.stats_fields, args.stats_align);
+    if args.sloc && !summary.sloc.is_empty() {
+        let sloc = formatter::SlocBox::new(&summary.sloc).align(args.stats_align);
+        statusln!("{}", sloc.render().bright_cyan());
+    }
+    if args.language_chart && !summary.language_bytes.is_empty() {
+        statusln!(
+            "\n{}",
+            formatter::LanguageChart::new(&summary.language_bytes).render()
+        );
+    }
+
+    if args.no_content {
+        statusln!("{}", messages::Messages::structure_only());
+    }
+    if summary.skipped_generated > 0 {
+        statusln!(
+            "{}",
+            messages::Messages::skipped_generated(summary.skipped_generated)
+        );
+    }
+    if let Some(previous) = &args.exclude_from_output
+        && summary.skipped_shared > 0
+    {
+        statusln!(
+            "{}",
+            messages::Messages::skipped_shared(
+                summary.skipped_shared,
+                &previous.display().to_string()
+            )
+        );
+    }
+    if summary.skipped_oversized > 0 {
+        statusln!(
+            "{}",
+            messages::Messages::skipped_oversized(summary.skipped_oversized)
+        );
+    }
+    if summary.skipped_symlinks > 0 {
+        statusln!(
+            "{}",
+            messages::Messages::skipped_symlinks(summary.skipped_symlinks)
+        );
+    }
+    if summary.skipped_minified > 0 {
+        statusln!(
+            "{}",
+            messages::Messages::skipped_minified(summary.skipped_minified)
+        );
+    }
+    if args.content_match.is_some() || args.content_exclude.is_some() {
+        statusln!(
+            "{}",
+            messages::Messages::content_filtered(summary.files.len(), summary.skipped_content)
+        );
+    }
+    if summary.truncated_lines > 0 {
+        statusln!(
+            "{}",
+            messages::Messages::truncated_lines(
+                summary.truncated_lines,
+                summary.files_with_truncated_lines
+            )
+        );
+    }
 }
 
 /// Handles editor opening and cleanup operations.
 fn handle_editor(args: &RunArgs, output: &Path) -> anyhow::Result<()> {
     if args.editor {
         if !args.fast_mode {
-            println!("\n{}", messages::Messages::opening_editor());
+            statusln!("\n{}", messages::Messages::opening_editor());
         }
 
-        editor::open(output)?;
+        if args.wait {
+            editor::open_and_wait(output)?;
+        } else {
+            if args.delete {
+                eprintln!("{}", messages::Messages::delete_without_wait());
+            }
+            editor::open(output)?;
+        }
 
         if !args.fast_mode {
-            println!("{}", messages::Messages::editor_opened());
+            statusln!("{}", messages::Messages::editor_opened());
         }
 
         // Handle file deletion after editor closes
         if args.delete {
             if !args.fast_mode {
-                println!("\n{}", messages::Messages::cleaning_up());
+                statusln!("\n{}", messages::Messages::cleaning_up());
             }
 
             editor::delete(output)?;
 
             if !args.fast_mode {
-                println!("{}", messages::Messages::cleaned_up());
+                statusln!("{}", messages::Messages::cleaned_up());
             }
         }
     }
@@ -187,25 +1125,48 @@ fn handle_editor(args: &RunArgs, output: &Path) -> anyhow::Result<()> {
     Ok(())
 }
 
-/// Displays content statistics for the output file.
-fn show_stats(output: &Path) -> anyhow::Result<()> {
-    use colored::Colorize;
-
-    let content = fs::read_to_string(output)?;
-    let lines = content.split('\n').count();
-    let chars = content.chars().count();
-    let words = content.split_whitespace().count();
-    let bytes = content.len();
-
-    let stats = formatter::StatsBox::new(lines, chars, words, bytes);
-    println!("{}", stats.render().bright_cyan());
+/// Displays the content statistics, limited to the selected fields.
+fn show_stats(
+    stats: &walker::ContentStats,
+    fields: &[formatter::StatsField],
+    align: formatter::StatsAlign,
+) {
+    let stats = stats_box(stats, fields, align);
+    statusln!("{}", stats.render().bright_cyan());
 
     let (emoji, message) = stats.get_size_message();
-    println!("  {emoji} {message}");
+    statusln!("  {emoji} {message}");
+}
+
+/// Appends the content statistics as a final `(stats)` block (`--footer-stats`).
+///
+/// JSON and DOT output are meant for tools, so they get no block.
+fn write_footer_stats(
+    args: &RunArgs,
+    stats: &walker::ContentStats,
+    sink: &mut OutputSink,
+) -> anyhow::Result<()> {
+    if matches!(args.format, OutputFormat::Text | OutputFormat::Markdown) {
+        let title = Path::new(format::STATS_TITLE);
+        let header = format!("{}{}", unpack::HEADER_PREFIX, format::STATS_TITLE);
+        let rendered = stats_box(stats, &args.stats_fields, args.stats_align).render_plain();
+        sink.write_file(title, &header, &rendered)?;
+    }
 
     Ok(())
 }
 
+/// Builds the statistics box for the counted content.
+fn stats_box(
+    stats: &walker::ContentStats,
+    fields: &[formatter::StatsField],
+    align: formatter::StatsAlign,
+) -> formatter::StatsBox {
+    formatter::StatsBox::new(stats.lines, stats.chars, stats.words, stats.bytes)
+        .fields(fields)
+        .align(align)
+}
+
 /// Logs the current configuration settings to stdout.
 #[rustfmt::skip]
 fn log_config(args: &RunArgs) -> anyhow::Result<()> {
@@ -215,53 +1176,60 @@ fn log_config(args: &RunArgs) -> anyhow::Result<()> {
         args.output_path.as_ref(),
     );
 
-    println!(
+    statusln!(
         "{}",
         formatter::ConfigFormatter::format_section_header("Paths to traverse", "📂")
     );
     for path in inputs {
-        println!(
+        statusln!(
             "{}",
             formatter::ConfigFormatter::format_list_item("▸", &path.display().to_string())
         );
     }
-    println!();
-    println!(
+    statusln!();
+    statusln!(
         "{}",
         formatter::ConfigFormatter::format_section_header("Configuration Settings", "🔧")
     );
+    let output_display = if args.writes_to_stdout() {
+        "stdout".cyan().bold()
+    } else {
+        formatter::ConfigFormatter::format_path(output.expect("output path must be supplied"))
+    };
     let config_items = vec![
         ("🌍", "Root Path", formatter::ConfigFormatter::format_path(root.expect("root path must be supplied"))),
-        ("💾", "Output Path", formatter::ConfigFormatter::format_path(output.expect("output path must be supplied"))),
+        ("💾", "Output Path", output_display),
         ("✏️", "Editor", formatter::ConfigFormatter::format_bool(args.editor)),
         ("🗑️", "Cleanup", formatter::ConfigFormatter::format_bool(args.delete)),
         ("📋", "Clipboard", formatter::ConfigFormatter::format_bool(args.clipboard)),
         ("📊", "Stats", formatter::ConfigFormatter::format_bool(args.stats)),
-        ("👻", "Skip Hidden", formatter::ConfigFormatter::format_bool(args.skip_hidden)),
+        ("👻", "Skip Hidden", formatter::ConfigFormatter::format_bool(args.skips_hidden())),
+        ("🧪", "Skip Tests", formatter::ConfigFormatter::format_bool(args.exclude_test_files)),
+        ("🔒", "Skip Lockfiles", formatter::ConfigFormatter::format_bool(args.exclude_lockfiles)),
         ("⚡", "Fast Mode", formatter::ConfigFormatter::format_bool(args.fast_mode)),
     ];
 
     for (icon, label, value) in config_items {
-        println!(
+        statusln!(
             "{}",
             formatter::ConfigFormatter::format_config_line(icon, label, value)
         );
     }
 
     if !args.exclude.is_empty() {
-        println!(
+        statusln!(
             "{}",
             formatter::ConfigFormatter::format_section_header("Excluded Patterns", "🚫")
         );
         for pattern in &args.exclude {
-            println!(
+            statusln!(
                 "{}",
                 formatter::ConfigFormatter::format_list_item("▸", pattern)
             );
         }
     }
 
-    println!("{}", messages::Messages::ready_to_launch());
+    statusln!("{}", messages::Messages::ready_to_launch());
     Ok(())
 }
 
@@ -271,6 +1239,72 @@ mod run_tests {
     use std::fs;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_env_exclude_patterns_split_lines() {
+        assert_eq!(
+            env_exclude_patterns("target\n  *.log , dist\n\n"),
+            ["target", "*.log , dist"]
+        );
+        assert!(env_exclude_patterns(" \n").is_empty());
+    }
+
+    #[test]
+    fn test_too_few_files_still_copies_output_to_stdout() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("spool.txt");
+        let mut sink = OutputSink::create(&output)?;
+        sink.write_file(Path::new("main.rs"), "==> main.rs", "fn main() {}")?;
+
+        let mut stdout = Vec::new();
+        finish_too_few(sink, &output, Some(&mut stdout))?;
+
+        assert_eq!(String::from_utf8(stdout)?, fs::read_to_string(&output)?);
+        assert!(fs::read_to_string(&output)?.contains("fn main() {}"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_overwrite_confirmation_spares_disposable_files() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+
+        let important = temp_dir.path().join("important.txt");
+        assert!(!needs_overwrite_confirmation(&important));
+        fs::write(&important, "keep me")?;
+        assert!(needs_overwrite_confirmation(&important));
+
+        let default = temp_dir.path().join(DEFAULT_OUTPUT_NAME);
+        fs::write(&default, "scratch")?;
+        assert!(!needs_overwrite_confirmation(&default));
+
+        let previous = temp_dir.path().join("dump.txt");
+        fs::write(&previous, "# treeclip-output v2\n==> a.txt\na\n")?;
+        assert!(!needs_overwrite_confirmation(&previous));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cleanup_removes_only_the_default_output() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let args = RunArgs {
+            fast_mode: true,
+            ..RunArgs::default()
+        };
+
+        let default = temp_dir.path().join(DEFAULT_OUTPUT_NAME);
+        fs::write(&default, "scratch")?;
+        cleanup_output(&args, &default)?;
+        assert!(!default.exists());
+
+        let named = temp_dir.path().join("context.txt");
+        fs::write(&named, "keep me")?;
+        cleanup_output(&args, &named)?;
+        assert!(named.exists());
+
+        Ok(())
+    }
+
     #[test]
     fn test_normalize_paths_current_dir() -> anyhow::Result<()> {
         let mut args = RunArgs {
@@ -286,6 +1320,7 @@ mod run_tests {
             skip_hidden: true,
             raw: true,
             fast_mode: false,
+            ..RunArgs::default()
         };
 
         normalize_paths(&mut args)?;
@@ -298,15 +1333,87 @@ mod run_tests {
     }
 
     #[test]
-    fn test_show_stats_with_content() -> anyhow::Result<()> {
+    fn test_auto_name_uses_input_basename() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let project = temp_dir.path().join("my-project");
+        fs::create_dir(&project)?;
+
+        let mut args = RunArgs {
+            input_paths: vec![project.join("."), temp_dir.path().to_path_buf()],
+            output_path: Some(PathBuf::from(".")),
+            auto_name: true,
+            ..RunArgs::default()
+        };
+        normalize_paths(&mut args)?;
+        assert_eq!(
+            args.output_path,
+            Some(Path::new(".").join("my-project.txt"))
+        );
+
+        assert_eq!(auto_output_name(&[PathBuf::from("/")]), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_output_directory_gets_default_file() -> anyhow::Result<()> {
         let temp_dir = TempDir::new()?;
-        let output_path = temp_dir.path().join("output.txt");
-        fs::write(&output_path, "Hello\nWorld\nTest content")?;
+        let mut args = RunArgs {
+            input_paths: vec![temp_dir.path().to_path_buf()],
+            output_path: Some(temp_dir.path().to_path_buf()),
+            ..RunArgs::default()
+        };
 
-        // This should not panic
-        let result = show_stats(&output_path);
-        assert!(result.is_ok());
+        normalize_paths(&mut args)?;
+        assert_eq!(
+            args.output_path,
+            Some(temp_dir.path().join(DEFAULT_OUTPUT_NAME))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_input_list() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let list_path = temp_dir.path().join("selection.txt");
+        let files = vec![PathBuf::from("src/main.rs"), PathBuf::from("README.md")];
+
+        write_input_list(&list_path, &files)?;
 
+        assert_eq!(fs::read_to_string(&list_path)?, "src/main.rs\nREADME.md\n");
         Ok(())
     }
+
+    #[test]
+    fn test_read_shared_paths_from_earlier_output() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let previous = temp_dir.path().join("previous.txt");
+        fs::write(
+            &previous,
+            "# treeclip-output v2\nPath prefix: src/\n==> main.rs\nfn main() {}\n\n==> cli.rs\n",
+        )?;
+
+        assert_eq!(
+            read_shared_paths(&previous)?,
+            HashSet::from([PathBuf::from("src/main.rs"), PathBuf::from("src/cli.rs")])
+        );
+
+        fs::write(&previous, "just some notes\n")?;
+        assert!(read_shared_paths(&previous).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_stats_box_uses_counted_content() {
+        let mut stats = walker::ContentStats::default();
+        stats.add("Hello\nWorld\nTest content");
+
+        let rendered = stats_box(
+            &stats,
+            &formatter::StatsField::ALL,
+            formatter::StatsAlign::default(),
+        )
+        .render_plain();
+        assert!(rendered.contains("24 B"));
+    }
 }
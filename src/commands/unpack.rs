@@ -0,0 +1,99 @@
+//! unpack - Execution logic for the unpack command, restoring files from treeclip output.
+
+use super::args::UnpackArgs;
+use crate::core::clipboard::Clipboard;
+use crate::core::errors::FileSystemError;
+use crate::core::ui::messages::Messages;
+use crate::core::unpack;
+use anyhow::Context;
+use std::fs;
+
+/// Executes the unpack command with the provided arguments.
+///
+/// Reads the dump from the clipboard or the input file, parses it, and writes every
+/// file below the output directory.
+pub fn execute(args: UnpackArgs) -> anyhow::Result<()> {
+    let dump = if args.from_clipboard {
+        println!("{}", Messages::reading_clipboard());
+        Clipboard::get_text()?
+    } else {
+        let input = args
+            .input
+            .as_ref()
+            .expect("clap requires an input file unless --from-clipboard is set");
+        fs::read_to_string(input)
+            .map_err(|e| FileSystemError::ReadFailed {
+                path: input.clone(),
+                source: e,
+            })
+            .with_context(|| format!("Failed to read treeclip dump: {}", input.display()))?
+    };
+
+    let files = unpack::parse(&dump).with_context(|| {
+        if args.from_clipboard {
+            "Clipboard does not contain treeclip output".to_string()
+        } else {
+            "Input file does not contain treeclip output".to_string()
+        }
+    })?;
+
+    unpack::write_all(&files, &args.output_dir)?;
+
+    for file in &files {
+        println!(
+            "{}",
+            Messages::unpacked_file(&file.path.display().to_string())
+        );
+    }
+    println!(
+        "{}",
+        Messages::unpack_complete(files.len(), &args.output_dir.display().to_string())
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod unpack_command_tests {
+    use super::*;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_execute_from_file() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let dump = temp_dir.path().join("dump.txt");
+        fs::write(&dump, "==> src/lib.rs\npub fn lib() {}\n")?;
+
+        let out = temp_dir.path().join("out");
+        execute(UnpackArgs {
+            input: Some(dump),
+            from_clipboard: false,
+            output_dir: out.clone(),
+        })?;
+
+        assert_eq!(
+            fs::read_to_string(out.join("src/lib.rs"))?,
+            "pub fn lib() {}\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_execute_rejects_non_treeclip_file() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let dump = temp_dir.path().join("notes.txt");
+        fs::write(&dump, "hello there\n")?;
+
+        let result = execute(UnpackArgs {
+            input: Some(dump),
+            from_clipboard: false,
+            output_dir: PathBuf::from(temp_dir.path()),
+        });
+
+        assert!(result.is_err());
+        let error_msg = format!("{:?}", result.unwrap_err());
+        assert!(error_msg.contains("does not contain treeclip output"));
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+//! version - Execution logic for the version command, printing build details for bug reports.
+
+use super::args::VersionArgs;
+use crate::core::clipboard::platform;
+
+/// Executes the version command with the provided arguments.
+pub fn execute(args: &VersionArgs) {
+    println!("treeclip {}", env!("CARGO_PKG_VERSION"));
+
+    if args.verbose {
+        for (label, value) in details() {
+            println!("{label:<10} {value}");
+        }
+    }
+}
+
+// -------------------------------------------- Private Helper Functions --------------------------------------------
+
+/// Build and platform details, as label and value.
+fn details() -> [(&'static str, &'static str); 4] {
+    [
+        ("commit", env!("TREECLIP_GIT_COMMIT")),
+        ("rustc", env!("TREECLIP_RUSTC_VERSION")),
+        ("target", env!("TREECLIP_TARGET")),
+        ("clipboard", platform::backend_name()),
+    ]
+}
+
+#[cfg(test)]
+mod version_tests {
+    use super::*;
+
+    #[test]
+    fn test_details_are_filled_in() {
+        for (label, value) in details() {
+            assert!(!value.is_empty(), "{label} is empty");
+        }
+    }
+}
@@ -0,0 +1,52 @@
+//! chunk - Splits oversized clipboard content at a safe boundary.
+
+/// Splits `content` so the first part is at most `limit` bytes.
+///
+/// The cut is placed after the last line break that fits, falling back to the last
+/// character boundary when a single line is longer than `limit`.
+///
+/// # Returns
+///
+/// Returns `Some((head, rest))` if the content had to be split, `None` if it fits.
+pub fn split_at_limit(content: &str, limit: usize) -> Option<(&str, &str)> {
+    if content.len() <= limit {
+        return None;
+    }
+
+    let mut cut = limit;
+    while !content.is_char_boundary(cut) {
+        cut -= 1;
+    }
+
+    if let Some(newline) = content[..cut].rfind('\n') {
+        cut = newline + 1;
+    }
+
+    Some(content.split_at(cut))
+}
+
+#[cfg(test)]
+mod chunk_tests {
+    use super::*;
+
+    #[test]
+    fn test_split_not_needed() {
+        assert_eq!(split_at_limit("short", 10), None);
+        assert_eq!(split_at_limit("exact", 5), None);
+    }
+
+    #[test]
+    fn test_split_prefers_line_break() {
+        let (head, rest) = split_at_limit("line one\nline two\nline three\n", 20).unwrap();
+        assert_eq!(head, "line one\nline two\n");
+        assert_eq!(rest, "line three\n");
+    }
+
+    #[test]
+    fn test_split_respects_char_boundaries() {
+        // Each 'é' is two bytes; a limit of 3 would land mid-character
+        let (head, rest) = split_at_limit("éééé", 3).unwrap();
+        assert_eq!(head, "é");
+        assert_eq!(rest, "ééé");
+    }
+}
@@ -1,6 +1,10 @@
 //! clipboard - Handles system clipboard operations for file content.
 
+mod chunk;
+pub mod platform;
+
 use crate::core::errors::{ClipboardError, FileSystemError};
+use crate::core::output::{format, html};
 use anyhow::Context;
 use std::fs::File;
 use std::io::Read;
@@ -17,6 +21,14 @@ pub struct Clipboard {
     data: PathBuf,
     /// Handle to the system clipboard.
     clip: arboard::Clipboard,
+    /// Largest number of bytes to place on the clipboard at once, if capped.
+    chunk_limit: Option<usize>,
+    /// Text to copy instead of the data file's contents.
+    text: Option<String>,
+    /// Whether to add to the clipboard's current text rather than replace it.
+    append: bool,
+    /// Whether to also offer the content as HTML rendered from markdown.
+    html: bool,
 }
 
 impl Clipboard {
@@ -36,10 +48,51 @@ impl Clipboard {
         Ok(Self {
             data: data.to_path_buf(),
             clip,
+            chunk_limit: None,
+            text: None,
+            append: false,
+            html: false,
         })
     }
 
-    /// Reads the output file and places its contents into the system clipboard.
+    /// Copies `text` instead of the data file's contents, when given.
+    ///
+    /// The data file path is still used to place the remainder of a split copy.
+    pub fn with_text(mut self, text: Option<String>) -> Self {
+        self.text = text;
+        self
+    }
+
+    /// Adds the content after the clipboard's current text instead of replacing it.
+    ///
+    /// A clipboard holding no text (empty, or an image) counts as empty. The chunk
+    /// limit applies to the combined text.
+    pub fn with_append(mut self, append: bool) -> Self {
+        self.append = append;
+        self
+    }
+
+    /// Also places an HTML rendering of the content, which must be markdown.
+    ///
+    /// Rich-text targets paste the HTML; everything else still gets the markdown as
+    /// plain text.
+    pub fn with_html(mut self, html: bool) -> Self {
+        self.html = html;
+        self
+    }
+
+    /// Caps how many bytes are placed on the clipboard in one set.
+    ///
+    /// Content over the limit is split: the clipboard receives the largest piece that
+    /// fits (cut at a line break) followed by a continuation note, and the rest is saved
+    /// next to the output file. `None` disables splitting.
+    pub fn with_chunk_limit(mut self, limit: Option<usize>) -> Self {
+        self.chunk_limit = limit;
+        self
+    }
+
+    /// Reads the output file (or the text set with [`Clipboard::with_text`]) and places
+    /// its contents into the system clipboard.
     ///
     /// # Platform Notes
     ///
@@ -51,25 +104,149 @@ impl Clipboard {
     /// On most desktop environments this works out of the box. On minimal window
     /// managers without a clipboard manager, contents may not persist after exit.
     ///
+    /// A chunk limit (see [`Clipboard::with_chunk_limit`]) helps where large sets fail
+    /// silently: X11 clipboard managers that drop big selections, clipboard sharing in
+    /// RDP/VNC sessions and VM guests, and terminal OSC 52 forwarding (tmux, SSH).
+    ///
+    /// # Returns
+    ///
+    /// Returns the path of the remainder file if the content was split.
+    ///
     /// # Errors
     ///
     /// Returns `ClipboardError` if:
     /// - File cannot be read
     /// - File is too large (>100MB)
     /// - Clipboard cannot be accessed
-    pub fn set_clipboard(&mut self) -> anyhow::Result<()> {
+    pub fn set_clipboard(&mut self) -> anyhow::Result<Option<PathBuf>> {
+        let mut output_content = match self.text.take() {
+            Some(text) => {
+                check_size(text.len())?;
+                text
+            }
+            None => self.read_data()?,
+        };
+        if self.append {
+            let existing = self.clip.get_text().unwrap_or_default();
+            output_content = appended(existing, output_content);
+            check_size(output_content.len())?;
+        }
+
+        let remainder = match self.chunk_limit {
+            Some(limit) => self.split_oversized(&mut output_content, limit)?,
+            None => None,
+        };
+
+        // Set clipboard text
+        // On Linux, clipboard managers usually take ownership immediately
+        let set = self.clip.set();
+        let result = if self.html {
+            set.html(html::from_markdown(&output_content), Some(output_content))
+        } else {
+            set.text(output_content)
+        };
+        result
+            .map_err(|e| ClipboardError::SetFailed(format!("Clipboard operation failed: {}", e)))
+            .with_context(|| "Failed to set clipboard content - clipboard may not be available")?;
+
+        // NOTE: Sleep guarantees clipboard ownership (required by arboard on some platforms)
+        thread::sleep(Duration::from_millis(100));
+
+        Ok(remainder)
+    }
+
+    /// Reads the current text content of the system clipboard.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ClipboardError` if the clipboard cannot be accessed or holds no text.
+    pub fn get_text() -> anyhow::Result<String> {
+        let mut clip = arboard::Clipboard::new()
+            .map_err(|e| {
+                ClipboardError::InitializationFailed(format!(
+                    "Failed to access system clipboard: {}",
+                    e
+                ))
+            })
+            .with_context(|| "Failed to open clipboard for reading")?;
+
+        clip.get_text()
+            .map_err(|e| ClipboardError::GetFailed(format!("Clipboard read failed: {}", e)))
+            .with_context(|| "Failed to read text from clipboard - it may be empty or non-text")
+    }
+
+    /// Checks that the clipboard can be written and read back, for `treeclip doctor`.
+    ///
+    /// Sets `probe`, reads it back, then restores the previous text if there was any.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ClipboardError` if the clipboard cannot be accessed, or reads back
+    /// something other than `probe`.
+    pub fn round_trip(probe: &str) -> Result<(), ClipboardError> {
+        let mut clip = arboard::Clipboard::new().map_err(|e| {
+            ClipboardError::InitializationFailed(format!(
+                "Failed to access system clipboard: {}",
+                e
+            ))
+        })?;
+        let previous = clip.get_text().ok();
+
+        clip.set_text(probe)
+            .map_err(|e| ClipboardError::SetFailed(format!("Clipboard operation failed: {}", e)))?;
+        let read_back = clip
+            .get_text()
+            .map_err(|e| ClipboardError::GetFailed(format!("Clipboard read failed: {}", e)));
+
+        if let Some(previous) = previous {
+            let _ = clip.set_text(previous);
+        }
+
+        match read_back? {
+            text if text == probe => Ok(()),
+            _ => Err(ClipboardError::GetFailed(
+                "Clipboard returned different text than was set".to_string(),
+            )),
+        }
+    }
+}
+
+// -------------------------------------------- Private Helper Functions --------------------------------------------
+
+/// Rejects content over `MAX_CLIPBOARD_SIZE`.
+fn check_size(size: usize) -> Result<(), ClipboardError> {
+    if size > MAX_CLIPBOARD_SIZE {
+        return Err(ClipboardError::ContentTooLarge {
+            size,
+            max: MAX_CLIPBOARD_SIZE,
+        });
+    }
+    Ok(())
+}
+
+/// Joins the clipboard's `existing` text and new `content`, on a new line unless
+/// `existing` already ends with one.
+fn appended(mut existing: String, content: String) -> String {
+    if existing.is_empty() {
+        return content;
+    }
+    if !existing.ends_with('\n') {
+        existing.push('\n');
+    }
+    existing.push_str(&content);
+    existing
+}
+
+impl Clipboard {
+    /// Reads the data file, rejecting it up front if it is too large.
+    ///
+    /// The output marker and BOM are only there for later runs and other tools to
+    /// recognise the file, so they are left out of the copy.
+    fn read_data(&self) -> anyhow::Result<String> {
         // Check file size first
         let metadata = std::fs::metadata(&self.data)
             .with_context(|| format!("Failed to read file metadata: {}", self.data.display()))?;
-
-        let file_size = metadata.len() as usize;
-        if file_size > MAX_CLIPBOARD_SIZE {
-            return Err(ClipboardError::ContentTooLarge {
-                size: file_size,
-                max: MAX_CLIPBOARD_SIZE,
-            }
-            .into());
-        }
+        check_size(metadata.len() as usize)?;
 
         // TODO: Optimize for huge files - consider streaming or chunking instead of loading entire file
         // Read entire file into memory (clipboard APIs require full content as string)
@@ -101,18 +278,47 @@ impl Clipboard {
                 )
             })?;
 
-        // Set clipboard text
-        // On Linux, clipboard managers usually take ownership immediately
-        self.clip
-            .set()
-            .text(output_content)
-            .map_err(|e| ClipboardError::SetFailed(format!("Clipboard operation failed: {}", e)))
-            .with_context(|| "Failed to set clipboard content - clipboard may not be available")?;
+        // JSON has no marker line, but may still start with a BOM
+        let unmarked = format::strip_marker(&output_content).trim_start_matches(format::UTF8_BOM);
+        Ok(unmarked.to_string())
+    }
 
-        // NOTE: Sleep guarantees clipboard ownership (required by arboard on some platforms)
-        thread::sleep(Duration::from_millis(100));
+    /// Trims `content` to fit `limit`, saving the overflow to a `.rest.txt` file.
+    ///
+    /// Returns the remainder file path if a split happened.
+    fn split_oversized(
+        &self,
+        content: &mut String,
+        limit: usize,
+    ) -> anyhow::Result<Option<PathBuf>> {
+        let rest_path = self.data.with_extension("rest.txt");
+        let marker = format!(
+            "\n--- treeclip: clipboard limit reached; the rest is in {} ---\n",
+            rest_path.display()
+        );
+
+        let Some((head, rest)) = chunk::split_at_limit(content, limit.saturating_sub(marker.len()))
+        else {
+            return Ok(None);
+        };
+
+        std::fs::write(&rest_path, rest)
+            .map_err(|e| FileSystemError::WriteFailed {
+                path: rest_path.clone(),
+                source: e,
+            })
+            .with_context(|| {
+                format!(
+                    "Failed to save clipboard remainder to: {}",
+                    rest_path.display()
+                )
+            })?;
 
-        Ok(())
+        let head_len = head.len();
+        content.truncate(head_len);
+        content.push_str(&marker);
+
+        Ok(Some(rest_path))
     }
 }
 
@@ -134,6 +340,21 @@ mod clipboard_tests {
         Ok(())
     }
 
+    #[test]
+    fn test_read_data_leaves_out_the_marker_and_bom() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let file_path = temp_dir.path().join("output.txt");
+        fs::write(&file_path, "# treeclip-output v2\n==> a.txt\nalpha\n")?;
+
+        let clipboard = Clipboard::new(&file_path)?;
+        assert_eq!(clipboard.read_data()?, "==> a.txt\nalpha\n");
+
+        fs::write(&file_path, "\u{feff}[\n]\n")?;
+        assert_eq!(clipboard.read_data()?, "[\n]\n");
+
+        Ok(())
+    }
+
     #[test]
     fn test_clipboard_with_nonexistent_file() {
         let result = Clipboard::new(Path::new("/nonexistent/file.txt"));
@@ -202,6 +423,13 @@ mod clipboard_tests {
         Ok(())
     }
 
+    #[test]
+    fn test_appended_starts_on_a_new_line() {
+        assert_eq!(appended(String::new(), "new".to_string()), "new");
+        assert_eq!(appended("old".to_string(), "new".to_string()), "old\nnew");
+        assert_eq!(appended("old\n".to_string(), "new".to_string()), "old\nnew");
+    }
+
     #[test]
     fn test_clipboard_nonexistent_file_error() {
         let file_path = PathBuf::from("/nonexistent/file.txt");
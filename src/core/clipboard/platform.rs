@@ -0,0 +1,157 @@
+//! platform - Platform-specific clipboard checks.
+
+use std::env;
+use std::ffi::OsStr;
+use std::fs;
+
+/// Size above which a Wayland compositor may reject or truncate a selection (4MB).
+///
+/// Deliberately conservative: limits differ between compositors and are rarely
+/// documented, and failures are silent.
+pub const WAYLAND_SAFE_SIZE: u64 = 4 * 1024 * 1024;
+
+/// Process names of clipboard managers, which take over the selection when treeclip
+/// exits so it can still be pasted.
+///
+/// GNOME Shell and KDE Plasma (through Klipper) include one.
+const CLIPBOARD_MANAGERS: &[&str] = &[
+    "gnome-shell",
+    "plasmashell",
+    "klipper",
+    "copyq",
+    "parcellite",
+    "clipit",
+    "diodon",
+    "gpaste-daemon",
+    "xfce4-clipman",
+    "clipmenud",
+    "greenclip",
+    "cliphist",
+    "clipman",
+    "wl-paste",
+];
+
+/// Returns `true` if content of `size` bytes should trigger the Wayland size warning
+/// in the current session.
+pub fn wayland_size_warning(size: u64) -> bool {
+    is_risky_on_wayland(env::var_os("WAYLAND_DISPLAY").as_deref(), size)
+}
+
+/// Names the clipboard backend arboard talks to in the current session.
+///
+/// On Linux arboard is built with X11 support only, so Wayland sessions go through
+/// XWayland and sessions without `DISPLAY` have no clipboard at all.
+pub fn backend_name() -> &'static str {
+    if cfg!(target_os = "windows") {
+        "Windows clipboard"
+    } else if cfg!(target_os = "macos") {
+        "macOS pasteboard"
+    } else {
+        linux_backend(
+            env::var_os("WAYLAND_DISPLAY").as_deref(),
+            env::var_os("DISPLAY").as_deref(),
+        )
+    }
+}
+
+/// Returns `true` if the clipboard content may be lost when treeclip exits.
+///
+/// On Linux an X11 selection belongs to the program that set it, so it only outlives
+/// treeclip if a clipboard manager takes it over. This looks for a known manager
+/// among the running processes. The check is a heuristic: it is skipped when the
+/// process list cannot be read, and an unlisted manager goes unseen.
+pub fn may_not_persist() -> bool {
+    if !cfg!(target_os = "linux") {
+        return false;
+    }
+    let Ok(processes) = fs::read_dir("/proc") else {
+        return false;
+    };
+    let names = processes
+        .filter_map(Result::ok)
+        .filter_map(|entry| fs::read_to_string(entry.path().join("comm")).ok());
+    find_manager(names).is_none()
+}
+
+// -------------------------------------------- Private Helper Functions --------------------------------------------
+
+/// Returns the first of the process `names` that is a known clipboard manager.
+///
+/// `/proc/<pid>/comm` ends in a newline and is cut at 15 bytes, so names are compared
+/// trimmed, on that prefix.
+fn find_manager(names: impl Iterator<Item = String>) -> Option<String> {
+    names.map(|name| name.trim().to_string()).find(|name| {
+        !name.is_empty()
+            && CLIPBOARD_MANAGERS
+                .iter()
+                .any(|manager| manager.as_bytes()[..manager.len().min(15)] == *name.as_bytes())
+    })
+}
+
+/// Decides whether `size` is risky given the value of `WAYLAND_DISPLAY`.
+fn is_risky_on_wayland(wayland_display: Option<&OsStr>, size: u64) -> bool {
+    let on_wayland = wayland_display.is_some_and(|display| !display.is_empty());
+    on_wayland && size > WAYLAND_SAFE_SIZE
+}
+
+/// Picks the X11 backend description from `WAYLAND_DISPLAY` and `DISPLAY`.
+fn linux_backend(wayland_display: Option<&OsStr>, display: Option<&OsStr>) -> &'static str {
+    let set = |var: Option<&OsStr>| var.is_some_and(|value| !value.is_empty());
+    match (set(display), set(wayland_display)) {
+        (true, true) => "X11 (through XWayland)",
+        (true, false) => "X11",
+        (false, _) => "none (DISPLAY is not set)",
+    }
+}
+
+#[cfg(test)]
+mod platform_tests {
+    use super::*;
+
+    #[test]
+    fn test_linux_backend() {
+        let display = Some(OsStr::new(":0"));
+
+        assert_eq!(linux_backend(None, display), "X11");
+        assert_eq!(
+            linux_backend(Some(OsStr::new("wayland-0")), display),
+            "X11 (through XWayland)"
+        );
+        assert_eq!(linux_backend(None, None), "none (DISPLAY is not set)");
+    }
+
+    #[test]
+    fn test_find_manager() {
+        let running = |names: &[&str]| {
+            names
+                .iter()
+                .map(|name| format!("{name}\n"))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            find_manager(running(&["bash", "copyq", "treeclip"]).into_iter()),
+            Some("copyq".to_string())
+        );
+        // Truncated by the kernel to 15 bytes
+        assert!(find_manager(running(&["xfce4-clipman"]).into_iter()).is_some());
+        assert!(find_manager(running(&["gpaste-daemon"]).into_iter()).is_some());
+        assert_eq!(
+            find_manager(running(&["bash", "i3", "Xorg"]).into_iter()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_warns_only_on_wayland_above_threshold() {
+        let wayland = Some(OsStr::new("wayland-0"));
+
+        assert!(is_risky_on_wayland(wayland, WAYLAND_SAFE_SIZE + 1));
+        assert!(!is_risky_on_wayland(wayland, WAYLAND_SAFE_SIZE));
+        assert!(!is_risky_on_wayland(None, WAYLAND_SAFE_SIZE * 10));
+        assert!(!is_risky_on_wayland(
+            Some(OsStr::new("")),
+            WAYLAND_SAFE_SIZE * 10
+        ));
+    }
+}
@@ -0,0 +1,524 @@
+//! config - Renders the resolved `run` options as TOML (`--print-config`).
+//!
+//! `RunArgs` derives `Serialize`, and `to_toml` serializes it into one `key = value`
+//! line per field, so every option is covered without listing them all here. Keys
+//! are the field names in kebab-case, which are the long flag names (positional
+//! arguments are keyed by their name), so each line maps straight back to the option
+//! it came from. Values are printed as the flag would take them.
+//!
+//! This is output only: treeclip has no config file loader yet, so nothing reads the
+//! rendered TOML back.
+
+use crate::core::errors::ConfigError;
+use regex::Regex;
+use serde::ser::{self, Impossible, Serialize, Serializer};
+use std::fmt::Display;
+
+/// Renders one `key = value` line per field of `value`, defaults included, in the
+/// order the fields are defined.
+///
+/// Booleans and numbers stay bare, lists become arrays and everything else is a
+/// string. Fields without a value (`None`) are left out, as TOML has no null.
+///
+/// # Errors
+///
+/// Returns `ConfigError::Unsupported` for values TOML lines cannot hold, such as
+/// nested tables or paths that are not valid UTF-8.
+pub fn to_toml(value: &impl Serialize) -> Result<String, ConfigError> {
+    let toml = value.serialize(ValueSerializer { nested: false })?;
+    Ok(toml.unwrap_or_default())
+}
+
+/// Serializes an optional regex as its pattern, for `#[serde(serialize_with)]`.
+pub fn regex<S: Serializer>(regex: &Option<Regex>, serializer: S) -> Result<S::Ok, S::Error> {
+    regex.as_ref().map(Regex::as_str).serialize(serializer)
+}
+
+/// Serializes `--max-file-size-by-ext` pairs as the `EXT=SIZE` arguments they were
+/// parsed from, for `#[serde(serialize_with)]`.
+pub fn ext_sizes<S: Serializer>(pairs: &[(String, u64)], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(
+        pairs
+            .iter()
+            .map(|(extension, size)| format!("{extension}={size}")),
+    )
+}
+
+// -------------------------------------------- Private Helper Functions --------------------------------------------
+
+impl ser::Error for ConfigError {
+    fn custom<T: Display>(message: T) -> Self {
+        Self::Unsupported(message.to_string())
+    }
+}
+
+/// Renders one value as TOML text, or `None` for a missing one.
+///
+/// The top-level struct becomes the lines of the document; any struct below it is
+/// rejected, since one line per option leaves no place for tables.
+struct ValueSerializer {
+    nested: bool,
+}
+
+/// Lines of the document, one per field with a value.
+struct Table {
+    lines: String,
+}
+
+/// Items of a TOML array.
+struct Array {
+    items: Vec<String>,
+}
+
+/// Rejects a kind of value no option uses.
+fn unsupported<T>(kind: &str) -> Result<T, ConfigError> {
+    Err(ConfigError::Unsupported(format!("{kind} values")))
+}
+
+/// Renders a number bare.
+fn number(value: impl Display) -> Result<Option<String>, ConfigError> {
+    Ok(Some(value.to_string()))
+}
+
+impl Serializer for ValueSerializer {
+    type Ok = Option<String>;
+    type Error = ConfigError;
+    type SerializeSeq = Array;
+    type SerializeTuple = Array;
+    type SerializeTupleStruct = Impossible<Self::Ok, Self::Error>;
+    type SerializeTupleVariant = Impossible<Self::Ok, Self::Error>;
+    type SerializeMap = Impossible<Self::Ok, Self::Error>;
+    type SerializeStruct = Table;
+    type SerializeStructVariant = Impossible<Self::Ok, Self::Error>;
+
+    fn serialize_bool(self, value: bool) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(value.to_string()))
+    }
+
+    fn serialize_i8(self, value: i8) -> Result<Self::Ok, Self::Error> {
+        number(value)
+    }
+
+    fn serialize_i16(self, value: i16) -> Result<Self::Ok, Self::Error> {
+        number(value)
+    }
+
+    fn serialize_i32(self, value: i32) -> Result<Self::Ok, Self::Error> {
+        number(value)
+    }
+
+    fn serialize_i64(self, value: i64) -> Result<Self::Ok, Self::Error> {
+        number(value)
+    }
+
+    fn serialize_u8(self, value: u8) -> Result<Self::Ok, Self::Error> {
+        number(value)
+    }
+
+    fn serialize_u16(self, value: u16) -> Result<Self::Ok, Self::Error> {
+        number(value)
+    }
+
+    fn serialize_u32(self, value: u32) -> Result<Self::Ok, Self::Error> {
+        number(value)
+    }
+
+    fn serialize_u64(self, value: u64) -> Result<Self::Ok, Self::Error> {
+        number(value)
+    }
+
+    fn serialize_f32(self, value: f32) -> Result<Self::Ok, Self::Error> {
+        self.serialize_f64(f64::from(value))
+    }
+
+    fn serialize_f64(self, value: f64) -> Result<Self::Ok, Self::Error> {
+        // Debug keeps the decimal point, so `1.0` stays a TOML float
+        Ok(Some(format!("{value:?}")))
+    }
+
+    fn serialize_char(self, value: char) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(toml_string(value.encode_utf8(&mut [0; 4]))))
+    }
+
+    fn serialize_str(self, value: &str) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(toml_string(value)))
+    }
+
+    fn serialize_bytes(self, _value: &[u8]) -> Result<Self::Ok, Self::Error> {
+        unsupported("byte")
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Ok(None)
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        unsupported("unit")
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        unsupported("unit struct")
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(toml_string(variant)))
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        unsupported("enum variant")
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Ok(Array {
+            items: Vec::with_capacity(len.unwrap_or_default()),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        unsupported("tuple struct")
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        unsupported("enum variant")
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        unsupported("map")
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        if self.nested {
+            return unsupported("nested struct");
+        }
+        Ok(Table {
+            lines: String::new(),
+        })
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        unsupported("enum variant")
+    }
+}
+
+impl ser::SerializeStruct for Table {
+    type Ok = Option<String>;
+    type Error = ConfigError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        if let Some(rendered) = value.serialize(ValueSerializer { nested: true })? {
+            self.lines.push_str(&format!("{key} = {rendered}\n"));
+        }
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(self.lines))
+    }
+}
+
+impl ser::SerializeSeq for Array {
+    type Ok = Option<String>;
+    type Error = ConfigError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        match value.serialize(ValueSerializer { nested: true })? {
+            Some(item) => self.items.push(item),
+            None => return unsupported("missing array"),
+        }
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(format!("[{}]", self.items.join(", "))))
+    }
+}
+
+impl ser::SerializeTuple for Array {
+    type Ok = Option<String>;
+    type Error = ConfigError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+/// Quotes and escapes a TOML basic string.
+fn toml_string(text: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04X}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod config_tests {
+    use super::*;
+    use crate::commands::args::RunArgs;
+    use clap::{Args, Command, FromArgMatches};
+
+    fn run_command() -> Command {
+        RunArgs::augment_args(Command::new("run"))
+    }
+
+    /// Parses `args` the way `treeclip run` does.
+    fn parse(args: &[&str]) -> RunArgs {
+        let matches = run_command().try_get_matches_from(args).unwrap();
+        RunArgs::from_arg_matches(&matches).unwrap()
+    }
+
+    /// Reads the values of one printed line: each item of an array, or the scalar.
+    fn values(rendered: &str) -> Vec<String> {
+        let inner = match rendered.strip_prefix('[') {
+            Some(array) => array.strip_suffix(']').unwrap(),
+            None => rendered,
+        };
+        let mut values = Vec::new();
+        let mut chars = inner.chars();
+        let mut current = String::new();
+        let mut quoted = false;
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => quoted = !quoted,
+                '\\' if quoted => match chars.next().unwrap() {
+                    'n' => current.push('\n'),
+                    't' => current.push('\t'),
+                    'u' => {
+                        let hex: String = chars.by_ref().take(4).collect();
+                        current
+                            .push(char::from_u32(u32::from_str_radix(&hex, 16).unwrap()).unwrap());
+                    }
+                    escaped => current.push(escaped),
+                },
+                ',' if !quoted => values.push(std::mem::take(&mut current)),
+                ' ' if !quoted && current.is_empty() => {}
+                c => current.push(c),
+            }
+        }
+        if !inner.is_empty() {
+            values.push(current);
+        }
+        values
+    }
+
+    /// Turns the printed lines that differ from `defaults` back into arguments.
+    fn to_arguments(toml: &str, defaults: &str) -> Vec<String> {
+        let mut arguments = vec!["run".to_string()];
+        for line in toml
+            .lines()
+            .filter(|line| !defaults.lines().any(|d| d == *line))
+        {
+            let (key, rendered) = line.split_once(" = ").unwrap();
+            match (key, rendered) {
+                ("input-paths", _) => arguments.extend(values(rendered)),
+                (_, "true") => arguments.push(format!("--{key}")),
+                _ => {
+                    for value in values(rendered) {
+                        arguments.push(format!("--{key}={value}"));
+                    }
+                }
+            }
+        }
+        arguments
+    }
+
+    #[test]
+    fn test_to_toml_renders_each_kind_of_value() -> anyhow::Result<()> {
+        let args = parse(&[
+            "run",
+            "src",
+            "--stats",
+            "-e",
+            "target",
+            "-e",
+            "*.lock",
+            "--tab-width",
+            "4",
+            "--header-prefix",
+            "say \"hi\"",
+            "--print-config",
+        ]);
+
+        let toml = to_toml(&args)?;
+        let lines: Vec<&str> = toml.lines().collect();
+        assert!(lines.contains(&"input-paths = [\"src\"]"), "{toml}");
+        assert!(lines.contains(&"stats = true"), "{toml}");
+        assert!(lines.contains(&"clipboard = false"), "{toml}");
+        assert!(
+            lines.contains(&"exclude = [\"target\", \"*.lock\"]"),
+            "{toml}"
+        );
+        assert!(lines.contains(&"tab-width = 4"), "{toml}");
+        assert!(
+            lines.contains(&"header-prefix = \"say \\\"hi\\\"\""),
+            "{toml}"
+        );
+        assert!(lines.contains(&"format = \"text\""), "{toml}");
+        assert!(!toml.contains("print-config"), "{toml}");
+        assert!(!toml.contains("header-suffix"), "{toml}");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_toml_keys_name_run_options() -> anyhow::Result<()> {
+        let command = run_command();
+
+        // Each key maps back to one option, so the file can be read back flag by flag
+        for line in to_toml(&parse(&["run"]))?.lines() {
+            let key = line.split(" = ").next().unwrap();
+            assert!(
+                command.get_arguments().any(|arg| {
+                    arg.get_long() == Some(key) || arg.get_id().as_str().replace('_', "-") == key
+                }),
+                "unknown key {key}"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_toml_renders_values_as_flags_take_them() -> anyhow::Result<()> {
+        let args = parse(&[
+            "run",
+            "--min-files",
+            "3",
+            "--exclude-minified",
+            "--minified-newline-ratio",
+            "1",
+            "--header-prefix",
+            "42",
+            "--max-file-size-by-ext",
+            "json=50kb",
+            "--mask-paths",
+            "^/home/[^/]+=~",
+            "--content-match",
+            r"fn \w+",
+        ]);
+
+        // Numbers come from the parsed value, not from what the text looks like
+        let toml = to_toml(&args)?;
+        let lines: Vec<&str> = toml.lines().collect();
+        assert!(lines.contains(&"min-files = 3"), "{toml}");
+        assert!(lines.contains(&"minified-newline-ratio = 1.0"), "{toml}");
+        assert!(lines.contains(&"header-prefix = \"42\""), "{toml}");
+        assert!(
+            lines.contains(&"max-file-size-by-ext = [\"json=51200\"]"),
+            "{toml}"
+        );
+        assert!(
+            lines.contains(&"mask-paths = [\"^/home/[^/]+=~\"]"),
+            "{toml}"
+        );
+        assert!(lines.contains(&r#"content-match = "fn \\w+""#), "{toml}");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_printed_config_parses_back_to_the_same_options() -> anyhow::Result<()> {
+        let defaults = to_toml(&parse(&["run"]))?;
+        let args = parse(&[
+            "run",
+            "src",
+            "docs",
+            "--stats",
+            "--stats-fields",
+            "lines,size",
+            "-e",
+            "target,*.lock",
+            "--format",
+            "markdown",
+            "--sort",
+            "size",
+            "--split-size",
+            "1048576",
+            "--header-prefix",
+            "-- \"x\"\t",
+            "--content-replace",
+            "foo=bar",
+            "--content-exclude",
+            "#\\[cfg",
+        ]);
+        let toml = to_toml(&args)?;
+
+        let arguments = to_arguments(&toml, &defaults);
+        let reparsed = run_command().try_get_matches_from(&arguments)?;
+        assert_eq!(
+            to_toml(&RunArgs::from_arg_matches(&reparsed)?)?,
+            toml,
+            "{arguments:?}"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_toml_string_escapes() {
+        assert_eq!(toml_string("a\\b\n\u{1b}"), "\"a\\\\b\\n\\u001B\"");
+    }
+}
@@ -0,0 +1,220 @@
+//! doctor - Environment checks behind `treeclip doctor`.
+//!
+//! The clipboard and editor integrations depend on the platform: a display server,
+//! a clipboard manager, a launcher or `$EDITOR`. Each check here reports whether that
+//! piece is in place and, when it is not, what to do about it.
+
+use crate::core::clipboard::{Clipboard, platform};
+use crate::core::editor;
+use crate::core::ui::table::FormattedBox;
+use crate::core::utils;
+use colored::Colorize;
+use std::env;
+use std::io::{self, IsTerminal};
+
+/// Text placed on the clipboard by the round-trip check.
+const CLIPBOARD_PROBE: &str = "treeclip doctor clipboard check";
+
+/// Outcome of one check.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// Result of one check, with a remediation hint when it did not pass.
+#[derive(Debug)]
+pub struct Check {
+    pub name: &'static str,
+    pub status: Status,
+    pub detail: String,
+    pub hint: Option<&'static str>,
+}
+
+/// Runs every check, in report order.
+pub fn run_checks() -> Vec<Check> {
+    vec![
+        check_clipboard(),
+        check_launcher(),
+        check_cli_editor(),
+        check_color(),
+        check_unicode(),
+    ]
+}
+
+/// Renders the status of each check as a box, followed by the details of each check
+/// and the hint of each one that did not pass.
+pub fn render(checks: &[Check]) -> String {
+    let mut report = FormattedBox::new("🩺 treeclip doctor");
+    for check in checks {
+        let status = match check.status {
+            Status::Pass => "✓ pass".green(),
+            Status::Warn => "⚠ warn".yellow(),
+            Status::Fail => "✗ fail".red(),
+        };
+        report = report.row(format!("{}:", check.name), status.to_string());
+    }
+
+    let mut rendered = report.render();
+    for check in checks {
+        rendered.push_str(&format!("\n  {}: {}", check.name.bold(), check.detail));
+        if let Some(hint) = check.hint.filter(|_| check.status != Status::Pass) {
+            rendered.push_str(&format!("\n    {} {hint}", "💡".yellow()));
+        }
+    }
+    rendered
+}
+
+// -------------------------------------------- Private Helper Functions --------------------------------------------
+
+/// Sets and reads back a test string (`--clipboard`, `unpack --from-clipboard`).
+fn check_clipboard() -> Check {
+    let backend = platform::backend_name();
+    match Clipboard::round_trip(CLIPBOARD_PROBE) {
+        Ok(()) if cfg!(any(target_os = "windows", target_os = "macos")) => Check {
+            name: "Clipboard",
+            status: Status::Pass,
+            detail: backend.to_string(),
+            hint: None,
+        },
+        // Elsewhere keeping the content after exit is up to the desktop
+        Ok(()) if platform::may_not_persist() => Check {
+            name: "Clipboard",
+            status: Status::Warn,
+            detail: format!("{backend}, no clipboard manager found"),
+            hint: Some("run a clipboard manager (e.g. CopyQ) so copies outlive treeclip"),
+        },
+        Ok(()) => Check {
+            name: "Clipboard",
+            status: Status::Pass,
+            detail: format!("{backend}, persists with a clipboard manager"),
+            hint: None,
+        },
+        Err(e) => Check {
+            name: "Clipboard",
+            status: Status::Fail,
+            detail: e.to_string(),
+            hint: Some(
+                "run inside a desktop session (DISPLAY set); on Linux install a clipboard manager",
+            ),
+        },
+    }
+}
+
+/// Looks for the graphical launcher `--editor` tries first.
+fn check_launcher() -> Check {
+    let launcher = editor::platform_open_command();
+    // `start` is a cmd.exe builtin, so there is no executable to find
+    let found = cfg!(windows) || utils::find_executable(launcher).is_some();
+    Check {
+        name: "Launcher",
+        status: if found { Status::Pass } else { Status::Warn },
+        detail: if found {
+            launcher.to_string()
+        } else {
+            format!("{launcher} (not found)")
+        },
+        hint: Some("install xdg-utils, or set $VISUAL/$EDITOR so --editor can fall back"),
+    }
+}
+
+/// Resolves `$VISUAL`/`$EDITOR`, used by `--wait` and when the launcher fails.
+fn check_cli_editor() -> Check {
+    match editor::configured_cli_editor() {
+        Some(program) if utils::find_executable(&program).is_some() => Check {
+            name: "Editor",
+            status: Status::Pass,
+            detail: program,
+            hint: None,
+        },
+        Some(program) => Check {
+            name: "Editor",
+            status: Status::Fail,
+            detail: format!("{program} (not found)"),
+            hint: Some("point $VISUAL or $EDITOR at an installed editor"),
+        },
+        None => Check {
+            name: "Editor",
+            status: Status::Warn,
+            detail: "not set, falls back to nano".to_string(),
+            hint: Some("set $EDITOR, e.g. EDITOR=vim or EDITOR=\"code --wait\""),
+        },
+    }
+}
+
+/// Whether colored output will be shown.
+fn check_color() -> Check {
+    let colorize = colored::control::SHOULD_COLORIZE.should_colorize();
+    let detail = match (colorize, env::var_os("NO_COLOR").is_some()) {
+        (true, _) => "enabled",
+        (false, true) => "disabled by NO_COLOR",
+        (false, false) => "disabled (not a terminal)",
+    };
+    Check {
+        name: "Color",
+        status: if colorize || !io::stdout().is_terminal() {
+            Status::Pass
+        } else {
+            Status::Warn
+        },
+        detail: detail.to_string(),
+        hint: Some("unset NO_COLOR, or set CLICOLOR_FORCE=1"),
+    }
+}
+
+/// Whether the locale is UTF-8, so banners, emojis and box drawing render.
+fn check_unicode() -> Check {
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .into_iter()
+        .find_map(|var| env::var(var).ok().filter(|value| !value.is_empty()));
+    let utf8 = cfg!(windows) || locale.as_deref().is_some_and(is_utf8_locale);
+    Check {
+        name: "Unicode",
+        status: if utf8 { Status::Pass } else { Status::Warn },
+        detail: locale.unwrap_or_else(|| "no locale set".to_string()),
+        hint: Some("use a UTF-8 locale, e.g. LANG=en_US.UTF-8, or pass --fast-mode"),
+    }
+}
+
+/// Returns `true` if a locale name such as `en_US.UTF-8` uses UTF-8.
+fn is_utf8_locale(locale: &str) -> bool {
+    let locale = locale.to_ascii_lowercase();
+    locale.contains("utf-8") || locale.contains("utf8")
+}
+
+#[cfg(test)]
+mod doctor_tests {
+    use super::*;
+
+    #[test]
+    fn test_is_utf8_locale() {
+        assert!(is_utf8_locale("en_US.UTF-8"));
+        assert!(is_utf8_locale("C.utf8"));
+        assert!(!is_utf8_locale("C"));
+        assert!(!is_utf8_locale("POSIX"));
+    }
+
+    #[test]
+    fn test_render_lists_hints_for_failed_checks() {
+        let checks = [
+            Check {
+                name: "Editor",
+                status: Status::Fail,
+                detail: "vim (not found)".to_string(),
+                hint: Some("install vim"),
+            },
+            Check {
+                name: "Unicode",
+                status: Status::Pass,
+                detail: "en_US.UTF-8".to_string(),
+                hint: Some("use a UTF-8 locale"),
+            },
+        ];
+
+        let report = render(&checks);
+        assert!(report.contains("vim (not found)"));
+        assert!(report.contains("💡 install vim"));
+        assert!(!report.contains("use a UTF-8 locale"));
+    }
+}
@@ -6,8 +6,8 @@
 
 use crate::core::errors::{EditorError, FileSystemError};
 use anyhow::Context;
-use std::path::Path;
-use std::{env, fs, process};
+use std::path::{Path, PathBuf};
+use std::{env, fs, io, process};
 
 /// Opens the file in the system's default text editor.
 ///
@@ -20,7 +20,10 @@ use std::{env, fs, process};
 /// - **Unix/Linux**: Uses `xdg-open` command
 ///
 /// If the graphical editor fails, attempts to use the CLI editor specified
-/// in the `EDITOR` environment variable, or `/bin/nano` as final fallback.
+/// in the `VISUAL` or `EDITOR` environment variable, or `/bin/nano` as final fallback.
+///
+/// The path is canonicalized when possible; if that fails (e.g. on some network
+/// filesystems) the path is passed to the editor as given.
 ///
 /// # Errors
 ///
@@ -35,13 +38,7 @@ pub fn open(path: &Path) -> anyhow::Result<()> {
         .into());
     }
 
-    let canonical_path = path
-        .canonicalize()
-        .map_err(|e| FileSystemError::CanonicalizeFailed {
-            path: path.to_path_buf(),
-            source: e,
-        })
-        .with_context(|| format!("Failed to resolve absolute path for: {}", path.display()))?;
+    let canonical_path = resolve_path(path);
 
     match process::Command::new(command).arg(&canonical_path).status() {
         Ok(status) if status.success() => Ok(()),
@@ -64,11 +61,74 @@ pub fn open(path: &Path) -> anyhow::Result<()> {
     }
 }
 
+/// Opens the file in the CLI editor and blocks until the editor exits.
+///
+/// Graphical launchers (`xdg-open`, `open`, `start`) hand the file to the desktop and
+/// return at once, so there is nothing to wait on. This goes straight to `$VISUAL` or
+/// `$EDITOR` instead; for a GUI editor, set one that blocks, e.g. `code --wait`.
+/// Editors that detach from the terminal themselves cannot be waited on either.
+///
+/// # Errors
+///
+/// Returns `EditorError` if the editor cannot be launched or exits unsuccessfully.
+pub fn open_and_wait(path: &Path) -> anyhow::Result<()> {
+    let resolved = resolve_path(path);
+    open_with_cli_editor(&resolved)
+        .with_context(|| format!("Failed to open editor for file: {}", path.display()))
+}
+
+/// Shows the file in the user's pager (`$PAGER`, else `less`), blocking until it exits.
+///
+/// The file is written to the pager's stdin, the way `git` and `man` feed theirs.
+/// Quitting the pager before the end is not an error.
+///
+/// # Errors
+///
+/// Returns `EditorError` if the pager cannot be launched or exits unsuccessfully.
+pub fn page(path: &Path) -> anyhow::Result<()> {
+    let (program, args) = pager_command(env::var("PAGER").ok());
+    let mut file = fs::File::open(path)
+        .map_err(|e| FileSystemError::ReadFailed {
+            path: path.to_path_buf(),
+            source: e,
+        })
+        .with_context(|| format!("Failed to open file for the pager: {}", path.display()))?;
+
+    let mut pager = process::Command::new(&program)
+        .args(&args)
+        .stdin(process::Stdio::piped())
+        .spawn()
+        .map_err(|e| EditorError::OpenFailed {
+            path: path.to_path_buf(),
+            source: e,
+        })
+        .with_context(|| format!("Failed to launch pager '{}'", program))?;
+
+    if let Some(mut stdin) = pager.stdin.take() {
+        match io::copy(&mut file, &mut stdin) {
+            Err(e) if e.kind() != io::ErrorKind::BrokenPipe => {
+                return Err(e).with_context(|| format!("Failed to write to pager '{}'", program));
+            }
+            _ => {}
+        }
+    }
+
+    let status = pager
+        .wait()
+        .with_context(|| format!("Failed to wait for pager '{}'", program))?;
+    if !status.success() {
+        return Err(EditorError::ProcessFailed { status }.into());
+    }
+
+    Ok(())
+}
+
 /// Deletes the specified file from the filesystem.
 ///
 /// # Note
 ///
-/// There is no guarantee that the file is immediately deleted. Depending on
+/// After [`open`], the editor may not have loaded the file yet; use [`open_and_wait`]
+/// before deleting. There is no guarantee that the file is immediately deleted. Depending on
 /// platform and open file descriptors, removal may be delayed.
 ///
 /// # Errors
@@ -85,6 +145,19 @@ pub fn delete(path: &Path) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Returns the graphical launcher [`open`] tries first (`xdg-open`, `open`, `start`).
+pub fn platform_open_command() -> &'static str {
+    get_platform_open_command()
+}
+
+/// Returns the CLI editor program set in `$VISUAL` or `$EDITOR`, if either is usable.
+///
+/// Unlike opening a file, this does not fall back to nano.
+pub fn configured_cli_editor() -> Option<String> {
+    configured_editor_command(env::var("VISUAL").ok(), env::var("EDITOR").ok())
+        .map(|(program, _)| program)
+}
+
 // -------------------------------------------- Private Helper Functions --------------------------------------------
 
 /// Returns the platform-specific command for opening files.
@@ -100,17 +173,24 @@ fn get_platform_open_command() -> &'static str {
     }
 }
 
-/// Opens the file using a CLI text editor.
-fn open_with_cli_editor(path: &Path) -> anyhow::Result<()> {
-    let default_cli_editor = env::var("EDITOR").unwrap_or_else(|e| {
+/// Canonicalizes `path`, falling back to the path as given if that fails.
+fn resolve_path(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|e| {
         eprintln!(
-            "Error reading EDITOR environment variable: {}. Falling back to nano.",
+            "Could not resolve absolute path for {}: {}. Using it as given.",
+            path.display(),
             e
         );
-        "/bin/nano".to_string()
-    });
+        path.to_path_buf()
+    })
+}
 
-    let status = process::Command::new(&default_cli_editor)
+/// Opens the file using a CLI text editor.
+fn open_with_cli_editor(path: &Path) -> anyhow::Result<()> {
+    let (program, args) = cli_editor_command(env::var("VISUAL").ok(), env::var("EDITOR").ok());
+
+    let status = process::Command::new(&program)
+        .args(&args)
         .arg(path)
         .status()
         .map_err(|e| EditorError::OpenFailed {
@@ -120,7 +200,7 @@ fn open_with_cli_editor(path: &Path) -> anyhow::Result<()> {
         .with_context(|| {
             format!(
                 "Failed to launch editor '{}' for file: {}",
-                default_cli_editor,
+                program,
                 path.display()
             )
         })?;
@@ -132,12 +212,68 @@ fn open_with_cli_editor(path: &Path) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Picks the CLI editor from `$VISUAL`, then `$EDITOR`, falling back to nano.
+///
+/// Values are split shell-style, so `EDITOR="code --wait"` runs `code` with `--wait`.
+/// Empty or unparsable values are skipped.
+fn cli_editor_command(visual: Option<String>, editor: Option<String>) -> (String, Vec<String>) {
+    configured_editor_command(visual, editor).unwrap_or_else(|| {
+        eprintln!("No usable VISUAL or EDITOR set. Falling back to nano.");
+        ("/bin/nano".to_string(), Vec::new())
+    })
+}
+
+/// Splits `$PAGER` into program and arguments, falling back to `less`.
+///
+/// An empty or unparsable value counts as unset.
+fn pager_command(pager: Option<String>) -> (String, Vec<String>) {
+    pager
+        .and_then(|value| shell_words::split(&value).ok())
+        .and_then(|words| {
+            let mut words = words.into_iter();
+            Some((words.next()?, words.collect()))
+        })
+        .unwrap_or_else(|| ("less".to_string(), Vec::new()))
+}
+
+/// Splits the first usable value of `$VISUAL` and `$EDITOR` into program and arguments.
+fn configured_editor_command(
+    visual: Option<String>,
+    editor: Option<String>,
+) -> Option<(String, Vec<String>)> {
+    [visual, editor]
+        .into_iter()
+        .flatten()
+        .find_map(|value| match shell_words::split(&value) {
+            Ok(words) if !words.is_empty() => {
+                let mut words = words.into_iter();
+                let program = words.next()?;
+                Some((program, words.collect()))
+            }
+            Ok(_) => None,
+            Err(e) => {
+                eprintln!("Could not parse editor command '{}': {}", value, e);
+                None
+            }
+        })
+}
+
 #[cfg(test)]
 mod editor_tests {
     use super::*;
     use std::fs;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_pager_command_falls_back_to_less() {
+        assert_eq!(pager_command(None), ("less".to_string(), vec![]));
+        assert_eq!(pager_command(Some("  ".to_string())).0, "less");
+        assert_eq!(
+            pager_command(Some("less -R".to_string())),
+            ("less".to_string(), vec!["-R".to_string()])
+        );
+    }
+
     #[test]
     fn test_get_platform_open_command() {
         let command = get_platform_open_command();
@@ -153,6 +289,31 @@ mod editor_tests {
         }
     }
 
+    #[test]
+    fn test_cli_editor_command_splits_arguments() {
+        let (program, args) = cli_editor_command(None, Some("code --wait".to_string()));
+        assert_eq!(program, "code");
+        assert_eq!(args, ["--wait"]);
+
+        let (program, args) =
+            cli_editor_command(None, Some("'/opt/My Editor/bin/edit' -n".to_string()));
+        assert_eq!(program, "/opt/My Editor/bin/edit");
+        assert_eq!(args, ["-n"]);
+    }
+
+    #[test]
+    fn test_cli_editor_command_prefers_visual() {
+        let (program, _) =
+            cli_editor_command(Some("vim".to_string()), Some("code --wait".to_string()));
+        assert_eq!(program, "vim");
+
+        // Empty or unparsable values fall through to the next candidate
+        let (program, _) = cli_editor_command(Some("  ".to_string()), Some("nvim".to_string()));
+        assert_eq!(program, "nvim");
+        let (program, _) = cli_editor_command(Some("'unclosed".to_string()), None);
+        assert_eq!(program, "/bin/nano");
+    }
+
     #[test]
     fn test_delete_file() -> anyhow::Result<()> {
         let temp_dir = TempDir::new()?;
@@ -178,13 +339,22 @@ mod editor_tests {
     }
 
     #[test]
-    fn test_open_with_nonexistent_file() {
-        let result = open(Path::new("/nonexistent/file.txt"));
-        // This will fail because canonicalize fails on non-existent paths
-        assert!(result.is_err());
+    fn test_resolve_path_falls_back_when_canonicalize_fails() {
+        // canonicalize fails on non-existent paths; the editor still gets the path as given
+        let missing = Path::new("/nonexistent/file.txt");
+        assert_eq!(resolve_path(missing), missing);
+    }
 
-        let error_msg = format!("{:?}", result.unwrap_err());
-        assert!(error_msg.contains("Failed to resolve") || error_msg.contains("canonicalize"));
+    #[test]
+    fn test_resolve_path_canonicalizes_existing_file() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let file_path = temp_dir.path().join("test.txt");
+        fs::write(&file_path, "test content")?;
+
+        let relative = temp_dir.path().join(".").join("test.txt");
+        assert_eq!(resolve_path(&relative), file_path.canonicalize()?);
+
+        Ok(())
     }
 
     #[test]
@@ -0,0 +1,243 @@
+//! editorconfig - Per-file normalization settings from `.editorconfig` (`--respect-editorconfig`).
+//!
+//! Reads the properties treeclip can act on: `indent_style`, `indent_size`, `tab_width`
+//! and `end_of_line`. Files are looked up from each file's directory upwards until one
+//! declares `root = true`; nearer files override farther ones, and later sections
+//! override earlier ones. Section globs support `*`, `?`, `[...]`, `{a,b}` and `**` as a
+//! whole path component (`lib/**/*.js`); numeric ranges (`{1..3}`) are not supported
+//! and match literally.
+
+use globset::{Glob, GlobBuilder, GlobMatcher};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the file read in each directory.
+const FILE_NAME: &str = ".editorconfig";
+
+/// Line ending requested by `end_of_line`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EndOfLine {
+    Lf,
+    Crlf,
+    Cr,
+}
+
+impl EndOfLine {
+    /// The characters ending each line.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Lf => "\n",
+            Self::Crlf => "\r\n",
+            Self::Cr => "\r",
+        }
+    }
+}
+
+/// The settings `.editorconfig` files give one file.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Properties {
+    /// Whether `indent_style` is `space`.
+    pub indent_with_spaces: bool,
+    /// Columns per tab stop: `tab_width`, else a numeric `indent_size`.
+    pub tab_width: Option<usize>,
+    /// Line ending from `end_of_line`.
+    pub end_of_line: Option<EndOfLine>,
+}
+
+impl Properties {
+    /// Tab stop width to expand tabs with; only set for files indented with spaces.
+    pub fn expand_tabs_width(&self) -> Option<usize> {
+        self.tab_width.filter(|_| self.indent_with_spaces)
+    }
+}
+
+/// Resolves [`Properties`] for files, reading each directory's `.editorconfig` once.
+#[derive(Default)]
+pub struct EditorConfig {
+    /// Parsed files by directory; `None` where a directory has none.
+    files: HashMap<PathBuf, Option<ConfigFile>>,
+}
+
+/// One parsed `.editorconfig` file.
+struct ConfigFile {
+    /// Whether lookup stops at this file.
+    root: bool,
+    sections: Vec<Section>,
+}
+
+/// A `[glob]` section and its `key = value` pairs, keys lowercased.
+struct Section {
+    glob: Option<GlobMatcher>,
+    properties: Vec<(String, String)>,
+}
+
+impl EditorConfig {
+    /// Returns the settings for `path`, which must be absolute.
+    pub fn properties(&mut self, path: &Path) -> Properties {
+        // Nearest directory first, up to and including the first `root = true`
+        let mut dirs = Vec::new();
+        for dir in path.ancestors().skip(1) {
+            let file = self
+                .files
+                .entry(dir.to_path_buf())
+                .or_insert_with(|| ConfigFile::read(dir));
+            let is_root = file.as_ref().is_some_and(|file| file.root);
+            dirs.push(dir);
+            if is_root {
+                break;
+            }
+        }
+
+        let mut values: HashMap<&str, &str> = HashMap::new();
+        for dir in dirs.into_iter().rev() {
+            let Some(Some(file)) = self.files.get(dir) else {
+                continue;
+            };
+            let Ok(relative) = path.strip_prefix(dir) else {
+                continue;
+            };
+            for section in &file.sections {
+                if section
+                    .glob
+                    .as_ref()
+                    .is_some_and(|glob| glob.is_match(relative))
+                {
+                    for (key, value) in &section.properties {
+                        values.insert(key, value);
+                    }
+                }
+            }
+        }
+
+        resolve(&values)
+    }
+}
+
+// -------------------------------------------- Private Helper Functions --------------------------------------------
+
+impl ConfigFile {
+    /// Reads `dir`'s `.editorconfig`, if it has a readable one.
+    fn read(dir: &Path) -> Option<Self> {
+        fs::read_to_string(dir.join(FILE_NAME))
+            .ok()
+            .map(|text| Self::parse(&text))
+    }
+
+    /// Parses the INI-like format; lines that are not a section or a pair are skipped.
+    fn parse(text: &str) -> Self {
+        let mut root = false;
+        let mut sections: Vec<Section> = Vec::new();
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+            if let Some(pattern) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                sections.push(Section {
+                    glob: section_glob(pattern),
+                    properties: Vec::new(),
+                });
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let key = key.trim().to_ascii_lowercase();
+            let value = value.trim().to_ascii_lowercase();
+            match sections.last_mut() {
+                Some(section) => section.properties.push((key, value)),
+                // Only `root` is meaningful before the first section
+                None => root |= key == "root" && value == "true",
+            }
+        }
+        Self { root, sections }
+    }
+}
+
+/// Compiles a section pattern to match paths relative to the file's directory.
+///
+/// Patterns without a `/` match a file name at any depth, as editorconfig specifies.
+fn section_glob(pattern: &str) -> Option<GlobMatcher> {
+    let pattern = match pattern.strip_prefix('/') {
+        Some(anchored) => anchored.to_string(),
+        None if pattern.contains('/') => pattern.to_string(),
+        None => format!("**/{pattern}"),
+    };
+    GlobBuilder::new(&pattern)
+        .literal_separator(true)
+        .build()
+        .as_ref()
+        .map(Glob::compile_matcher)
+        .ok()
+}
+
+/// Turns the raw values that apply to a file into [`Properties`].
+fn resolve(values: &HashMap<&str, &str>) -> Properties {
+    let number = |key: &str| values.get(key).and_then(|value| value.parse().ok());
+    Properties {
+        indent_with_spaces: values.get("indent_style") == Some(&"space"),
+        tab_width: number("tab_width").or_else(|| number("indent_size")),
+        end_of_line: match values.get("end_of_line").copied() {
+            Some("lf") => Some(EndOfLine::Lf),
+            Some("crlf") => Some(EndOfLine::Crlf),
+            Some("cr") => Some(EndOfLine::Cr),
+            _ => None,
+        },
+    }
+}
+
+#[cfg(test)]
+mod editorconfig_tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_properties_follow_sections_and_nesting() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("src/legacy"))?;
+        fs::write(
+            root.join(FILE_NAME),
+            "root = true\n\n[*]\nend_of_line = lf\n\n[*.{rs,toml}]\nindent_style = space\nindent_size = 4\n\n[Makefile]\nindent_style = tab\n",
+        )?;
+        fs::write(
+            root.join("src/legacy").join(FILE_NAME),
+            "# older code\n[*.rs]\nindent_size = 2\nend_of_line = CRLF\n",
+        )?;
+
+        let mut editorconfig = EditorConfig::default();
+        let main = editorconfig.properties(&root.join("src/main.rs"));
+        assert_eq!(main.expand_tabs_width(), Some(4));
+        assert_eq!(main.end_of_line, Some(EndOfLine::Lf));
+
+        // The nearer file wins for the keys it sets
+        let old = editorconfig.properties(&root.join("src/legacy/old.rs"));
+        assert_eq!(old.expand_tabs_width(), Some(2));
+        assert_eq!(old.end_of_line, Some(EndOfLine::Crlf));
+
+        let makefile = editorconfig.properties(&root.join("Makefile"));
+        assert_eq!(makefile.expand_tabs_width(), None);
+        assert_eq!(makefile.end_of_line, Some(EndOfLine::Lf));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_anchored_sections_match_from_their_directory() {
+        let file =
+            ConfigFile::parse("[/docs/*.md]\ntab_width = 8\n[lib/**/*.js]\nindent_style = space\n");
+        let matches = |section: usize, path: &str| {
+            file.sections[section]
+                .glob
+                .as_ref()
+                .is_some_and(|glob| glob.is_match(path))
+        };
+
+        assert!(matches(0, "docs/intro.md"));
+        assert!(!matches(0, "src/docs/intro.md"));
+        assert!(!matches(0, "docs/guide/intro.md"));
+        assert!(matches(1, "lib/a/b.js"));
+        assert!(!file.root);
+    }
+}
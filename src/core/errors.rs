@@ -161,7 +161,7 @@
 //!
 //! ## Testing Errors
 //!
-//! ```rust
+//! ```rust,ignore
 //! #[test]
 //! fn test_error_handling() -> anyhow::Result<()> {
 //!     let result = risky_operation();
@@ -295,6 +295,7 @@ use thiserror::Error;
 
 /// Main error type for TreeClip operations.
 #[derive(Error, Debug)]
+#[allow(dead_code)]
 pub enum TreeClipError {
     /// Error related to clipboard operations.
     #[error("Clipboard error: {0}")]
@@ -316,6 +317,14 @@ pub enum TreeClipError {
     #[error("Pattern error: {0}")]
     Pattern(#[from] PatternError),
 
+    /// Error related to unpacking treeclip output.
+    #[error("Unpack error: {0}")]
+    Unpack(#[from] UnpackError),
+
+    /// Error related to rendering the resolved options.
+    #[error("Config error: {0}")]
+    Config(#[from] ConfigError),
+
     /// Generic I/O error with context.
     #[error("I/O error: {message}")]
     Io {
@@ -334,7 +343,11 @@ pub enum ClipboardError {
     #[error("Failed to set clipboard content: {0}")]
     SetFailed(String),
 
+    #[error("Failed to read clipboard content: {0}")]
+    GetFailed(String),
+
     #[error("Failed to read file for clipboard: {path}")]
+    #[allow(dead_code)]
     ReadFailed {
         path: PathBuf,
         #[source]
@@ -351,6 +364,9 @@ pub enum FileSystemError {
     #[error("Path does not exist: {0}")]
     PathNotFound(PathBuf),
 
+    #[error("{} paths do not exist:{}", .0.len(), list_paths(.0))]
+    PathsNotFound(Vec<PathBuf>),
+
     #[error("Failed to canonicalize path: {path}")]
     CanonicalizeFailed {
         path: PathBuf,
@@ -386,7 +402,11 @@ pub enum FileSystemError {
         source: std::io::Error,
     },
 
+    #[error("Refusing to overwrite existing file: {0} (pass --force to overwrite it)")]
+    OutputExists(PathBuf),
+
     #[error("Permission denied: {path}")]
+    #[allow(dead_code)]
     PermissionDenied { path: PathBuf },
 }
 
@@ -401,9 +421,11 @@ pub enum TraversalError {
     },
 
     #[error("Failed to access directory entry: {path}")]
+    #[allow(dead_code)]
     EntryAccessFailed { path: PathBuf },
 
     #[error("Failed to write output file: {path}")]
+    #[allow(dead_code)]
     OutputWriteFailed {
         path: PathBuf,
         #[source]
@@ -412,6 +434,9 @@ pub enum TraversalError {
 
     #[error("No files found in directory: {0}")]
     NoFilesFound(PathBuf),
+
+    #[error("Traversal deadline reached (--timeout)")]
+    DeadlineReached,
 }
 
 /// Errors specific to editor operations.
@@ -431,6 +456,7 @@ pub enum EditorError {
     NoEditorFound(String),
 
     #[error("Failed to get EDITOR environment variable")]
+    #[allow(dead_code)]
     NoEditorEnvVar,
 }
 
@@ -444,8 +470,10 @@ pub enum PatternError {
         source: ignore::Error,
     },
 
+    #[error("{} invalid exclusion patterns:{}", .0.len(), list_pattern_errors(.0))]
+    InvalidPatterns(Vec<PatternError>),
+
     #[error("Failed to read ignore file: {path}")]
-    #[allow(dead_code)]
     IgnoreFileReadFailed {
         path: PathBuf,
         #[source]
@@ -459,8 +487,26 @@ pub enum PatternError {
     },
 }
 
+/// Errors specific to unpacking treeclip output back into files.
+#[derive(Error, Debug)]
+pub enum UnpackError {
+    #[error("Content is not treeclip output (no '==> path' headers found)")]
+    NotATreeclipDump,
+
+    #[error("Refusing to unpack outside the output directory: {0}")]
+    UnsafePath(PathBuf),
+}
+
+/// Errors specific to rendering the resolved options (`--print-config`).
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("Cannot render option as TOML: {0}")]
+    Unsupported(String),
+}
+
 impl TreeClipError {
     /// Creates an I/O error with context message.
+    #[allow(dead_code)]
     pub fn io_with_context(message: impl Into<String>, source: std::io::Error) -> Self {
         Self::Io {
             message: message.into(),
@@ -469,6 +515,27 @@ impl TreeClipError {
     }
 }
 
+/// Renders paths as an indented list, one per line.
+fn list_paths(paths: &[PathBuf]) -> String {
+    paths
+        .iter()
+        .map(|path| format!("\n  - {}", path.display()))
+        .collect()
+}
+
+/// Renders pattern errors as an indented list with the reason for each, one per line.
+fn list_pattern_errors(errors: &[PatternError]) -> String {
+    errors
+        .iter()
+        .map(|error| match error {
+            PatternError::InvalidPattern { pattern, source } => {
+                format!("\n  - '{pattern}': {source}")
+            }
+            other => format!("\n  - {other}"),
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod errors_tests {
     use super::*;
@@ -485,6 +552,12 @@ mod errors_tests {
         let path = PathBuf::from("/test/path");
         let err = FileSystemError::PathNotFound(path.clone());
         assert!(err.to_string().contains("/test/path"));
+
+        let err = FileSystemError::PathsNotFound(vec![path, PathBuf::from("b")]);
+        assert_eq!(
+            err.to_string(),
+            "2 paths do not exist:\n  - /test/path\n  - b"
+        );
     }
 
     #[test]
@@ -514,6 +587,17 @@ mod errors_tests {
         assert!(err.to_string().contains("Invalid exclusion pattern"));
     }
 
+    #[test]
+    fn test_unpack_error_display() {
+        let err = UnpackError::UnsafePath(PathBuf::from("../escape"));
+        assert!(err.to_string().contains("../escape"));
+        assert!(
+            UnpackError::NotATreeclipDump
+                .to_string()
+                .contains("not treeclip output")
+        );
+    }
+
     #[test]
     fn test_io_error_with_context() {
         let io_err = io::Error::new(io::ErrorKind::NotFound, "file not found");
@@ -1,22 +1,42 @@
 //! exclude - Handles file and directory exclusion patterns using gitignore-style rules.
 
 use crate::core::errors::PatternError;
+use crate::core::git;
+use crate::core::ui::animations::statusln;
 use crate::core::ui::messages::Messages;
 use anyhow::Context;
+use ignore::Match;
 use ignore::gitignore::{Gitignore, GitignoreBuilder};
-use std::path::Path;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+pub mod presets;
+
+/// Ignore files loaded from the root, lowest precedence first.
+///
+/// `.ignore` and `.rgignore` follow the ripgrep/fd convention, so their rules are
+/// shared with those tools; `.treeclipignore` overrides both, as `.rgignore` overrides
+/// `.ignore` in ripgrep.
+pub const IGNORE_FILES: &[&str] = &[".ignore", ".rgignore", ".treeclipignore"];
 
 /// ExcludeMatcher determines whether paths should be excluded from traversal.
 pub struct ExcludeMatcher {
     inner: Gitignore,
+    /// Patterns that only apply to directories (`--exclude-dir`).
+    dirs_only: Gitignore,
+    /// Patterns that only apply to files (`--exclude-file`).
+    files_only: Gitignore,
+    /// `.gitignore` files from the root up to the git root, deepest first
+    /// (`--resolve-gitignore-in-parent-dirs`).
+    gitignores: Vec<Gitignore>,
 }
 
 impl ExcludeMatcher {
-    /// Creates a new ExcludeMatcher with patterns from .treeclipignore and CLI arguments.
+    /// Creates a new ExcludeMatcher with patterns from the ignore files and CLI arguments.
     ///
     /// # Arguments
     ///
-    /// * `root` - Root directory to search for .treeclipignore file
+    /// * `root` - Root directory to search for ignore files (see [`IGNORE_FILES`])
     /// * `cli_patterns` - Additional exclusion patterns from command-line arguments
     ///
     /// # Errors
@@ -24,11 +44,52 @@ impl ExcludeMatcher {
     /// Returns `PatternError` if:
     /// - The gitignore builder fails to compile patterns
     /// - Invalid pattern syntax is provided
+    #[cfg(test)]
     pub fn new(root: &Path, cli_patterns: &[String]) -> anyhow::Result<Self> {
+        Self::with_presets(root, &[], &[], false, cli_patterns)
+    }
+
+    /// Creates a new ExcludeMatcher that also applies built-in preset patterns.
+    ///
+    /// Later sources win, so the precedence from lowest to highest is: presets,
+    /// `.ignore`, `.rgignore`, `.treeclipignore`, `--exclude-from` files, CLI patterns.
+    /// The run command puts `TREECLIP_EXCLUDE` patterns ahead of the `-e` values in
+    /// `cli_patterns`, so they rank just below them. Any of them can re-include an
+    /// earlier match with `!pattern`.
+    ///
+    /// Ignore files in the root are optional: an unreadable or invalid one is skipped
+    /// with a warning. Files named with `--exclude-from` were asked for, so the same
+    /// problems are errors unless `ignore_missing` is set.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - Root directory to search for ignore files (see [`IGNORE_FILES`])
+    /// * `presets` - Built-in patterns (see [`presets`])
+    /// * `exclude_from` - Extra ignore files named on the command line
+    /// * `ignore_missing` - Warn instead of failing when an `exclude_from` file cannot be used
+    /// * `cli_patterns` - Additional exclusion patterns from command-line arguments
+    ///
+    /// # Errors
+    ///
+    /// Returns `PatternError` if any pattern is invalid, an `exclude_from` file cannot
+    /// be used, or the matcher fails to build.
+    pub fn with_presets(
+        root: &Path,
+        presets: &[&str],
+        exclude_from: &[PathBuf],
+        ignore_missing: bool,
+        cli_patterns: &[String],
+    ) -> anyhow::Result<Self> {
         let mut builder = GitignoreBuilder::new(root);
 
-        // Add .treeclipignore file patterns (if exists)
-        Self::add_ignore_file(&mut builder, root)?;
+        // Add built-in presets (lowest precedence)
+        Self::add_preset_patterns(&mut builder, presets)
+            .with_context(|| "Failed to process built-in exclusion presets")?;
+
+        // Add ignore file patterns (those that exist)
+        Self::add_ignore_files(&mut builder, root);
+
+        Self::add_exclude_from(&mut builder, exclude_from, ignore_missing)?;
 
         // Add CLI patterns
         Self::add_cli_patterns(&mut builder, cli_patterns)
@@ -44,62 +105,331 @@ impl ExcludeMatcher {
                 )
             })?;
 
-        Ok(Self { inner })
+        Ok(Self {
+            inner,
+            dirs_only: Gitignore::empty(),
+            files_only: Gitignore::empty(),
+            gitignores: Vec::new(),
+        })
+    }
+
+    /// Adds patterns that only match directories or only match files.
+    ///
+    /// Directory names become `name/` patterns, so a file called `build` is kept when
+    /// `build` is given as a directory. File globs are checked against files only,
+    /// which plain gitignore patterns cannot express.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PatternError` if any pattern is invalid or a matcher fails to build.
+    pub fn with_typed_patterns(
+        mut self,
+        root: &Path,
+        dirs: &[String],
+        files: &[String],
+    ) -> anyhow::Result<Self> {
+        self.dirs_only = build_matcher(root, dirs.iter().map(|name| dir_pattern(name)))
+            .with_context(|| "Failed to process --exclude-dir patterns")?;
+        self.files_only = build_matcher(root, files.iter().map(|glob| file_pattern(glob)))
+            .with_context(|| "Failed to process --exclude-file patterns")?;
+        Ok(self)
+    }
+
+    /// Adds the `.gitignore` of `root` and of each parent directory up to the git root,
+    /// as git applies them when run from a subdirectory.
+    ///
+    /// Each file is matched relative to its own directory, and a deeper file overrides
+    /// a shallower one. Together they rank below every other source, so an ignore file
+    /// or CLI pattern can still re-include a path with `!pattern`. Outside a repository
+    /// only the root's own `.gitignore` is read. `.gitignore` files below the root are
+    /// not read. Like ignore files in the root, one that cannot be used is skipped with
+    /// a warning.
+    pub fn with_parent_gitignores(mut self, root: &Path) -> Self {
+        let Ok(root) = std::path::absolute(root) else {
+            return self;
+        };
+        let top = git::find_root(&root).unwrap_or_else(|| root.clone());
+
+        for dir in root.ancestors() {
+            let gitignore = dir.join(".gitignore");
+            if gitignore.exists() {
+                let mut builder = GitignoreBuilder::new(dir);
+                let matcher = add_ignore_file(&mut builder, &gitignore).and_then(|()| {
+                    builder
+                        .build()
+                        .map_err(|e| PatternError::BuildFailed { source: e })
+                });
+                match matcher {
+                    Ok(matcher) => {
+                        statusln!(
+                            "{}",
+                            Messages::found_ignore_file(&gitignore.display().to_string())
+                        );
+                        self.gitignores.push(matcher);
+                    }
+                    Err(e) => eprintln!(
+                        "{}",
+                        Messages::skipped_ignore_file(&format!("{:#}", anyhow::Error::new(e)))
+                    ),
+                }
+            }
+            if dir == top {
+                break;
+            }
+        }
+        self
     }
 
     /// Checks if a path should be excluded based on configured patterns.
     pub fn is_excluded(&self, path: &Path) -> bool {
-        self.inner.matched(path, path.is_dir()).is_ignore()
+        let is_dir = path.is_dir();
+        let typed = if is_dir {
+            &self.dirs_only
+        } else {
+            &self.files_only
+        };
+
+        match self.inner.matched(path, is_dir) {
+            Match::Ignore(_) => true,
+            Match::Whitelist(_) => typed.matched(path, is_dir).is_ignore(),
+            Match::None => {
+                typed.matched(path, is_dir).is_ignore() || self.is_gitignored(path, is_dir)
+            }
+        }
     }
 }
 
 // -------------------------------------------- Private Helper Functions --------------------------------------------
 
 impl ExcludeMatcher {
-    /// Adds patterns from .treeclipignore file if it exists.
-    fn add_ignore_file(builder: &mut GitignoreBuilder, root: &Path) -> anyhow::Result<()> {
-        let ignore_file = root.join(".treeclipignore");
-
-        // TODO: Path operations are not concurrent-safe - consider locking or TOCTOU handling
-        // See: https://doc.rust-lang.org/stable/std/fs/index.html (TOCTOU section)
-        if ignore_file.exists() {
-            println!(
-                "{}",
-                Messages::found_ignore_file(&ignore_file.display().to_string())
-            );
-            println!("{}", Messages::applying_ignore_rules());
+    /// Checks the `.gitignore` chain; the deepest file with a matching rule decides.
+    fn is_gitignored(&self, path: &Path, is_dir: bool) -> bool {
+        if self.gitignores.is_empty() {
+            return false;
+        }
+        // Each matcher has its own root, so compare absolute paths
+        let Ok(path) = std::path::absolute(path) else {
+            return false;
+        };
+        self.gitignores
+            .iter()
+            .map(|gitignore| gitignore.matched(&path, is_dir))
+            .find(|matched| !matched.is_none())
+            .is_some_and(|matched| matched.is_ignore())
+    }
 
-            // Add with error handling
-            builder.add(&ignore_file);
+    /// Adds patterns from each of [`IGNORE_FILES`] that exists, in precedence order.
+    ///
+    /// These files are optional, so one that cannot be used only gets a warning.
+    fn add_ignore_files(builder: &mut GitignoreBuilder, root: &Path) {
+        let mut found_any = false;
+
+        for name in IGNORE_FILES {
+            let ignore_file = root.join(name);
+
+            // TODO: Path operations are not concurrent-safe - consider locking or TOCTOU handling
+            // See: https://doc.rust-lang.org/stable/std/fs/index.html (TOCTOU section)
+            if ignore_file.exists() {
+                statusln!(
+                    "{}",
+                    Messages::found_ignore_file(&ignore_file.display().to_string())
+                );
+                found_any = true;
+
+                if let Err(e) = add_ignore_file(builder, &ignore_file) {
+                    eprintln!(
+                        "{}",
+                        Messages::skipped_ignore_file(&format!("{:#}", anyhow::Error::new(e)))
+                    );
+                }
+            }
         }
 
-        Ok(())
+        if found_any {
+            statusln!("{}", Messages::applying_ignore_rules());
+        }
     }
 
-    /// Adds CLI-provided exclusion patterns to the builder.
-    fn add_cli_patterns(
+    /// Adds patterns from each file named with `--exclude-from`, in the order given.
+    ///
+    /// A file that cannot be used is an error, or a warning with `ignore_missing`.
+    fn add_exclude_from(
         builder: &mut GitignoreBuilder,
-        cli_patterns: &[String],
+        files: &[PathBuf],
+        ignore_missing: bool,
     ) -> anyhow::Result<()> {
-        for (index, pat) in cli_patterns.iter().enumerate() {
+        for file in files {
+            match add_ignore_file(builder, file) {
+                Ok(()) => statusln!(
+                    "{}",
+                    Messages::found_ignore_file(&file.display().to_string())
+                ),
+                Err(e) if ignore_missing => {
+                    eprintln!(
+                        "{}",
+                        Messages::skipped_ignore_file(&format!("{:#}", anyhow::Error::new(e)))
+                    );
+                }
+                Err(e) => {
+                    return Err(e).with_context(|| {
+                        format!("Failed to load --exclude-from file: {}", file.display())
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds built-in preset patterns to the builder.
+    fn add_preset_patterns(builder: &mut GitignoreBuilder, presets: &[&str]) -> anyhow::Result<()> {
+        for pat in presets {
             builder
                 .add_line(None, pat)
                 .map_err(|e| PatternError::InvalidPattern {
-                    pattern: pat.clone(),
+                    pattern: pat.to_string(),
                     source: e,
                 })
-                .with_context(|| {
-                    format!(
-                        "Invalid exclusion pattern #{}: '{}' - check pattern syntax",
-                        index + 1,
-                        pat
-                    )
-                })?;
+                .with_context(|| format!("Invalid preset pattern: '{pat}'"))?;
+        }
+        Ok(())
+    }
+
+    /// Adds CLI-provided exclusion patterns to the builder.
+    ///
+    /// Each value may hold several comma-separated patterns (see `split_pattern_list`).
+    fn add_cli_patterns(
+        builder: &mut GitignoreBuilder,
+        cli_patterns: &[String],
+    ) -> anyhow::Result<()> {
+        let patterns = cli_patterns
+            .iter()
+            .flat_map(|value| split_pattern_list(value));
+
+        // Every pattern is tried, so all mistakes are reported in one run
+        let mut errors = Vec::new();
+        for (index, pat) in patterns.enumerate() {
+            if let Err(e) = builder.add_line(None, &pat) {
+                errors.push((index, pat, e));
+            }
+        }
+
+        if errors.len() == 1 {
+            let (index, pat, e) = errors.remove(0);
+            return Err(PatternError::InvalidPattern {
+                pattern: pat.clone(),
+                source: e,
+            })
+            .with_context(|| {
+                format!(
+                    "Invalid exclusion pattern #{}: '{}' - check pattern syntax",
+                    index + 1,
+                    pat
+                )
+            });
+        }
+        if !errors.is_empty() {
+            let errors = errors
+                .into_iter()
+                .map(|(_, pattern, source)| PatternError::InvalidPattern { pattern, source })
+                .collect();
+            return Err(PatternError::InvalidPatterns(errors).into());
         }
         Ok(())
     }
 }
 
+/// Adds every pattern of one ignore file.
+///
+/// `GitignoreBuilder::add` reports a missing file and a bad line the same way, and
+/// keeps the valid lines; opening the file first tells the two apart.
+fn add_ignore_file(builder: &mut GitignoreBuilder, path: &Path) -> Result<(), PatternError> {
+    File::open(path).map_err(|e| PatternError::IgnoreFileReadFailed {
+        path: path.to_path_buf(),
+        source: e,
+    })?;
+
+    match builder.add(path) {
+        Some(e) => Err(PatternError::InvalidPattern {
+            pattern: path.display().to_string(),
+            source: e,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Compiles standalone patterns into a matcher.
+fn build_matcher(root: &Path, patterns: impl Iterator<Item = String>) -> anyhow::Result<Gitignore> {
+    let mut builder = GitignoreBuilder::new(root);
+    for pat in patterns.filter(|pat| !pat.is_empty()) {
+        builder
+            .add_line(None, &pat)
+            .map_err(|e| PatternError::InvalidPattern {
+                pattern: pat.clone(),
+                source: e,
+            })
+            .with_context(|| format!("Invalid exclusion pattern: '{pat}'"))?;
+    }
+
+    builder
+        .build()
+        .map_err(|e| PatternError::BuildFailed { source: e })
+        .with_context(|| "Failed to build exclusion matcher")
+}
+
+/// Turns a directory name into a directory-only pattern, e.g. `build` -> `build/`.
+///
+/// Names without a slash match at any depth; `src/gen` is anchored to the root,
+/// as in gitignore.
+fn dir_pattern(name: &str) -> String {
+    let name = name.trim().trim_end_matches('/');
+    if name.is_empty() {
+        return String::new();
+    }
+    format!("{name}/")
+}
+
+/// Normalizes a file glob; a trailing slash would make it match directories only.
+fn file_pattern(glob: &str) -> String {
+    glob.trim().trim_end_matches('/').to_string()
+}
+
+/// Splits a comma-separated list of patterns, e.g. `target,node_modules,*.lock`.
+///
+/// Commas inside `{...}` alternations are kept, and `\,` yields a literal comma.
+/// Surrounding whitespace is trimmed and empty entries are dropped.
+fn split_pattern_list(value: &str) -> Vec<String> {
+    let mut patterns = Vec::new();
+    let mut current = String::new();
+    let mut brace_depth = 0usize;
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.as_str().starts_with(',') => {
+                current.push(',');
+                chars.next();
+            }
+            '{' => {
+                brace_depth += 1;
+                current.push(c);
+            }
+            '}' => {
+                brace_depth = brace_depth.saturating_sub(1);
+                current.push(c);
+            }
+            ',' if brace_depth == 0 => patterns.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    patterns.push(current);
+
+    patterns
+        .into_iter()
+        .map(|pattern| pattern.trim().to_string())
+        .filter(|pattern| !pattern.is_empty())
+        .collect()
+}
+
 #[cfg(test)]
 mod exclude_tests {
     use super::*;
@@ -150,6 +480,28 @@ mod exclude_tests {
         Ok(())
     }
 
+    #[test]
+    fn test_ripgrep_ignore_files_and_precedence() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+
+        for name in ["vendor", "dist", "logs"] {
+            fs::create_dir(root.join(name))?;
+        }
+        fs::write(root.join(".ignore"), "vendor\ndist\nlogs")?;
+        fs::write(root.join(".rgignore"), "!dist")?;
+        fs::write(root.join(".treeclipignore"), "!logs")?;
+
+        let matcher = ExcludeMatcher::new(root, &[])?;
+
+        assert!(matcher.is_excluded(&root.join("vendor")));
+        // Higher-precedence files re-include what .ignore excluded
+        assert!(!matcher.is_excluded(&root.join("dist")));
+        assert!(!matcher.is_excluded(&root.join("logs")));
+
+        Ok(())
+    }
+
     #[test]
     fn test_is_excluded_with_cli_patterns() -> anyhow::Result<()> {
         let temp_dir = TempDir::new()?;
@@ -172,6 +524,44 @@ mod exclude_tests {
         Ok(())
     }
 
+    #[test]
+    fn test_typed_patterns_distinguish_files_and_dirs() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+
+        fs::create_dir_all(root.join("build"))?;
+        fs::create_dir_all(root.join("src").join("build"))?;
+        fs::write(root.join("src").join("build.rs"), "fn main() {}")?;
+        fs::write(root.join("docs"), "a file named like a directory")?;
+        fs::create_dir_all(root.join("notes").join("docs"))?;
+
+        let matcher = ExcludeMatcher::new(root, &[])?.with_typed_patterns(
+            root,
+            &["build".to_string()],
+            &["docs".to_string(), "*.rs/".to_string()],
+        )?;
+
+        // --exclude-dir build: both build directories, at any depth
+        assert!(matcher.is_excluded(&root.join("build")));
+        assert!(matcher.is_excluded(&root.join("src").join("build")));
+
+        // --exclude-file docs: the file, not the directory of the same name
+        assert!(matcher.is_excluded(&root.join("docs")));
+        assert!(!matcher.is_excluded(&root.join("notes").join("docs")));
+
+        // A trailing slash on a file glob is ignored
+        assert!(matcher.is_excluded(&root.join("src").join("build.rs")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_dir_pattern_construction() {
+        assert_eq!(dir_pattern("target"), "target/");
+        assert_eq!(dir_pattern("src/gen/"), "src/gen/");
+        assert_eq!(dir_pattern(" / "), "");
+    }
+
     #[test]
     fn test_is_excluded_with_multiple_patterns() -> anyhow::Result<()> {
         let temp_dir = TempDir::new()?;
@@ -203,6 +593,21 @@ mod exclude_tests {
         Ok(())
     }
 
+    #[test]
+    fn test_invalid_patterns_all_reported() {
+        let temp_dir = TempDir::new().unwrap();
+        let patterns = ["[z-a]".to_string(), "*.log".to_string(), "a{b".to_string()];
+
+        let error = ExcludeMatcher::new(temp_dir.path(), &patterns)
+            .err()
+            .expect("a reversed range and an unclosed group are invalid");
+        let error = format!("{error:#}");
+        assert!(error.contains("2 invalid exclusion patterns:"), "{error}");
+        assert!(error.contains("'[z-a]': "), "{error}");
+        assert!(error.contains("'a{b': "), "{error}");
+        assert!(!error.contains("*.log"), "{error}");
+    }
+
     #[test]
     fn test_invalid_pattern_error() {
         let temp_dir = TempDir::new().unwrap();
@@ -253,6 +658,134 @@ mod exclude_tests {
         Ok(())
     }
 
+    #[test]
+    fn test_split_pattern_list() {
+        assert_eq!(
+            split_pattern_list("target, node_modules,*.lock"),
+            ["target", "node_modules", "*.lock"]
+        );
+        assert_eq!(split_pattern_list("*.{js,ts},dist"), ["*.{js,ts}", "dist"]);
+        assert_eq!(split_pattern_list("odd\\,name.txt"), ["odd,name.txt"]);
+        assert_eq!(split_pattern_list("single"), ["single"]);
+        assert!(split_pattern_list(" , ").is_empty());
+    }
+
+    #[test]
+    fn test_comma_separated_cli_patterns() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+
+        let patterns = vec!["*.log,target".to_string(), "node_modules".to_string()];
+        let matcher = ExcludeMatcher::new(root, &patterns)?;
+
+        assert!(matcher.is_excluded(&root.join("debug.log")));
+        assert!(matcher.is_excluded(&root.join("target")));
+        assert!(matcher.is_excluded(&root.join("node_modules")));
+        assert!(!matcher.is_excluded(&root.join("main.rs")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_test_file_presets() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+
+        let tests_dir = root.join("tests");
+        fs::create_dir(&tests_dir)?;
+        let jest_dir = root.join("__tests__");
+        fs::create_dir(&jest_dir)?;
+
+        let matcher = ExcludeMatcher::with_presets(root, presets::TEST_FILES, &[], false, &[])?;
+
+        assert!(matcher.is_excluded(&tests_dir));
+        assert!(matcher.is_excluded(&jest_dir));
+        assert!(matcher.is_excluded(&root.join("walker_test.rs")));
+        assert!(matcher.is_excluded(&root.join("test_models.py")));
+        assert!(matcher.is_excluded(&root.join("button.test.js")));
+        assert!(matcher.is_excluded(&root.join("api.spec.ts")));
+
+        assert!(!matcher.is_excluded(&root.join("main.rs")));
+        assert!(!matcher.is_excluded(&root.join("models.py")));
+        assert!(!matcher.is_excluded(&root.join("testing.js")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_exclude_from_files() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+        let list = root.join("ci.ignore");
+        fs::write(&list, "*.snap\n")?;
+        let missing = root.join("missing.ignore");
+
+        let matcher =
+            ExcludeMatcher::with_presets(root, &[], std::slice::from_ref(&list), false, &[])?;
+        assert!(matcher.is_excluded(&root.join("a.snap")));
+
+        // Asked for explicitly, so a missing file is an error...
+        let result =
+            ExcludeMatcher::with_presets(root, &[], &[missing.clone(), list.clone()], false, &[]);
+        assert!(result.is_err());
+
+        // ...unless it may be missing
+        let matcher = ExcludeMatcher::with_presets(root, &[], &[missing, list], true, &[])?;
+        assert!(matcher.is_excluded(&root.join("a.snap")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parent_gitignores_apply_from_nested_dir() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let repo = temp_dir.path().join("repo");
+        let nested = repo.join("crates").join("app");
+        fs::create_dir_all(&nested)?;
+        fs::create_dir(repo.join(".git"))?;
+        // Above the git root, so never read
+        fs::write(temp_dir.path().join(".gitignore"), "*.rs\n")?;
+        fs::write(repo.join(".gitignore"), "*.log\n/crates/app/gen/\n")?;
+        fs::write(nested.join(".gitignore"), "!keep.log\n")?;
+        fs::create_dir(nested.join("gen"))?;
+
+        let matcher = ExcludeMatcher::new(&nested, &[])?.with_parent_gitignores(&nested);
+        assert!(matcher.is_excluded(&nested.join("debug.log")));
+        assert!(matcher.is_excluded(&nested.join("gen")));
+        assert!(!matcher.is_excluded(&nested.join("keep.log")));
+        assert!(!matcher.is_excluded(&nested.join("main.rs")));
+
+        // Without the option no .gitignore is read
+        let matcher = ExcludeMatcher::new(&nested, &[])?;
+        assert!(!matcher.is_excluded(&nested.join("debug.log")));
+
+        // Other sources can re-include a gitignored path
+        let matcher = ExcludeMatcher::new(&nested, &["!debug.log".to_string()])?
+            .with_parent_gitignores(&nested);
+        assert!(!matcher.is_excluded(&nested.join("debug.log")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_presets_can_be_overridden() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+
+        let matcher = ExcludeMatcher::with_presets(
+            root,
+            presets::TEST_FILES,
+            &[],
+            false,
+            &["!integration_test.rs".to_string()],
+        )?;
+
+        assert!(matcher.is_excluded(&root.join("unit_test.rs")));
+        assert!(!matcher.is_excluded(&root.join("integration_test.rs")));
+
+        Ok(())
+    }
+
     #[test]
     fn test_wildcard_cli_patterns() -> anyhow::Result<()> {
         let temp_dir = TempDir::new()?;
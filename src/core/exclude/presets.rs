@@ -0,0 +1,58 @@
+//! presets - Built-in exclusion pattern sets enabled by convenience flags.
+//!
+//! Presets are added to the matcher *before* the ignore files and CLI patterns,
+//! so any of them can be re-included with a gitignore negation, e.g.
+//! `-e '!tests/'`.
+
+/// Patterns matching common test-file conventions (`--exclude-test-files`).
+pub const TEST_FILES: &[&str] = &[
+    // Rust / Go
+    "*_test.rs",
+    "*_test.go",
+    // Python
+    "test_*.py",
+    "*_test.py",
+    // JavaScript / TypeScript
+    "*.test.js",
+    "*.test.jsx",
+    "*.test.ts",
+    "*.test.tsx",
+    "*.spec.js",
+    "*.spec.jsx",
+    "*.spec.ts",
+    "*.spec.tsx",
+    // Test directories
+    "tests/",
+    "__tests__/",
+];
+
+/// Well-known dependency lockfiles (`--exclude-lockfiles`).
+pub const LOCKFILES: &[&str] = &[
+    "Cargo.lock",
+    "package-lock.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "poetry.lock",
+    "Pipfile.lock",
+    "Gemfile.lock",
+    "composer.lock",
+    "go.sum",
+];
+
+#[cfg(test)]
+mod presets_tests {
+    use super::*;
+
+    #[test]
+    fn test_test_file_presets_not_empty() {
+        assert!(!TEST_FILES.is_empty());
+        assert!(TEST_FILES.contains(&"tests/"));
+        assert!(TEST_FILES.contains(&"*_test.rs"));
+    }
+
+    #[test]
+    fn test_lockfile_presets_not_empty() {
+        assert!(LOCKFILES.contains(&"Cargo.lock"));
+        assert!(LOCKFILES.contains(&"package-lock.json"));
+    }
+}
@@ -0,0 +1,282 @@
+//! git - Provenance lines for `--git-context`, per-file diffs for `--as-diff` and
+//! repository discovery for `--relative-to-git-root`.
+//!
+//! Shells out to the `git` binary rather than linking a git library; each lookup is
+//! one or two git processes per file, which is why the options are opt-in.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Prefix of the provenance line prepended to each file's content.
+pub const CONTEXT_PREFIX: &str = "git: ";
+
+/// First line of every diff git prints, used to fence `--as-diff` blocks as diffs.
+pub const DIFF_PREFIX: &str = "diff --git ";
+
+/// Returns the short hash and author of the last commit that touched `path`.
+///
+/// # Returns
+///
+/// Returns `None` when the file is not in a git repository, is untracked, or git
+/// cannot be run, so callers simply leave the line out.
+pub fn last_commit(path: &Path) -> Option<String> {
+    let (dir, name) = split_path(path)?;
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["log", "-1", "--format=%h %an", "--"])
+        .arg(name)
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let commit = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!commit.is_empty()).then_some(commit)
+}
+
+/// Prepends the `--git-context` line for `path` to `content`, if it has a commit.
+pub fn with_context_line(path: &Path, content: &str) -> Option<String> {
+    last_commit(path).map(|commit| format!("{CONTEXT_PREFIX}{commit}\n{content}"))
+}
+
+/// Returns whether `rev` names a commit in the repository containing `dir`.
+pub fn is_commit(dir: &Path, rev: &str) -> bool {
+    Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["rev-parse", "--verify", "--quiet"])
+        .arg(format!("{rev}^{{commit}}"))
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Returns the unified diff of `path` against `rev` (`--as-diff`).
+///
+/// A file git does not track yet is shown as added in full, against `/dev/null`.
+///
+/// # Returns
+///
+/// Returns `None` when the file is unchanged since `rev`, is ignored by git, is
+/// outside a repository, or git cannot be run.
+pub fn diff(path: &Path, rev: &str) -> Option<String> {
+    let (dir, name) = split_path(path)?;
+    let tracked = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["ls-files", "--error-unmatch", "--"])
+        .arg(name)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .ok()?
+        .success();
+
+    let output = if tracked {
+        Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["diff", "--no-color", "--no-ext-diff", rev, "--"])
+            .arg(name)
+            .stderr(Stdio::null())
+            .output()
+            .ok()?
+    } else if is_untracked(dir, name) {
+        // Exits with 1 whenever the files differ, so only the output counts
+        Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args([
+                "diff",
+                "--no-index",
+                "--no-color",
+                "--no-ext-diff",
+                "--",
+                "/dev/null",
+            ])
+            .arg(name)
+            .stderr(Stdio::null())
+            .output()
+            .ok()?
+    } else {
+        return None;
+    };
+
+    let diff = String::from_utf8_lossy(&output.stdout).into_owned();
+    diff.starts_with(DIFF_PREFIX).then_some(diff)
+}
+
+/// Returns the root of the git repository containing `start`, found by walking up
+/// to the nearest directory with a `.git` entry.
+///
+/// `.git` may be a file, as in worktrees and submodules. Returns `None` outside a
+/// repository; no git process is run.
+pub fn find_root(start: &Path) -> Option<PathBuf> {
+    let start = std::path::absolute(start).ok()?;
+    start
+        .ancestors()
+        .find(|dir| dir.join(".git").exists())
+        .map(Path::to_path_buf)
+}
+
+// -------------------------------------------- Private Helper Functions --------------------------------------------
+
+/// Splits `path` into the directory to run git in and the file name.
+fn split_path(path: &Path) -> Option<(&Path, &std::ffi::OsStr)> {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    Some((dir, path.file_name()?))
+}
+
+/// Returns whether `name` in `dir` is untracked and not ignored, i.e. a new file.
+fn is_untracked(dir: &Path, name: &std::ffi::OsStr) -> bool {
+    Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["ls-files", "--others", "--exclude-standard", "--"])
+        .arg(name)
+        .stderr(Stdio::null())
+        .output()
+        .is_ok_and(|output| output.status.success() && !output.stdout.is_empty())
+}
+
+#[cfg(test)]
+mod git_tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// Runs git in `dir`, panicking on failure.
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(args)
+            .stdout(Stdio::null())
+            .status()
+            .expect("git runs");
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    #[test]
+    fn test_last_commit_of_tracked_file() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+        git(root, &["init", "-q"]);
+        fs::write(root.join("tracked.txt"), "hello")?;
+        fs::write(root.join("untracked.txt"), "new")?;
+        git(root, &["add", "tracked.txt"]);
+        git(
+            root,
+            &[
+                "-c",
+                "user.name=Jane Doe",
+                "-c",
+                "user.email=jane@example.com",
+                "commit",
+                "-q",
+                "-m",
+                "init",
+            ],
+        );
+
+        let commit = last_commit(&root.join("tracked.txt")).expect("tracked file has a commit");
+        assert!(commit.ends_with(" Jane Doe"));
+
+        let content = with_context_line(&root.join("tracked.txt"), "hello").unwrap();
+        assert_eq!(content, format!("git: {commit}\nhello"));
+
+        assert_eq!(last_commit(&root.join("untracked.txt")), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_diff_against_commit() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+        git(root, &["init", "-q"]);
+        fs::write(root.join("changed.txt"), "one\n")?;
+        fs::write(root.join("same.txt"), "same\n")?;
+        git(root, &["add", "."]);
+        git(
+            root,
+            &[
+                "-c",
+                "user.name=Jane Doe",
+                "-c",
+                "user.email=jane@example.com",
+                "commit",
+                "-q",
+                "-m",
+                "init",
+            ],
+        );
+        fs::write(root.join("changed.txt"), "two\n")?;
+        fs::write(root.join("new.txt"), "fresh\n")?;
+
+        assert!(is_commit(root, "HEAD"));
+        assert!(!is_commit(root, "no-such-branch"));
+
+        let changed = diff(&root.join("changed.txt"), "HEAD").expect("changed file has a diff");
+        assert!(changed.starts_with("diff --git a/changed.txt b/changed.txt"));
+        assert!(changed.contains("-one\n+two\n"));
+
+        let added = diff(&root.join("new.txt"), "HEAD").expect("new file has a diff");
+        assert!(added.contains("new file mode"));
+        assert!(added.contains("+fresh\n"));
+
+        assert_eq!(diff(&root.join("same.txt"), "HEAD"), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_root_walks_up_to_dot_git() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let repo = temp_dir.path().join("repo");
+        let nested = repo.join("src").join("core");
+        fs::create_dir_all(&nested)?;
+        fs::create_dir(repo.join(".git"))?;
+
+        assert_eq!(find_root(&nested), Some(repo.clone()));
+        assert_eq!(find_root(&repo), Some(repo.clone()));
+
+        // A worktree's `.git` is a file pointing at the real one
+        let worktree = temp_dir.path().join("worktree");
+        fs::create_dir(&worktree)?;
+        fs::write(worktree.join(".git"), "gitdir: ../repo/.git/worktrees/wt")?;
+        assert_eq!(find_root(&worktree), Some(worktree));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_last_commit_outside_repository() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let file = temp_dir.path().join("a.txt");
+        fs::write(&file, "a")?;
+
+        // A temp dir could sit inside a repository; only check it does not panic there
+        if Command::new("git")
+            .arg("-C")
+            .arg(temp_dir.path())
+            .args(["rev-parse", "--git-dir"])
+            .stderr(Stdio::null())
+            .stdout(Stdio::null())
+            .status()
+            .is_ok_and(|status| !status.success())
+        {
+            assert_eq!(last_commit(&file), None);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,205 @@
+//! lang - Lightweight language detection based on file names and extensions.
+//!
+//! Detection is intentionally shallow: it only looks at the file name, never the
+//! content, so it stays cheap enough to run on every file in a large tree.
+
+use std::path::Path;
+
+/// Comment delimiters wrapped around a single line in some language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommentStyle {
+    /// Text placed before the commented line, including trailing space.
+    pub prefix: &'static str,
+    /// Text placed after the commented line, including leading space (may be empty).
+    pub suffix: &'static str,
+}
+
+const SLASHES: CommentStyle = CommentStyle {
+    prefix: "// ",
+    suffix: "",
+};
+const HASH: CommentStyle = CommentStyle {
+    prefix: "# ",
+    suffix: "",
+};
+const DASHES: CommentStyle = CommentStyle {
+    prefix: "-- ",
+    suffix: "",
+};
+const SEMICOLONS: CommentStyle = CommentStyle {
+    prefix: ";; ",
+    suffix: "",
+};
+const PERCENT: CommentStyle = CommentStyle {
+    prefix: "% ",
+    suffix: "",
+};
+const BLOCK: CommentStyle = CommentStyle {
+    prefix: "/* ",
+    suffix: " */",
+};
+const MARKUP: CommentStyle = CommentStyle {
+    prefix: "<!-- ",
+    suffix: " -->",
+};
+
+/// Returns the single-line comment syntax for a file, if its language is known.
+///
+/// Formats without comments (e.g. JSON) and unknown extensions return `None`.
+pub fn comment_style(path: &Path) -> Option<CommentStyle> {
+    let file_name = path.file_name()?.to_str()?;
+
+    // Files identified by name rather than extension
+    match file_name {
+        "Dockerfile" | "Makefile" | "Gemfile" | "Rakefile" | "CMakeLists.txt" => {
+            return Some(HASH);
+        }
+        _ => {}
+    }
+
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    let style = match extension.as_str() {
+        "rs" | "c" | "h" | "cc" | "cpp" | "hpp" | "cs" | "go" | "java" | "kt" | "kts" | "scala"
+        | "swift" | "dart" | "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" | "php" | "proto"
+        | "zig" | "groovy" | "gradle" | "v" | "sol" => SLASHES,
+        "py" | "rb" | "sh" | "bash" | "zsh" | "fish" | "pl" | "r" | "yaml" | "yml" | "toml"
+        | "ini" | "conf" | "cfg" | "ex" | "exs" | "nim" | "ps1" | "cmake" | "tf" | "mk" => HASH,
+        "sql" | "lua" | "hs" | "elm" | "ada" => DASHES,
+        "clj" | "cljs" | "el" | "lisp" | "scm" => SEMICOLONS,
+        "tex" | "erl" | "m" => PERCENT,
+        "css" | "scss" | "less" => BLOCK,
+        "html" | "htm" | "xml" | "svg" | "vue" | "svelte" | "md" | "markdown" => MARKUP,
+        _ => return None,
+    };
+
+    Some(style)
+}
+
+/// Maps a file extension to the language name used on a markdown code fence.
+pub fn lang_from_extension(extension: &str) -> Option<&'static str> {
+    let language = match extension.to_ascii_lowercase().as_str() {
+        "rs" => "rust",
+        "py" | "pyi" => "python",
+        "js" | "mjs" | "cjs" => "javascript",
+        "jsx" => "jsx",
+        "ts" | "mts" | "cts" => "typescript",
+        "tsx" => "tsx",
+        "go" => "go",
+        "java" => "java",
+        "kt" | "kts" => "kotlin",
+        "scala" => "scala",
+        "swift" => "swift",
+        "c" | "h" => "c",
+        "cc" | "cpp" | "cxx" | "hh" | "hpp" | "hxx" => "cpp",
+        "cs" => "csharp",
+        "rb" => "ruby",
+        "php" => "php",
+        "lua" => "lua",
+        "dart" => "dart",
+        "ex" | "exs" => "elixir",
+        "erl" => "erlang",
+        "hs" => "haskell",
+        "ml" | "mli" => "ocaml",
+        "zig" => "zig",
+        "sh" | "bash" => "bash",
+        "zsh" => "zsh",
+        "fish" => "fish",
+        "ps1" => "powershell",
+        "sql" => "sql",
+        "html" | "htm" => "html",
+        "css" => "css",
+        "scss" => "scss",
+        "vue" => "vue",
+        "svelte" => "svelte",
+        "json" => "json",
+        "toml" => "toml",
+        "yaml" | "yml" => "yaml",
+        "xml" => "xml",
+        "md" | "markdown" => "markdown",
+        "proto" => "protobuf",
+        "graphql" | "gql" => "graphql",
+        "dockerfile" => "dockerfile",
+        "mk" => "makefile",
+        "nix" => "nix",
+        "tf" => "hcl",
+        "txt" => "text",
+        _ => return None,
+    };
+    Some(language)
+}
+
+/// Name files without a known language are grouped under in per-language stats.
+pub const OTHER_LANGUAGE: &str = "other";
+
+/// Language a file is grouped under in per-language stats (`--sloc`,
+/// `--language-chart`): its fence language, else its lowercased extension.
+pub fn language_name(path: &Path) -> String {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some(extension) => lang_from_extension(extension)
+            .map_or_else(|| extension.to_ascii_lowercase(), str::to_string),
+        None => OTHER_LANGUAGE.to_string(),
+    }
+}
+
+/// Returns `true` for languages where leading indentation carries meaning, so
+/// `--collapse-whitespace-runs` keeps it: Python, YAML, Haskell and Makefiles.
+pub fn is_indentation_sensitive(path: &Path) -> bool {
+    let by_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| matches!(name, "Makefile" | "GNUmakefile"));
+    by_name
+        || matches!(
+            language_name(path).as_str(),
+            "python" | "yaml" | "haskell" | "makefile"
+        )
+}
+
+#[cfg(test)]
+mod lang_tests {
+    use super::*;
+
+    #[test]
+    fn test_comment_style_by_extension() {
+        assert_eq!(comment_style(Path::new("src/main.rs")), Some(SLASHES));
+        assert_eq!(comment_style(Path::new("app.py")), Some(HASH));
+        assert_eq!(comment_style(Path::new("query.SQL")), Some(DASHES));
+        assert_eq!(comment_style(Path::new("styles.css")), Some(BLOCK));
+        assert_eq!(comment_style(Path::new("index.html")), Some(MARKUP));
+    }
+
+    #[test]
+    fn test_comment_style_by_file_name() {
+        assert_eq!(comment_style(Path::new("docker/Dockerfile")), Some(HASH));
+        assert_eq!(comment_style(Path::new("Makefile")), Some(HASH));
+    }
+
+    #[test]
+    fn test_lang_from_extension() {
+        assert_eq!(lang_from_extension("rs"), Some("rust"));
+        assert_eq!(lang_from_extension("YML"), Some("yaml"));
+        assert_eq!(lang_from_extension("foo"), None);
+    }
+
+    #[test]
+    fn test_language_name() {
+        assert_eq!(language_name(Path::new("b.RS")), "rust");
+        assert_eq!(language_name(Path::new("notes.FOO")), "foo");
+        assert_eq!(language_name(Path::new("LICENSE")), OTHER_LANGUAGE);
+    }
+
+    #[test]
+    fn test_comment_style_unknown() {
+        assert_eq!(comment_style(Path::new("data.json")), None);
+        assert_eq!(comment_style(Path::new("LICENSE")), None);
+    }
+
+    #[test]
+    fn test_is_indentation_sensitive() {
+        assert!(is_indentation_sensitive(Path::new("app/main.py")));
+        assert!(is_indentation_sensitive(Path::new(".github/ci.YML")));
+        assert!(is_indentation_sensitive(Path::new("build/Makefile")));
+        assert!(!is_indentation_sensitive(Path::new("src/main.rs")));
+        assert!(!is_indentation_sensitive(Path::new("LICENSE")));
+    }
+}
@@ -1,7 +1,16 @@
 pub mod clipboard;
+pub mod config;
+pub mod doctor;
 pub mod editor;
+mod editorconfig;
 pub mod errors;
 mod exclude;
+pub mod git;
+pub mod lang;
+pub mod output;
+mod structure;
 pub mod traversal;
 pub mod ui;
+pub mod unpack;
 pub mod utils;
+pub mod watch;
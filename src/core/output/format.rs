@@ -0,0 +1,406 @@
+//! format - Renders files into the document shape selected with `--format`.
+//!
+//! A `Renderer` is fed files one at a time and produces the text to append for each,
+//! so a whole run is rendered in a single pass no matter how many input paths it has.
+
+use crate::core::traversal::{toc, tree};
+use crate::core::{git, lang};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Document shape of the output file (`--format`) or the clipboard (`--clipboard-format`).
+#[derive(clap::ValueEnum, serde::Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum OutputFormat {
+    /// `==> path` headers followed by the file content
+    #[default]
+    Text,
+    /// A `## path` heading and a fenced code block per file
+    Markdown,
+    /// An array of `{"path", "content"}` objects
+    Json,
+    /// A Graphviz graph of the directory structure, without content
+    Dot,
+}
+
+/// Marks a file as treeclip output, so later runs never ingest it.
+pub const OUTPUT_MARKER: &str = "treeclip-output v2";
+
+/// Byte-order mark written before the marker with `--bom`.
+pub const UTF8_BOM: char = '\u{feff}';
+
+/// Start of the line naming the prefix stripped from paths (`--collapse-paths`).
+pub const PATH_PREFIX_TITLE: &str = "Path prefix: ";
+
+/// Path shown in the header of the `--footer-stats` block, also used by unpack to skip it.
+pub const STATS_TITLE: &str = "(stats)";
+
+/// Returns `true` if `line` is the output marker in any format, after any BOM.
+pub fn is_marker_line(line: &str) -> bool {
+    let line = line.trim_start_matches(UTF8_BOM).trim();
+    line == format!("# {OUTPUT_MARKER}")
+        || line == format!("<!-- {OUTPUT_MARKER} -->")
+        || line == format!("// {OUTPUT_MARKER}")
+}
+
+/// Drops the output marker line (and any BOM before it) from the start of `text`.
+pub fn strip_marker(text: &str) -> &str {
+    match text.split_once('\n') {
+        Some((first, rest)) if is_marker_line(first) => rest,
+        _ => text,
+    }
+}
+
+/// Incremental renderer for one output document.
+pub struct Renderer {
+    format: OutputFormat,
+    /// Whether anything (table of contents or file) has been rendered yet.
+    started: bool,
+    /// Files seen so far, for formats rendered as a whole at the end.
+    paths: Vec<PathBuf>,
+    /// Extensions with no known fence language, and how many files had each.
+    unrecognized: BTreeMap<String, usize>,
+    /// Blank lines between file blocks in text and markdown (`--blank-lines-between`).
+    blank_lines: usize,
+}
+
+impl Renderer {
+    /// Creates a renderer for the given format.
+    pub fn new(format: OutputFormat) -> Self {
+        Self {
+            format,
+            started: false,
+            paths: Vec::new(),
+            unrecognized: BTreeMap::new(),
+            blank_lines: 1,
+        }
+    }
+
+    /// Sets how many blank lines separate file blocks; 1 by default.
+    pub fn set_blank_lines(&mut self, blank_lines: usize) {
+        self.blank_lines = blank_lines;
+    }
+
+    /// Renders the output marker line.
+    ///
+    /// JSON has no comments, so JSON output carries no marker.
+    pub fn marker(&self) -> String {
+        match self.format {
+            OutputFormat::Text => format!("# {OUTPUT_MARKER}\n"),
+            OutputFormat::Markdown => format!("<!-- {OUTPUT_MARKER} -->\n"),
+            OutputFormat::Dot => format!("// {OUTPUT_MARKER}\n"),
+            OutputFormat::Json => String::new(),
+        }
+    }
+
+    /// Renders the line naming the prefix stripped from every path (`--collapse-paths`).
+    ///
+    /// JSON and DOT output always carry full paths, so they have no such line.
+    pub fn path_prefix(&mut self, prefix: &Path) -> String {
+        let prefix = format!("{}/", prefix.display());
+        let rendered = match self.format {
+            OutputFormat::Text => format!("{PATH_PREFIX_TITLE}{prefix}\n"),
+            OutputFormat::Markdown => format!("{PATH_PREFIX_TITLE}`{prefix}`\n"),
+            OutputFormat::Json | OutputFormat::Dot => return String::new(),
+        };
+
+        let separator = if self.started { "\n" } else { "" };
+        self.started = true;
+        format!("{separator}{rendered}")
+    }
+
+    /// Renders the table of contents (`--toc`).
+    ///
+    /// JSON and DOT output have no table of contents; they list every file anyway.
+    pub fn toc(&mut self, entries: &[(&Path, u64)]) -> String {
+        let rendered = match self.format {
+            OutputFormat::Text => toc::render(entries),
+            OutputFormat::Markdown => toc::render_markdown(entries),
+            OutputFormat::Json | OutputFormat::Dot => return String::new(),
+        };
+
+        let separator = if self.started { "\n" } else { "" };
+        self.started = true;
+        format!("{separator}{rendered}")
+    }
+
+    /// Renders the directory tree (`--tree`).
+    ///
+    /// Like the table of contents, JSON and DOT output leave it out.
+    pub fn tree(&mut self, tree: &str) -> String {
+        let rendered = match self.format {
+            OutputFormat::Text => tree.to_string(),
+            OutputFormat::Markdown => format!("```text\n{tree}```\n"),
+            OutputFormat::Json | OutputFormat::Dot => return String::new(),
+        };
+
+        let separator = if self.started { "\n" } else { "" };
+        self.started = true;
+        format!("{separator}{rendered}")
+    }
+
+    /// Renders one file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path shown for the file
+    /// * `header` - Text-format header line, including any prefix and suffix
+    /// * `body` - File content, already trimmed
+    pub fn file(&mut self, path: &Path, header: &str, body: &str) -> String {
+        let started = std::mem::replace(&mut self.started, true);
+        let blank_lines = if started { self.blank_lines } else { 0 };
+
+        match self.format {
+            OutputFormat::Text => {
+                let separator = "\n".repeat(blank_lines);
+                format!("{separator}{header}\n{body}\n")
+            }
+            OutputFormat::Markdown => {
+                let separator = "\n".repeat(blank_lines);
+                let fence = "`".repeat(longest_backtick_run(body).max(2) + 1);
+                // `--as-diff` bodies are diffs, whatever the file holds
+                let language = if body.starts_with(git::DIFF_PREFIX) {
+                    "diff"
+                } else {
+                    self.fence_language(path)
+                };
+                format!(
+                    "{separator}## {}\n\n{fence}{language}\n{body}\n{fence}\n",
+                    path.display()
+                )
+            }
+            OutputFormat::Json => {
+                let separator = if started { ",\n" } else { "[\n" };
+                format!(
+                    "{separator}  {{\"path\": {}, \"content\": {}}}",
+                    json_string(&path.display().to_string()),
+                    json_string(body)
+                )
+            }
+            OutputFormat::Dot => {
+                // Structure only: the graph is rendered once every path is known
+                self.paths.push(path.to_path_buf());
+                String::new()
+            }
+        }
+    }
+
+    /// Extensions that fell back to a bare fence so far, with file counts.
+    pub fn unrecognized_extensions(&self) -> &BTreeMap<String, usize> {
+        &self.unrecognized
+    }
+
+    /// Renders whatever closes the document.
+    pub fn finish(&self) -> String {
+        match (self.format, self.started) {
+            (OutputFormat::Json, true) => "\n]\n".to_string(),
+            (OutputFormat::Json, false) => "[]\n".to_string(),
+            (OutputFormat::Dot, _) => {
+                let paths: Vec<&Path> = self.paths.iter().map(PathBuf::as_path).collect();
+                tree::render_dot(&paths)
+            }
+            _ => String::new(),
+        }
+    }
+}
+
+/// Returns the anchor GitHub generates for a markdown heading showing `path`.
+pub fn heading_anchor(path: &Path) -> String {
+    path.display()
+        .to_string()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+// -------------------------------------------- Private Helper Functions --------------------------------------------
+
+impl Renderer {
+    /// Fence language for `path`, recording extensions that have none.
+    fn fence_language(&mut self, path: &Path) -> &'static str {
+        let Some(extension) = path.extension().and_then(|ext| ext.to_str()) else {
+            return "";
+        };
+
+        lang::lang_from_extension(extension).unwrap_or_else(|| {
+            *self
+                .unrecognized
+                .entry(extension.to_ascii_lowercase())
+                .or_default() += 1;
+            ""
+        })
+    }
+}
+
+/// Length of the longest run of backticks in `text`, so fences can be made longer.
+fn longest_backtick_run(text: &str) -> usize {
+    text.split(|c| c != '`').map(str::len).max().unwrap_or(0)
+}
+
+/// Encodes `text` as a JSON string literal.
+pub fn json_string(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len() + 2);
+    encoded.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => encoded.push_str("\\\""),
+            '\\' => encoded.push_str("\\\\"),
+            '\n' => encoded.push_str("\\n"),
+            '\r' => encoded.push_str("\\r"),
+            '\t' => encoded.push_str("\\t"),
+            c if u32::from(c) < 0x20 => encoded.push_str(&format!("\\u{:04x}", u32::from(c))),
+            c => encoded.push(c),
+        }
+    }
+    encoded.push('"');
+    encoded
+}
+
+#[cfg(test)]
+mod format_tests {
+    use super::*;
+
+    #[test]
+    fn test_text_format() {
+        let mut renderer = Renderer::new(OutputFormat::Text);
+        let mut output = renderer.file(Path::new("a.rs"), "==> a.rs", "fn a() {}");
+        output += &renderer.file(Path::new("b.rs"), "==> b.rs", "fn b() {}");
+        output += &renderer.finish();
+
+        assert_eq!(output, "==> a.rs\nfn a() {}\n\n==> b.rs\nfn b() {}\n");
+    }
+
+    #[test]
+    fn test_blank_lines_between_files() {
+        let render = |blank_lines| {
+            let mut renderer = Renderer::new(OutputFormat::Text);
+            renderer.set_blank_lines(blank_lines);
+            let mut output = renderer.file(Path::new("a.rs"), "==> a.rs", "a");
+            output += &renderer.file(Path::new("b.rs"), "==> b.rs", "b");
+            output
+        };
+
+        assert_eq!(render(0), "==> a.rs\na\n==> b.rs\nb\n");
+        assert_eq!(render(1), "==> a.rs\na\n\n==> b.rs\nb\n");
+        assert_eq!(render(2), "==> a.rs\na\n\n\n==> b.rs\nb\n");
+    }
+
+    #[test]
+    fn test_markdown_format_lengthens_fences() {
+        let mut renderer = Renderer::new(OutputFormat::Markdown);
+        let output = renderer.file(Path::new("README.md"), "==> README.md", "```sh\nls\n```");
+
+        assert_eq!(
+            output,
+            "## README.md\n\n````markdown\n```sh\nls\n```\n````\n"
+        );
+    }
+
+    #[test]
+    fn test_markdown_fence_outgrows_nested_fences() {
+        // A guide that itself shows how to nest fences
+        let body = "````md\n```rs\nfn main() {}\n```\n````\nInline `code` too";
+        let mut renderer = Renderer::new(OutputFormat::Markdown);
+        let output = renderer.file(Path::new("docs/fences.md"), "==> docs/fences.md", body);
+
+        assert_eq!(
+            output,
+            format!("## docs/fences.md\n\n`````markdown\n{body}\n`````\n")
+        );
+    }
+
+    #[test]
+    fn test_markdown_tracks_unrecognized_extensions() {
+        let mut renderer = Renderer::new(OutputFormat::Markdown);
+        let output = renderer.file(Path::new("main.RS"), "==> main.RS", "");
+        assert!(output.contains("```rust\n"));
+
+        let output = renderer.file(Path::new("a.foo"), "==> a.foo", "");
+        assert!(output.contains("\n```\n\n```\n"));
+        renderer.file(Path::new("b.foo"), "==> b.foo", "");
+        renderer.file(Path::new("LICENSE"), "==> LICENSE", "");
+
+        assert_eq!(
+            renderer.unrecognized_extensions(),
+            &BTreeMap::from([("foo".to_string(), 2)])
+        );
+    }
+
+    #[test]
+    fn test_json_format_escapes_content() {
+        let mut renderer = Renderer::new(OutputFormat::Json);
+        let mut output = renderer.file(Path::new("a.txt"), "==> a.txt", "say \"hi\"\n\tbye\\");
+        output += &renderer.file(Path::new("b.txt"), "==> b.txt", "\u{1}");
+        output += &renderer.finish();
+
+        assert_eq!(
+            output,
+            "[\n  {\"path\": \"a.txt\", \"content\": \"say \\\"hi\\\"\\n\\tbye\\\\\"},\n  {\"path\": \"b.txt\", \"content\": \"\\u0001\"}\n]\n"
+        );
+        assert_eq!(Renderer::new(OutputFormat::Json).finish(), "[]\n");
+    }
+
+    #[test]
+    fn test_tree_is_fenced_in_markdown() {
+        let tree = "Directory Tree (1 file)\n.\n└── a.rs\n";
+
+        assert_eq!(Renderer::new(OutputFormat::Text).tree(tree), tree);
+        assert_eq!(
+            Renderer::new(OutputFormat::Markdown).tree(tree),
+            format!("```text\n{tree}```\n")
+        );
+        assert_eq!(Renderer::new(OutputFormat::Json).tree(tree), "");
+    }
+
+    #[test]
+    fn test_marker_round_trips() {
+        for format in [OutputFormat::Text, OutputFormat::Markdown] {
+            let marker = Renderer::new(format).marker();
+            assert!(is_marker_line(&marker));
+        }
+        assert!(is_marker_line(&Renderer::new(OutputFormat::Dot).marker()));
+        assert_eq!(Renderer::new(OutputFormat::Json).marker(), "");
+        assert!(!is_marker_line("# treeclip-output v1"));
+        assert!(is_marker_line(&format!("{UTF8_BOM}# {OUTPUT_MARKER}")));
+    }
+
+    #[test]
+    fn test_strip_marker_drops_only_the_marker_line() {
+        let marked = format!(
+            "{}==> a.txt\nalpha\n",
+            Renderer::new(OutputFormat::Text).marker()
+        );
+        assert_eq!(strip_marker(&marked), "==> a.txt\nalpha\n");
+        assert_eq!(
+            strip_marker("==> a.txt\n# treeclip-output v2\n"),
+            "==> a.txt\n# treeclip-output v2\n"
+        );
+        assert_eq!(
+            strip_marker(&format!("{UTF8_BOM}# {OUTPUT_MARKER}\nx")),
+            "x"
+        );
+    }
+
+    #[test]
+    fn test_dot_format_is_structure_only() {
+        let mut renderer = Renderer::new(OutputFormat::Dot);
+        assert_eq!(renderer.toc(&[(Path::new("a.rs"), 1)]), "");
+        assert_eq!(renderer.file(Path::new("a.rs"), "==> a.rs", "secret"), "");
+
+        let graph = renderer.finish();
+        assert!(graph.starts_with("digraph treeclip {"));
+        assert!(graph.contains("\".\" -> \"a.rs\";"));
+        assert!(!graph.contains("secret"));
+    }
+
+    #[test]
+    fn test_heading_anchor() {
+        assert_eq!(heading_anchor(Path::new("src/main.rs")), "srcmainrs");
+        assert_eq!(heading_anchor(Path::new("My Notes_v2.md")), "my-notes_v2md");
+    }
+}
@@ -0,0 +1,179 @@
+//! html - Renders treeclip's markdown output as HTML (`--clipboard-html`).
+//!
+//! Rich-text paste targets (Slack, email clients, docs) take the HTML flavor of the
+//! clipboard, so the document pastes with real headings and code blocks. Only the
+//! markdown treeclip itself writes is understood: headings, fenced code blocks of
+//! any fence length, numbered lists, inline code and links. Anything else becomes a
+//! plain paragraph, so the conversion never drops text.
+
+use crate::core::output::format;
+use regex::Regex;
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// Markdown links, `[text](target)`, matched after HTML escaping.
+static LINK: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[([^\]]+)\]\(([^)\s]+)\)").expect("valid link regex"));
+
+/// Converts a markdown document to an HTML fragment.
+pub fn from_markdown(markdown: &str) -> String {
+    let mut html = String::with_capacity(markdown.len() + markdown.len() / 4);
+    // Backtick count of the open fence, if inside a code block
+    let mut fence: Option<usize> = None;
+    let mut in_list = false;
+
+    for line in markdown.lines() {
+        if let Some(open) = fence {
+            if is_closing_fence(line, open) {
+                html.push_str("</code></pre>\n");
+                fence = None;
+            } else {
+                html.push_str(&escape(line));
+                html.push('\n');
+            }
+            continue;
+        }
+
+        let list_item = ordered_item(line);
+        if in_list && list_item.is_none() {
+            html.push_str("</ol>\n");
+            in_list = false;
+        }
+
+        if let Some((backticks, language)) = opening_fence(line) {
+            fence = Some(backticks);
+            match language {
+                "" => html.push_str("<pre><code>"),
+                language => html.push_str(&format!(
+                    "<pre><code class=\"language-{}\">",
+                    escape(language)
+                )),
+            }
+        } else if let Some(item) = list_item {
+            if !in_list {
+                html.push_str("<ol>\n");
+                in_list = true;
+            }
+            html.push_str(&format!("<li>{}</li>\n", inline(item)));
+        } else if let Some((level, text)) = heading(line) {
+            html.push_str(&format!(
+                "<h{level} id=\"{}\">{}</h{level}>\n",
+                escape(&format::heading_anchor(Path::new(text))),
+                inline(text)
+            ));
+        } else if !line.trim().is_empty() && !is_comment(line) {
+            html.push_str(&format!("<p>{}</p>\n", inline(line)));
+        }
+    }
+
+    // An unterminated block still closes, so the fragment stays well formed
+    if fence.is_some() {
+        html.push_str("</code></pre>\n");
+    }
+    if in_list {
+        html.push_str("</ol>\n");
+    }
+    html
+}
+
+// -------------------------------------------- Private Helper Functions --------------------------------------------
+
+/// Escapes the characters HTML gives a meaning to.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Renders inline code spans and links; everything else is escaped text.
+fn inline(text: &str) -> String {
+    let mut html = String::new();
+    // Odd pieces are inside backticks; an unpaired backtick is kept as text
+    let pieces: Vec<&str> = text.split('`').collect();
+    let paired = pieces.len() % 2 == 1;
+    for (index, piece) in pieces.iter().enumerate() {
+        if index % 2 == 1 && (paired || index < pieces.len() - 1) {
+            html.push_str(&format!("<code>{}</code>", escape(piece)));
+        } else {
+            if index % 2 == 1 {
+                html.push('`');
+            }
+            let escaped = escape(piece);
+            html.push_str(&LINK.replace_all(&escaped, "<a href=\"$2\">$1</a>"));
+        }
+    }
+    html
+}
+
+/// Backtick count and language of a line opening a fenced code block.
+fn opening_fence(line: &str) -> Option<(usize, &str)> {
+    let backticks = line.len() - line.trim_start_matches('`').len();
+    (backticks >= 3).then(|| (backticks, line[backticks..].trim()))
+}
+
+/// Returns `true` if `line` closes a block opened with `open` backticks.
+fn is_closing_fence(line: &str, open: usize) -> bool {
+    let line = line.trim_end();
+    line.len() >= open && line.chars().all(|c| c == '`')
+}
+
+/// Level and text of an ATX heading (`## text`).
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.len() - line.trim_start_matches('#').len();
+    let text = line[level..].strip_prefix(' ')?;
+    (1..=6).contains(&level).then_some((level, text.trim()))
+}
+
+/// Text of a numbered list item (`1. text`).
+fn ordered_item(line: &str) -> Option<&str> {
+    let (number, text) = line.split_once(". ")?;
+    (!number.is_empty() && number.chars().all(|c| c.is_ascii_digit())).then_some(text)
+}
+
+/// Returns `true` for a whole-line HTML comment, such as the output marker.
+fn is_comment(line: &str) -> bool {
+    let line = line.trim();
+    line.starts_with("<!--") && line.ends_with("-->")
+}
+
+#[cfg(test)]
+mod html_tests {
+    use super::*;
+
+    #[test]
+    fn test_from_markdown_renders_treeclip_output() {
+        let markdown = "<!-- treeclip-output v2 -->\n## Table of Contents (1 file)\n\n1. [src/main.rs](#srcmainrs) (12 B)\n\n## src/main.rs\n\n```rust\nfn main() { a < b && c; }\n```\n";
+
+        assert_eq!(
+            from_markdown(markdown),
+            "<h2 id=\"table-of-contents-1-file\">Table of Contents (1 file)</h2>\n\
+             <ol>\n<li><a href=\"#srcmainrs\">src/main.rs</a> (12 B)</li>\n</ol>\n\
+             <h2 id=\"srcmainrs\">src/main.rs</h2>\n\
+             <pre><code class=\"language-rust\">fn main() { a &lt; b &amp;&amp; c; }\n</code></pre>\n"
+        );
+    }
+
+    #[test]
+    fn test_longer_fences_keep_inner_fences_as_code() {
+        let markdown = "````markdown\n```sh\nls\n```\n````\nPath prefix: `src/`\n";
+
+        assert_eq!(
+            from_markdown(markdown),
+            "<pre><code class=\"language-markdown\">```sh\nls\n```\n</code></pre>\n\
+             <p>Path prefix: <code>src/</code></p>\n"
+        );
+    }
+
+    #[test]
+    fn test_inline_keeps_unpaired_backticks() {
+        assert_eq!(inline("a `b` c ` d"), "a <code>b</code> c ` d");
+    }
+}
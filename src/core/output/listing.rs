@@ -0,0 +1,156 @@
+//! listing - Renders the files a run would include (`--dry-run`, `--list-format`).
+//!
+//! The listing is meant for scripts as much as for people: plain paths one per line
+//! (or NUL-separated), a JSON array of `{"path", "bytes"}` objects, or CSV rows with a
+//! `path,bytes` header. `--du` sums the same entries per directory instead.
+
+use crate::core::output::format;
+use crate::core::utils;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Structure of the `--dry-run` listing (`--list-format`).
+#[derive(clap::ValueEnum, serde::Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ListFormat {
+    /// One path per line
+    #[default]
+    Plain,
+    /// An array of `{"path", "bytes"}` objects
+    Json,
+    /// `path,bytes` rows after a header row
+    Csv,
+}
+
+/// Renders the listing of `entries`, each a shown path and its size in bytes.
+///
+/// `null_separated` ends each plain entry with a NUL instead of a newline, for
+/// `xargs -0`; the other formats ignore it.
+pub fn render(entries: &[(&Path, u64)], list_format: ListFormat, null_separated: bool) -> String {
+    match list_format {
+        ListFormat::Plain => {
+            let separator = if null_separated { '\0' } else { '\n' };
+            entries
+                .iter()
+                .map(|(path, _)| format!("{}{separator}", path.display()))
+                .collect()
+        }
+        ListFormat::Json => {
+            let items: Vec<String> = entries
+                .iter()
+                .map(|(path, bytes)| {
+                    format!(
+                        "  {{\"path\": {}, \"bytes\": {bytes}}}",
+                        format::json_string(&path.to_string_lossy())
+                    )
+                })
+                .collect();
+            if items.is_empty() {
+                "[]\n".to_string()
+            } else {
+                format!("[\n{}\n]\n", items.join(",\n"))
+            }
+        }
+        ListFormat::Csv => {
+            let mut csv = String::from("path,bytes\n");
+            for (path, bytes) in entries {
+                csv.push_str(&format!("{},{bytes}\n", csv_field(&path.to_string_lossy())));
+            }
+            csv
+        }
+    }
+}
+
+/// Renders one `size  directory` line per directory holding any of `entries`,
+/// counting every file below it, largest first; ties are ordered by path.
+///
+/// Files directly in the root are counted under `.`, which therefore holds the total.
+pub fn render_du(entries: &[(&Path, u64)]) -> String {
+    let mut totals: HashMap<&Path, u64> = HashMap::new();
+    for (path, bytes) in entries {
+        for dir in path.ancestors().skip(1) {
+            *totals.entry(dir).or_default() += bytes;
+        }
+    }
+
+    let mut totals: Vec<(&Path, u64)> = totals.into_iter().collect();
+    totals.sort_by(|(a_dir, a_bytes), (b_dir, b_bytes)| {
+        b_bytes.cmp(a_bytes).then_with(|| a_dir.cmp(b_dir))
+    });
+
+    let sizes: Vec<String> = totals
+        .iter()
+        .map(|(_, bytes)| utils::format_bytes(*bytes as usize))
+        .collect();
+    let width = sizes.iter().map(String::len).max().unwrap_or(0);
+    totals
+        .iter()
+        .zip(&sizes)
+        .map(|((dir, _), size)| {
+            let dir = if dir.as_os_str().is_empty() {
+                Path::new(".")
+            } else {
+                dir
+            };
+            format!("{size:>width$}  {}\n", dir.display())
+        })
+        .collect()
+}
+
+// -------------------------------------------- Private Helper Functions --------------------------------------------
+
+/// Quotes a CSV field when it contains a delimiter, quote or line break.
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod listing_tests {
+    use super::*;
+
+    #[test]
+    fn test_render_each_format() {
+        let entries = [
+            (Path::new("src/main.rs"), 12),
+            (Path::new("say \"hi\", twice.txt"), 3),
+        ];
+
+        assert_eq!(
+            render(&entries, ListFormat::Plain, false),
+            "src/main.rs\nsay \"hi\", twice.txt\n"
+        );
+        assert_eq!(
+            render(&entries, ListFormat::Plain, true),
+            "src/main.rs\0say \"hi\", twice.txt\0"
+        );
+        assert_eq!(
+            render(&entries, ListFormat::Json, false),
+            "[\n  {\"path\": \"src/main.rs\", \"bytes\": 12},\n  {\"path\": \"say \\\"hi\\\", twice.txt\", \"bytes\": 3}\n]\n"
+        );
+        assert_eq!(
+            render(&entries, ListFormat::Csv, false),
+            "path,bytes\nsrc/main.rs,12\n\"say \"\"hi\"\", twice.txt\",3\n"
+        );
+        assert_eq!(render(&[], ListFormat::Json, false), "[]\n");
+    }
+
+    #[test]
+    fn test_render_du_sums_directories() {
+        let entries = [
+            (Path::new("src/main.rs"), 100),
+            (Path::new("src/core/mod.rs"), 2048),
+            (Path::new("docs/guide.md"), 100),
+            (Path::new("README.md"), 10),
+        ];
+
+        assert_eq!(
+            render_du(&entries),
+            "2.2 KB  .\n2.1 KB  src\n2.0 KB  src/core\n 100 B  docs\n"
+        );
+        assert_eq!(render_du(&[]), "");
+    }
+}
@@ -0,0 +1,651 @@
+//! output - The single writer every traversal appends to.
+//!
+//! The output file is opened (and truncated) once per run, then handed to each
+//! walker in turn. Everything written passes through `OutputSink`, so it can count
+//! bytes and tee the stream into a SHA-256 hasher without re-reading the file.
+//!
+//! Files are rendered by a `format::Renderer` for `--format`. When the clipboard wants
+//! a different format, a second renderer builds that document in memory alongside.
+//!
+//! With `--split-size` the sink writes numbered parts instead (`out.001.txt`, ...),
+//! rolling over to the next part before a file that would push the current one past
+//! the cap. Every part starts with the output marker.
+//!
+//! An in-memory sink (`OutputSink::in_memory`) renders the same way into a buffer,
+//! for clipboard snapshots that never touch the disk.
+
+pub mod format;
+pub mod html;
+pub mod listing;
+
+use crate::core::errors::FileSystemError;
+use anyhow::Context;
+use format::{OutputFormat, Renderer};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// Buffered writer for the run's output, with optional hashing.
+pub struct OutputSink {
+    path: PathBuf,
+    writer: Destination,
+    hasher: Option<Sha256>,
+    bytes_written: u64,
+    renderer: Renderer,
+    /// Renderer and in-memory document for a second format, if requested.
+    mirror: Option<(Renderer, String)>,
+    /// Part rollover state, when writing numbered parts.
+    split: Option<Split>,
+    /// Blank lines between file blocks, applied to both renderers.
+    blank_lines: usize,
+    /// Whether each file starts with a UTF-8 byte-order mark (`--bom`).
+    bom: bool,
+}
+
+/// Where the sink's bytes end up.
+enum Destination {
+    File(BufWriter<File>),
+    Memory(Vec<u8>),
+}
+
+/// Rollover state for `--split-size`.
+struct Split {
+    /// Output path the part names are derived from.
+    base: PathBuf,
+    /// Maximum bytes per part, unless a single file is larger.
+    cap: u64,
+    /// Bytes in the current part.
+    part_bytes: u64,
+    /// Bytes of the marker that opens every part.
+    marker_bytes: u64,
+    /// Every part opened so far, in order.
+    parts: Vec<PathBuf>,
+}
+
+/// What is left once the output has been flushed.
+#[derive(Debug, Default, PartialEq)]
+pub struct FinishedOutput {
+    /// Hex SHA-256 digest of the output, if hashing was enabled.
+    pub digest: Option<String>,
+    /// The document rendered in the mirror format, if one was requested.
+    pub mirror: Option<String>,
+    /// Extensions rendered with a bare markdown fence, with file counts.
+    pub unrecognized_extensions: BTreeMap<String, usize>,
+    /// Parts written with `--split-size`, in order; empty otherwise.
+    pub parts: Vec<PathBuf>,
+    /// Everything written, for an in-memory sink.
+    pub contents: Option<String>,
+}
+
+impl OutputSink {
+    /// Creates (or truncates) the output file.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FileSystemError::WriteFailed` if the file cannot be created.
+    pub fn create(path: &Path) -> anyhow::Result<Self> {
+        Ok(Self {
+            path: path.to_path_buf(),
+            writer: Destination::File(open_output(path)?),
+            hasher: None,
+            bytes_written: 0,
+            renderer: Renderer::new(OutputFormat::default()),
+            mirror: None,
+            split: None,
+            blank_lines: 1,
+            bom: false,
+        })
+    }
+
+    /// Creates a sink that keeps the output in memory, returned by `finish` as
+    /// `contents`.
+    pub fn in_memory() -> Self {
+        Self {
+            path: PathBuf::new(),
+            writer: Destination::Memory(Vec::new()),
+            hasher: None,
+            bytes_written: 0,
+            renderer: Renderer::new(OutputFormat::default()),
+            mirror: None,
+            split: None,
+            blank_lines: 1,
+            bom: false,
+        }
+    }
+
+    /// Creates the first of the numbered parts derived from `path`, each holding at
+    /// most `cap` bytes unless a single file is larger.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FileSystemError::WriteFailed` if the first part cannot be created.
+    pub fn create_split(path: &Path, cap: u64) -> anyhow::Result<Self> {
+        let first = part_path(path, 1);
+        let mut sink = Self::create(&first)?;
+        sink.split = Some(Split {
+            base: path.to_path_buf(),
+            cap,
+            part_bytes: 0,
+            marker_bytes: 0,
+            parts: vec![first],
+        });
+        Ok(sink)
+    }
+
+    /// Sets the format files are rendered in.
+    pub fn with_format(mut self, format: OutputFormat) -> Self {
+        self.renderer = Renderer::new(format);
+        self.renderer.set_blank_lines(self.blank_lines);
+        self
+    }
+
+    /// Additionally renders everything in `format` into memory, for the clipboard.
+    pub fn with_mirror(mut self, format: Option<OutputFormat>) -> Self {
+        self.mirror = format.map(|format| {
+            let mut renderer = Renderer::new(format);
+            renderer.set_blank_lines(self.blank_lines);
+            (renderer, String::new())
+        });
+        self
+    }
+
+    /// Sets how many blank lines separate file blocks, in the output and the mirror.
+    pub fn with_blank_lines(mut self, blank_lines: usize) -> Self {
+        self.blank_lines = blank_lines;
+        self.renderer.set_blank_lines(blank_lines);
+        if let Some((renderer, _)) = &mut self.mirror {
+            renderer.set_blank_lines(blank_lines);
+        }
+        self
+    }
+
+    /// Starts the output file, and every part when splitting, with a UTF-8 BOM.
+    pub fn with_bom(mut self, enabled: bool) -> Self {
+        self.bom = enabled;
+        self
+    }
+
+    /// Enables SHA-256 hashing of everything written from now on.
+    pub fn with_hash(mut self, enabled: bool) -> Self {
+        self.hasher = enabled.then(Sha256::new);
+        self
+    }
+
+    /// Path of the output file (the current part when splitting); empty in memory.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Total bytes written so far.
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes_written
+    }
+
+    /// Writes the marker that identifies the file as treeclip output.
+    ///
+    /// Written to the output file only, after the BOM if enabled; the clipboard copy
+    /// leaves it out (`format::strip_marker`).
+    ///
+    /// # Errors
+    ///
+    /// Returns `FileSystemError::WriteFailed` if the output cannot be written.
+    pub fn write_marker(&mut self) -> anyhow::Result<()> {
+        let mut rendered = self.renderer.marker();
+        if self.bom {
+            rendered.insert(0, format::UTF8_BOM);
+        }
+        if let Some(split) = &mut self.split {
+            split.marker_bytes = rendered.len() as u64;
+        }
+        self.write_rendered(&rendered)
+            .with_context(|| format!("Failed to write output marker to: {}", self.path.display()))
+    }
+
+    /// Writes the table of contents.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FileSystemError::WriteFailed` if the output cannot be written.
+    pub fn write_toc(&mut self, entries: &[(&Path, u64)]) -> anyhow::Result<()> {
+        if let Some((renderer, document)) = &mut self.mirror {
+            document.push_str(&renderer.toc(entries));
+        }
+        let rendered = self.renderer.toc(entries);
+        self.write_rendered(&rendered).with_context(|| {
+            format!(
+                "Failed to write table of contents to: {}",
+                self.path.display()
+            )
+        })
+    }
+
+    /// Writes the path prefix stripped from every header (`--collapse-paths`).
+    ///
+    /// # Errors
+    ///
+    /// Returns `FileSystemError::WriteFailed` if the write fails.
+    pub fn write_path_prefix(&mut self, prefix: &Path) -> anyhow::Result<()> {
+        if let Some((renderer, document)) = &mut self.mirror {
+            document.push_str(&renderer.path_prefix(prefix));
+        }
+        let rendered = self.renderer.path_prefix(prefix);
+        self.write_rendered(&rendered)
+            .with_context(|| format!("Failed to write path prefix to: {}", self.path.display()))
+    }
+
+    /// Writes the directory tree.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FileSystemError::WriteFailed` if the output cannot be written.
+    pub fn write_tree(&mut self, tree: &str) -> anyhow::Result<()> {
+        if let Some((renderer, document)) = &mut self.mirror {
+            document.push_str(&renderer.tree(tree));
+        }
+        let rendered = self.renderer.tree(tree);
+        self.write_rendered(&rendered)
+            .with_context(|| format!("Failed to write directory tree to: {}", self.path.display()))
+    }
+
+    /// Writes one file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path shown for the file
+    /// * `header` - Text-format header line, including any prefix and suffix
+    /// * `body` - File content, already trimmed
+    ///
+    /// # Errors
+    ///
+    /// Returns `FileSystemError::WriteFailed` if the output cannot be written.
+    pub fn write_file(&mut self, path: &Path, header: &str, body: &str) -> anyhow::Result<()> {
+        if let Some((renderer, document)) = &mut self.mirror {
+            document.push_str(&renderer.file(path, header, body));
+        }
+        let file = self.renderer.file(path, header, body);
+        let mut rendered = file.as_str();
+        if self
+            .split
+            .as_ref()
+            .is_some_and(|split| split.is_full(rendered.len()))
+        {
+            self.next_part()?;
+            // A part opens like the first one, without the blank lines between files;
+            // splitting is text and markdown only, where that separator is all newlines
+            rendered = rendered.trim_start_matches('\n');
+        }
+        self.write_rendered(rendered)
+            .with_context(|| format!("Failed to write file content for: {}", path.display()))
+    }
+
+    /// Closes the document and flushes the output.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FileSystemError::WriteFailed` if buffered data cannot be flushed.
+    pub fn finish(mut self) -> anyhow::Result<FinishedOutput> {
+        let closing = self.renderer.finish();
+        self.write_rendered(&closing)
+            .with_context(|| format!("Failed to write output file: {}", self.path.display()))?;
+
+        self.writer
+            .flush()
+            .map_err(|e| FileSystemError::WriteFailed {
+                path: self.path.clone(),
+                source: e,
+            })
+            .with_context(|| format!("Failed to flush output file: {}", self.path.display()))?;
+
+        // Both renderers see the same files, so count from whichever rendered markdown
+        let mut unrecognized_extensions = self.renderer.unrecognized_extensions().clone();
+        if let (true, Some((renderer, _))) = (unrecognized_extensions.is_empty(), &self.mirror) {
+            unrecognized_extensions = renderer.unrecognized_extensions().clone();
+        }
+
+        Ok(FinishedOutput {
+            digest: self.hasher.map(|hasher| format!("{:x}", hasher.finalize())),
+            unrecognized_extensions,
+            parts: self.split.map(|split| split.parts).unwrap_or_default(),
+            // Only rendered text is ever written, so the buffer is valid UTF-8
+            contents: match self.writer {
+                Destination::Memory(buffer) => Some(String::from_utf8_lossy(&buffer).into_owned()),
+                Destination::File(_) => None,
+            },
+            mirror: self.mirror.map(|(renderer, mut document)| {
+                document.push_str(&renderer.finish());
+                document
+            }),
+        })
+    }
+}
+
+// -------------------------------------------- Private Helper Functions --------------------------------------------
+
+impl OutputSink {
+    /// Flushes the current part and continues in the next one, starting with the marker.
+    fn next_part(&mut self) -> anyhow::Result<()> {
+        self.writer
+            .flush()
+            .map_err(|e| FileSystemError::WriteFailed {
+                path: self.path.clone(),
+                source: e,
+            })
+            .with_context(|| format!("Failed to flush output file: {}", self.path.display()))?;
+
+        let split = self.split.as_mut().expect("only called when splitting");
+        let next = part_path(&split.base, split.parts.len() + 1);
+        split.parts.push(next.clone());
+        split.part_bytes = 0;
+
+        self.writer = Destination::File(open_output(&next)?);
+        self.path = next;
+        self.write_marker()
+    }
+
+    /// Appends already rendered text to the output.
+    fn write_rendered(&mut self, rendered: &str) -> Result<(), FileSystemError> {
+        self.write_all(rendered.as_bytes())
+            .map_err(|e| FileSystemError::WriteFailed {
+                path: self.path.clone(),
+                source: e,
+            })
+    }
+}
+
+impl Write for OutputSink {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.writer.write(buf)?;
+        if let Some(hasher) = &mut self.hasher {
+            hasher.update(&buf[..written]);
+        }
+        self.bytes_written += written as u64;
+        if let Some(split) = &mut self.split {
+            split.part_bytes += written as u64;
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+impl Write for Destination {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::File(writer) => writer.write(buf),
+            Self::Memory(buffer) => buffer.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::File(writer) => writer.flush(),
+            Self::Memory(_) => Ok(()),
+        }
+    }
+}
+
+impl Split {
+    /// Whether `len` more bytes would overflow a part that already holds a file.
+    fn is_full(&self, len: usize) -> bool {
+        self.part_bytes > self.marker_bytes && self.part_bytes + len as u64 > self.cap
+    }
+}
+
+/// Creates (or truncates) one output file.
+fn open_output(path: &Path) -> anyhow::Result<BufWriter<File>> {
+    let file = File::create(path)
+        .map_err(|e| FileSystemError::WriteFailed {
+            path: path.to_path_buf(),
+            source: e,
+        })
+        .with_context(|| format!("Failed to create output file: {}", path.display()))?;
+    Ok(BufWriter::new(file))
+}
+
+/// Returns the path of part `index` of `output`: `out.txt` becomes `out.001.txt`.
+pub fn part_path(output: &Path, index: usize) -> PathBuf {
+    let stem = output.file_stem().unwrap_or(output.as_os_str());
+    let mut name = stem.to_owned();
+    name.push(format!(".{index:03}"));
+    if let Some(extension) = output.extension() {
+        name.push(".");
+        name.push(extension);
+    }
+    output.with_file_name(name)
+}
+
+/// Writes a `sha256sum`-compatible sidecar next to the output (`<output>.sha256`).
+///
+/// # Errors
+///
+/// Returns `FileSystemError::WriteFailed` if the sidecar cannot be written.
+pub fn write_hash_sidecar(output: &Path, digest: &str) -> anyhow::Result<PathBuf> {
+    let mut sidecar = output.as_os_str().to_owned();
+    sidecar.push(".sha256");
+    let sidecar = PathBuf::from(sidecar);
+
+    let file_name = output.file_name().map_or_else(
+        || output.display().to_string(),
+        |name| name.to_string_lossy().into_owned(),
+    );
+
+    std::fs::write(&sidecar, format!("{digest}  {file_name}\n"))
+        .map_err(|e| FileSystemError::WriteFailed {
+            path: sidecar.clone(),
+            source: e,
+        })
+        .with_context(|| format!("Failed to write hash sidecar: {}", sidecar.display()))?;
+
+    Ok(sidecar)
+}
+
+/// Reads up to `count` lines from the start of the output, for `--preview`.
+///
+/// # Returns
+///
+/// Returns the lines read and whether the output continues past them.
+///
+/// # Errors
+///
+/// Returns `FileSystemError::ReadFailed` if the output cannot be read.
+pub fn read_head(output: &Path, count: usize) -> anyhow::Result<(Vec<String>, bool)> {
+    let read_failed = |e| FileSystemError::ReadFailed {
+        path: output.to_path_buf(),
+        source: e,
+    };
+
+    let file = File::open(output)
+        .map_err(read_failed)
+        .with_context(|| format!("Failed to open output for preview: {}", output.display()))?;
+
+    let mut lines = BufReader::new(file).lines();
+    let head = lines
+        .by_ref()
+        .take(count)
+        .collect::<io::Result<Vec<_>>>()
+        .map_err(read_failed)
+        .with_context(|| format!("Failed to read output for preview: {}", output.display()))?;
+
+    Ok((head, lines.next().is_some()))
+}
+
+#[cfg(test)]
+mod output_tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_sink_hash_matches_file_contents() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.path().join("out.txt");
+
+        let mut sink = OutputSink::create(&path)?.with_hash(true);
+        assert_eq!(sink.bytes_written(), 0);
+        write!(sink, "hello")?;
+        writeln!(sink, " world")?;
+        assert_eq!(sink.bytes_written(), 12);
+
+        let digest = sink.finish()?.digest.expect("hashing was enabled");
+        assert_eq!(fs::read_to_string(&path)?, "hello world\n");
+        assert_eq!(
+            digest,
+            "a948904f2f0f479b8f8197694b30184b0d2ed1c1cd2a1ec0fb85d299a192a447"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sink_truncates_and_skips_hash_by_default() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.path().join("out.txt");
+        fs::write(&path, "old content from a previous run")?;
+
+        let mut sink = OutputSink::create(&path)?;
+        write!(sink, "new")?;
+
+        assert_eq!(sink.finish()?, FinishedOutput::default());
+        assert_eq!(fs::read_to_string(&path)?, "new");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mirror_renders_second_format() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.path().join("out.md");
+
+        let mut sink = OutputSink::create(&path)?
+            .with_format(OutputFormat::Markdown)
+            .with_mirror(Some(OutputFormat::Json));
+        sink.write_file(Path::new("a.txt"), "==> a.txt", "hi")?;
+
+        let finished = sink.finish()?;
+        assert_eq!(fs::read_to_string(&path)?, "## a.txt\n\n```text\nhi\n```\n");
+        assert_eq!(
+            finished.mirror.as_deref(),
+            Some("[\n  {\"path\": \"a.txt\", \"content\": \"hi\"}\n]\n")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_in_memory_sink_returns_contents() -> anyhow::Result<()> {
+        let mut sink = OutputSink::in_memory().with_format(OutputFormat::Markdown);
+        sink.write_file(Path::new("a.txt"), "==> a.txt", "hi")?;
+
+        let finished = sink.finish()?;
+        assert_eq!(
+            finished.contents.as_deref(),
+            Some("## a.txt\n\n```text\nhi\n```\n")
+        );
+        assert!(finished.parts.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_rolls_over_at_file_boundaries() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.path().join("out.txt");
+
+        let mut sink = OutputSink::create_split(&path, 60)?;
+        sink.write_marker()?;
+        sink.write_file(Path::new("a.txt"), "==> a.txt", "aaaa")?;
+        sink.write_file(Path::new("b.txt"), "==> b.txt", "bbbb")?;
+        sink.write_file(Path::new("big.txt"), "==> big.txt", &"x".repeat(50))?;
+        let parts = sink.finish()?.parts;
+
+        let marker = format!("# {}\n", format::OUTPUT_MARKER);
+        assert_eq!(
+            parts,
+            ["out.001.txt", "out.002.txt"].map(|name| temp_dir.path().join(name))
+        );
+        assert_eq!(
+            fs::read_to_string(&parts[0])?,
+            format!("{marker}==> a.txt\naaaa\n\n==> b.txt\nbbbb\n")
+        );
+        // A file bigger than the cap gets a part of its own, which opens like the first
+        assert_eq!(
+            fs::read_to_string(&parts[1])?,
+            format!("{marker}==> big.txt\n{}\n", "x".repeat(50))
+        );
+        for part in &parts {
+            assert!(fs::read_to_string(part)?.starts_with(&format!("{marker}==> ")));
+        }
+        assert!(!path.exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bom_starts_each_part_once() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.path().join("out.txt");
+
+        let mut sink = OutputSink::create_split(&path, 40)?.with_bom(true);
+        sink.write_marker()?;
+        sink.write_file(Path::new("a.txt"), "==> a.txt", "aaaa")?;
+        sink.write_file(Path::new("b.txt"), "==> b.txt", "bbbb")?;
+        let parts = sink.finish()?.parts;
+
+        assert_eq!(parts.len(), 2);
+        let bom = [0xEF, 0xBB, 0xBF];
+        for part in &parts {
+            let bytes = fs::read(part)?;
+            assert!(bytes.starts_with(&bom));
+            assert_eq!(bytes.windows(3).filter(|window| *window == bom).count(), 1);
+        }
+
+        // Off by default
+        let mut sink = OutputSink::create(&path)?;
+        sink.write_marker()?;
+        sink.finish()?;
+        assert!(!fs::read(&path)?.starts_with(&bom));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_part_path() {
+        assert_eq!(
+            part_path(Path::new("a/out.txt"), 2),
+            Path::new("a/out.002.txt")
+        );
+        assert_eq!(part_path(Path::new("dump"), 12), Path::new("dump.012"));
+    }
+
+    #[test]
+    fn test_read_head() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.path().join("out.txt");
+        fs::write(&path, "one\ntwo\nthree\n")?;
+
+        assert_eq!(
+            read_head(&path, 2)?,
+            (vec!["one".into(), "two".into()], true)
+        );
+        assert!(!read_head(&path, 3)?.1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_hash_sidecar() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("dump.txt");
+
+        let sidecar = write_hash_sidecar(&output, "abc123")?;
+
+        assert_eq!(sidecar, temp_dir.path().join("dump.txt.sha256"));
+        assert_eq!(fs::read_to_string(&sidecar)?, "abc123  dump.txt\n");
+
+        Ok(())
+    }
+}
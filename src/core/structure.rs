@@ -0,0 +1,275 @@
+//! structure - Best-effort extraction of declarations for `--context-only` output.
+//!
+//! Each supported language has a small line-based extractor that keeps imports and
+//! type/function declarations while eliding function bodies. There is no real
+//! parser behind this: braces or indentation inside string literals can confuse it,
+//! so the result is meant for reading, not compiling.
+//!
+//! To support another language, write an extractor with the `Extractor` signature
+//! and register its extensions in `extractor_for`.
+
+use regex::Regex;
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// Turns a file's full source into its declaration outline.
+type Extractor = fn(&str) -> String;
+
+/// Rust function declarations, including qualifiers like `pub(crate) async unsafe`.
+static RUST_FN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"^\s*(pub(\([^)]*\))?\s+)?((const|async|unsafe|extern(\s+"[^"]*")?)\s+)*fn\s"#)
+        .expect("valid Rust fn regex")
+});
+
+/// Python function definitions.
+static PYTHON_DEF: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*(async\s+)?def\s").expect("valid Python def regex"));
+
+/// Python lines kept as-is: classes, decorators and imports.
+static PYTHON_KEPT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*(class\s|@|import\s|from\s.+\simport\s)").expect("valid Python regex")
+});
+
+/// Extracts the declaration outline of a file.
+///
+/// # Returns
+///
+/// Returns `None` if the file's language is not supported, in which case callers
+/// should fall back to the full content.
+pub fn extract(path: &Path, content: &str) -> Option<String> {
+    extractor_for(path).map(|extractor| extractor(content))
+}
+
+// -------------------------------------------- Private Helper Functions --------------------------------------------
+
+/// Chooses the extractor for a file based on its extension.
+fn extractor_for(path: &Path) -> Option<Extractor> {
+    match path.extension()?.to_str()? {
+        "rs" => Some(extract_rust),
+        "py" | "pyi" => Some(extract_python),
+        _ => None,
+    }
+}
+
+/// Keeps every Rust item but replaces function bodies with `{ ... }`.
+///
+/// Plain `//` comments are dropped; doc comments and attributes are kept.
+fn extract_rust(source: &str) -> String {
+    let mut outline = Vec::new();
+    let mut lines = source.lines();
+    // Brace balance of the function body currently being skipped
+    let mut skip_balance = 0i64;
+
+    while let Some(line) = lines.next() {
+        if skip_balance > 0 {
+            skip_balance += brace_balance(line);
+            continue;
+        }
+
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("//") && !trimmed.starts_with("///") && !trimmed.starts_with("//!") {
+            continue;
+        }
+
+        if !RUST_FN.is_match(line) {
+            push_collapsing_blanks(&mut outline, line.to_string());
+            continue;
+        }
+
+        // Signatures may span several lines; gather until the body or `;` starts
+        let mut signature = line.to_string();
+        while !signature.contains('{') && !signature.trim_end().ends_with(';') {
+            match lines.next() {
+                Some(next) => {
+                    signature.push('\n');
+                    signature.push_str(next);
+                }
+                None => break,
+            }
+        }
+
+        match signature.find('{') {
+            Some(open) => {
+                outline.push(format!("{} {{ ... }}", signature[..open].trim_end()));
+                skip_balance = brace_balance(&signature[open..]);
+            }
+            None => outline.push(signature),
+        }
+    }
+
+    finish(outline)
+}
+
+/// Keeps imports, classes, decorators and `def` signatures, replacing bodies with `...`.
+fn extract_python(source: &str) -> String {
+    let mut outline = Vec::new();
+    let mut lines = source.lines();
+    // Indentation of the `def` whose body is currently being skipped
+    let mut skip_indent: Option<usize> = None;
+
+    while let Some(line) = lines.next() {
+        let indent = line.len() - line.trim_start().len();
+
+        if let Some(def_indent) = skip_indent {
+            if line.trim().is_empty() || indent > def_indent {
+                continue;
+            }
+            skip_indent = None;
+        }
+
+        if PYTHON_DEF.is_match(line) {
+            let mut signature = line.to_string();
+            while !strip_python_comment(&signature).trim_end().ends_with(':') {
+                match lines.next() {
+                    Some(next) => {
+                        signature.push('\n');
+                        signature.push_str(next);
+                    }
+                    None => break,
+                }
+            }
+
+            outline.push(signature);
+            outline.push(format!("{}...", " ".repeat(indent + 4)));
+            skip_indent = Some(indent);
+        } else if PYTHON_KEPT.is_match(line) {
+            outline.push(line.to_string());
+        }
+    }
+
+    finish(outline)
+}
+
+/// Counts `{` minus `}` in a line.
+fn brace_balance(text: &str) -> i64 {
+    text.chars().fold(0, |balance, c| match c {
+        '{' => balance + 1,
+        '}' => balance - 1,
+        _ => balance,
+    })
+}
+
+/// Removes a trailing `# comment` from a Python line (ignoring `#` inside strings is out of scope).
+fn strip_python_comment(line: &str) -> &str {
+    line.rfind('#').map_or(line, |hash| &line[..hash])
+}
+
+/// Appends a line, collapsing runs of blank lines into one.
+fn push_collapsing_blanks(outline: &mut Vec<String>, line: String) {
+    let blank = line.trim().is_empty();
+    if blank && outline.last().is_none_or(|last| last.trim().is_empty()) {
+        return;
+    }
+    outline.push(line);
+}
+
+/// Joins outline lines, dropping trailing blank lines.
+fn finish(mut outline: Vec<String>) -> String {
+    while outline.last().is_some_and(|last| last.trim().is_empty()) {
+        outline.pop();
+    }
+    outline.join("\n")
+}
+
+#[cfg(test)]
+mod structure_tests {
+    use super::*;
+
+    #[test]
+    fn test_rust_bodies_are_elided() {
+        let source = r#"use std::fs;
+
+// internal note
+/// A point.
+#[derive(Debug)]
+pub struct Point {
+    pub x: i32,
+}
+
+impl Point {
+    /// Creates a point.
+    pub fn new(x: i32) -> Self {
+        if x > 0 {
+            println!("{}", x);
+        }
+        Self { x }
+    }
+
+    pub(crate) async fn load(
+        path: &str,
+    ) -> Self {
+        todo!()
+    }
+}
+
+pub trait Shape {
+    fn area(&self) -> f64;
+}
+"#;
+
+        let expected = r#"use std::fs;
+
+/// A point.
+#[derive(Debug)]
+pub struct Point {
+    pub x: i32,
+}
+
+impl Point {
+    /// Creates a point.
+    pub fn new(x: i32) -> Self { ... }
+
+    pub(crate) async fn load(
+        path: &str,
+    ) -> Self { ... }
+}
+
+pub trait Shape {
+    fn area(&self) -> f64;
+}"#;
+
+        assert_eq!(extract(Path::new("lib.rs"), source).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_python_bodies_are_elided() {
+        let source = r#"import os
+from typing import List
+
+LIMIT = 10
+
+@dataclass
+class User:
+    name: str
+
+    def greet(self, other: "User") -> str:  # friendly
+        message = f"hi {other.name}"
+
+        return message
+
+async def fetch(
+    url: str,
+) -> bytes:
+    return b""
+"#;
+
+        let expected = r#"import os
+from typing import List
+@dataclass
+class User:
+    def greet(self, other: "User") -> str:  # friendly
+        ...
+async def fetch(
+    url: str,
+) -> bytes:
+    ..."#;
+
+        assert_eq!(extract(Path::new("app.py"), source).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_unsupported_language_returns_none() {
+        assert_eq!(extract(Path::new("main.go"), "package main"), None);
+        assert_eq!(extract(Path::new("README"), "text"), None);
+    }
+}
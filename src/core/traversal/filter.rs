@@ -1,5 +1,31 @@
 //! filter - Provides filtering functions for directory traversal operations.
 
+use crate::core::output::format;
+use crate::core::ui::animations::statusln;
+use regex::Regex;
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::Path;
+
+/// Number of leading lines scanned for generated-code markers.
+const GENERATED_SCAN_LINES: usize = 5;
+
+/// Lowercase markers that code generators conventionally put near the top of a file.
+const GENERATED_MARKERS: &[&str] = &[
+    "@generated",
+    "code generated by",
+    "do not edit",
+    "auto-generated",
+    "autogenerated",
+];
+
+/// Bytes read from the start of a file to judge whether it is minified.
+const MINIFIED_SAMPLE_BYTES: u64 = 64 * 1024;
+
+/// Files smaller than this are never judged minified; short one-liners are common.
+const MINIFIED_MIN_BYTES: usize = 1024;
+
 /// Checks if a directory entry is hidden (starts with a dot).
 ///
 /// # Arguments
@@ -17,20 +43,234 @@ pub fn is_hidden(entry: &walkdir::DirEntry, verbose: bool) -> bool {
         .map(|str| {
             let hidden_entry = str.starts_with('.');
             if hidden_entry && verbose {
-                println!("Hidden entry '{}' was skipped", entry.path().display());
+                statusln!("Hidden entry '{}' was skipped", entry.path().display());
             }
             hidden_entry
         })
         .unwrap_or(false)
 }
 
+/// Checks if a directory entry is a symlink (`--exclude-symlinks`).
+///
+/// The input path itself is never reported, even when it is a symlink.
+///
+/// # Arguments
+///
+/// * `entry` - The directory entry to check
+/// * `verbose` - If true, logs skipped symlinks to stdout
+pub fn is_symlink(entry: &walkdir::DirEntry, verbose: bool) -> bool {
+    let symlink = entry.depth() > 0 && entry.path_is_symlink();
+    if symlink && verbose {
+        statusln!("Symlink '{}' was skipped", entry.path().display());
+    }
+    symlink
+}
+
+/// Checks whether a file looks machine-generated, based on markers in its first lines.
+///
+/// Recognises conventions such as Go's `// Code generated by ... DO NOT EDIT.`,
+/// `@generated`, and "auto-generated" banners, case-insensitively. Unreadable
+/// files are reported as not generated so the normal read path surfaces the error.
+///
+/// # Arguments
+///
+/// * `path` - The file to scan
+/// * `verbose` - If true, logs skipped files to stdout
+pub fn is_generated(path: &Path, verbose: bool) -> bool {
+    let Ok(file) = File::open(path) else {
+        return false;
+    };
+
+    let generated = BufReader::new(file)
+        .lines()
+        .take(GENERATED_SCAN_LINES)
+        .map_while(Result::ok)
+        .any(|line| {
+            let line = line.to_lowercase();
+            GENERATED_MARKERS.iter().any(|marker| line.contains(marker))
+        });
+
+    if generated && verbose {
+        statusln!("Generated file '{}' was skipped", path.display());
+    }
+    generated
+}
+
+/// Checks whether a file looks minified, based on a sample from its start.
+///
+/// A file counts as minified when its non-blank lines average more than
+/// `max_line_length` characters, or when it has fewer than `min_newline_ratio`
+/// newlines per byte. Files under 1 KiB and unreadable files never count.
+///
+/// # Arguments
+///
+/// * `path` - The file to scan
+/// * `max_line_length` - Longest average line length, in characters
+/// * `min_newline_ratio` - Fewest newlines per byte
+/// * `verbose` - If true, logs skipped files to stdout
+pub fn is_minified(
+    path: &Path,
+    max_line_length: usize,
+    min_newline_ratio: f64,
+    verbose: bool,
+) -> bool {
+    let Ok(file) = File::open(path) else {
+        return false;
+    };
+
+    let mut sample = Vec::new();
+    if file
+        .take(MINIFIED_SAMPLE_BYTES)
+        .read_to_end(&mut sample)
+        .is_err()
+    {
+        return false;
+    }
+
+    let minified = looks_minified(
+        &String::from_utf8_lossy(&sample),
+        max_line_length,
+        min_newline_ratio,
+    );
+    if minified && verbose {
+        statusln!("Minified file '{}' was skipped", path.display());
+    }
+    minified
+}
+
+/// Returns `true` if `content` passes `--content-match` and `--content-exclude`.
+///
+/// # Arguments
+///
+/// * `content` - Full file content
+/// * `include` - File must match this pattern, if given
+/// * `exclude` - File must not match this pattern, if given
+pub fn matches_content(content: &str, include: Option<&Regex>, exclude: Option<&Regex>) -> bool {
+    include.is_none_or(|re| re.is_match(content)) && !exclude.is_some_and(|re| re.is_match(content))
+}
+
+/// Returns `true` if the file starts with the treeclip output marker.
+///
+/// Catches output from earlier runs wherever it was saved, which path-based
+/// skipping of the current output file cannot.
+pub fn is_previous_output(path: &Path, verbose: bool) -> bool {
+    let Ok(file) = File::open(path) else {
+        return false;
+    };
+
+    let marked = BufReader::new(file)
+        .lines()
+        .next()
+        .and_then(Result::ok)
+        .is_some_and(|line| format::is_marker_line(&line));
+
+    if marked && verbose {
+        statusln!("Previous treeclip output '{}' was skipped", path.display());
+    }
+    marked
+}
+
+/// How a file compares with its counterpart in the `--baseline` directory.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BaselineStatus {
+    /// The baseline has no file at that path.
+    Added,
+    /// The baseline's file has different content.
+    Changed,
+    /// The baseline's file has the same content.
+    Unchanged,
+}
+
+/// Compares `path` with `counterpart`, its file in the baseline directory, by
+/// SHA-256 of their contents.
+///
+/// Files of different sizes differ without being hashed. A file that cannot be read
+/// counts as changed, so it is still collected and the read error reported.
+pub fn baseline_status(path: &Path, counterpart: &Path) -> BaselineStatus {
+    let Some(baseline_size) = fs::metadata(counterpart)
+        .ok()
+        .filter(|meta| meta.is_file())
+        .map(|meta| meta.len())
+    else {
+        return BaselineStatus::Added;
+    };
+    if fs::metadata(path).map(|meta| meta.len()).ok() != Some(baseline_size) {
+        return BaselineStatus::Changed;
+    }
+
+    match (file_digest(path), file_digest(counterpart)) {
+        (Some(digest), Some(baseline)) if digest == baseline => BaselineStatus::Unchanged,
+        _ => BaselineStatus::Changed,
+    }
+}
+
+// -------------------------------------------- Private Helper Functions --------------------------------------------
+
+/// SHA-256 of a file's contents, or `None` if it cannot be read.
+fn file_digest(path: &Path) -> Option<[u8; 32]> {
+    let mut file = File::open(path).ok()?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher).ok()?;
+    Some(hasher.finalize().into())
+}
+
+/// Applies the `is_minified` thresholds to a sample of a file.
+fn looks_minified(sample: &str, max_line_length: usize, min_newline_ratio: f64) -> bool {
+    if sample.len() < MINIFIED_MIN_BYTES {
+        return false;
+    }
+
+    let (lines, chars) = sample
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .fold((0, 0), |(lines, chars), line| {
+            (lines + 1, chars + line.chars().count())
+        });
+    let newlines = sample.bytes().filter(|&byte| byte == b'\n').count();
+
+    chars / lines.max(1) > max_line_length
+        || (newlines as f64 / sample.len() as f64) < min_newline_ratio
+}
+
 #[cfg(test)]
 mod filter_tests {
     use super::*;
-    use std::fs;
     use tempfile::TempDir;
     use walkdir::WalkDir;
 
+    #[test]
+    fn test_baseline_status() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let file = temp_dir.path().join("a.rs");
+        let baseline = temp_dir.path().join("baseline.rs");
+        fs::write(&file, "fn a() {}")?;
+
+        assert_eq!(baseline_status(&file, &baseline), BaselineStatus::Added);
+        fs::write(&baseline, "fn a() {}")?;
+        assert_eq!(baseline_status(&file, &baseline), BaselineStatus::Unchanged);
+        // Same size, different content
+        fs::write(&baseline, "fn b() {}")?;
+        assert_eq!(baseline_status(&file, &baseline), BaselineStatus::Changed);
+        fs::write(&baseline, "fn a() { run(); }")?;
+        assert_eq!(baseline_status(&file, &baseline), BaselineStatus::Changed);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_previous_output() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let dump = temp_dir.path().join("dump.txt");
+        let notes = temp_dir.path().join("notes.md");
+        fs::write(&dump, "# treeclip-output v2\n==> a.rs\nfn a() {}\n")?;
+        fs::write(&notes, "# Notes\n# treeclip-output v2\n")?;
+
+        assert!(is_previous_output(&dump, false));
+        assert!(!is_previous_output(&notes, false));
+
+        Ok(())
+    }
+
     #[test]
     fn test_is_hidden_with_hidden_file() -> anyhow::Result<()> {
         let temp_dir = TempDir::new()?;
@@ -85,6 +325,53 @@ mod filter_tests {
         Ok(())
     }
 
+    #[test]
+    fn test_is_generated_markers() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+
+        let go = temp_dir.path().join("api.pb.go");
+        fs::write(
+            &go,
+            "// Code generated by protoc-gen-go. DO NOT EDIT.\npackage api\n",
+        )?;
+        assert!(is_generated(&go, false));
+
+        let js = temp_dir.path().join("bundle.js");
+        fs::write(&js, "/**\n * @generated\n */\nexport {};\n")?;
+        assert!(is_generated(&js, false));
+
+        let handwritten = temp_dir.path().join("main.rs");
+        fs::write(&handwritten, "fn main() {}\n")?;
+        assert!(!is_generated(&handwritten, false));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_generated_only_scans_leading_lines() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let file = temp_dir.path().join("notes.md");
+        fs::write(&file, "one\ntwo\nthree\nfour\nfive\nsix: DO NOT EDIT\n")?;
+
+        assert!(!is_generated(&file, false));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_looks_minified() {
+        let bundle = format!("!function(){{{}}}();", "var a=1;".repeat(200));
+        assert!(looks_minified(&bundle, 300, 0.001));
+
+        let source = "function add(a, b) {\n    return a + b;\n}\n\n".repeat(40);
+        assert!(!looks_minified(&source, 300, 0.001));
+        // Lowering the line length threshold flags ordinary code too
+        assert!(looks_minified(&source, 10, 0.001));
+
+        // Short files are never judged minified
+        assert!(!looks_minified("var a=1;var b=2;", 300, 0.001));
+    }
+
     #[test]
     fn test_is_hidden_verbose_mode() -> anyhow::Result<()> {
         let temp_dir = TempDir::new()?;
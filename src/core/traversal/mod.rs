@@ -1,2 +1,11 @@
 mod filter;
+mod oneline;
+pub mod pack;
+pub mod progress;
+mod reader;
+pub mod sloc;
+pub mod sort;
+pub mod toc;
+pub mod transform;
+pub mod tree;
 pub mod walker;
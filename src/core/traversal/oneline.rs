@@ -0,0 +1,92 @@
+//! oneline - One `path: first line` entry per file (`--oneline`).
+//!
+//! A dense inventory of a codebase: each file is listed with its first non-blank
+//! line as a preview. Only the start of each file is read, so scanning thousands
+//! of files stays fast.
+
+use crate::core::ui::table;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use terminal_size::{Width, terminal_size};
+
+/// Bytes read from the start of each file when looking for its first line.
+const PREVIEW_READ_BYTES: u64 = 8 * 1024;
+
+/// Line width used when the terminal width cannot be determined.
+const DEFAULT_WIDTH: usize = 100;
+
+/// Returns the first non-blank line of the file, trimmed, or `None` if the start of
+/// the file has none or it cannot be read.
+pub fn first_line(path: &Path) -> Option<String> {
+    let mut head = Vec::new();
+    File::open(path)
+        .ok()?
+        .take(PREVIEW_READ_BYTES)
+        .read_to_end(&mut head)
+        .ok()?;
+
+    // A cut at the read limit may split a character; the lossy decode absorbs it
+    String::from_utf8_lossy(&head)
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+/// Renders one `path: preview` line per entry, each cut to `width` columns, or the
+/// terminal's width when `None`.
+///
+/// # Arguments
+///
+/// * `entries` - Displayed path and first line of each file, in output order
+pub fn render(entries: &[(&Path, Option<&str>)], width: Option<usize>) -> String {
+    let width = width.unwrap_or_else(|| {
+        terminal_size()
+            .map(|(Width(w), _)| w as usize)
+            .unwrap_or(DEFAULT_WIDTH)
+    });
+
+    entries
+        .iter()
+        .map(|(path, preview)| {
+            let line = match preview {
+                Some(preview) => format!("{}: {preview}", path.display()),
+                None => format!("{}:", path.display()),
+            };
+            format!("{}\n", table::truncate_to_width(&line, width))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod oneline_tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_first_line_skips_blank_lines() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.path().join("main.rs");
+        fs::write(&path, "\n   \n  //! Entry point\nfn main() {}\n")?;
+        assert_eq!(first_line(&path).as_deref(), Some("//! Entry point"));
+
+        fs::write(&path, "\n\n")?;
+        assert_eq!(first_line(&path), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_truncates_to_width() {
+        let entries = [
+            (Path::new("src/main.rs"), Some("fn main() { run(); }")),
+            (Path::new("empty.txt"), None),
+        ];
+        assert_eq!(
+            render(&entries, Some(20)),
+            "src/main.rs: fn mai…\nempty.txt:\n"
+        );
+    }
+}
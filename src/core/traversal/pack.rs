@@ -0,0 +1,67 @@
+//! pack - Groups small files into one block (`--min-significant-size`).
+//!
+//! Directories full of one-liners spend more output on headers than on content.
+//! Files below the threshold are collected into a single `(small files)` block in
+//! which each file is introduced by a short `--- path` line instead of a full header.
+//!
+//! Only text output is packed; the other formats have no way to mark the entries.
+
+use std::path::Path;
+
+/// Path shown in the header of the grouped block, also used by unpack to recognise it.
+pub const SMALL_FILES_TITLE: &str = "(small files)";
+
+/// Prefix of the line introducing each file inside the grouped block.
+pub const ENTRY_PREFIX: &str = "--- ";
+
+/// Returns `true` if `body` can go into the grouped block.
+///
+/// A line of its own starting with `--- ` would read back as the next entry, so such
+/// files keep their full header.
+pub fn can_pack(body: &str) -> bool {
+    !body.lines().any(|line| line.starts_with(ENTRY_PREFIX))
+}
+
+/// Renders the body of the grouped block.
+///
+/// # Arguments
+///
+/// * `entries` - Displayed path and trimmed content of each small file, in output order
+pub fn render(entries: &[(&Path, &str)]) -> String {
+    entries
+        .iter()
+        .map(|(path, body)| {
+            if body.is_empty() {
+                format!("{ENTRY_PREFIX}{}", path.display())
+            } else {
+                format!("{ENTRY_PREFIX}{}\n{body}", path.display())
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod pack_tests {
+    use super::*;
+
+    #[test]
+    fn test_render_lists_each_file_compactly() {
+        let entries = [
+            (Path::new("src/mod.rs"), "pub mod a;"),
+            (Path::new("empty.txt"), ""),
+            (Path::new("b.txt"), "line 1\nline 2"),
+        ];
+
+        assert_eq!(
+            render(&entries),
+            "--- src/mod.rs\npub mod a;\n--- empty.txt\n--- b.txt\nline 1\nline 2"
+        );
+    }
+
+    #[test]
+    fn test_can_pack_refuses_entry_lines() {
+        assert!(can_pack("a: 1\n---\n-- note"));
+        assert!(!can_pack("a: 1\n--- y"));
+    }
+}
@@ -0,0 +1,21 @@
+//! progress - Progress events reported while files are collected.
+//!
+//! The walker itself never draws progress; it hands a `ProgressEvent` to whatever
+//! callback was registered with `Walker::with_progress`. The CLI's verbose counter is
+//! one such consumer, and an embedding application can render its own UI instead.
+
+/// Snapshot of traversal progress, emitted once per file written to the output.
+#[derive(Debug, Clone, Copy)]
+pub struct ProgressEvent {
+    /// Number of files written so far, including the current one.
+    pub files_processed: usize,
+    /// Total bytes written to the output so far, across all input paths.
+    pub bytes_written: u64,
+}
+
+/// Callback receiving progress events.
+///
+/// Bounded by `Send + Sync` because the callback may be invoked from worker threads
+/// when file reading is parallelised; implementations must not assume they run on
+/// the thread that started the traversal.
+pub type ProgressCallback = Box<dyn Fn(&ProgressEvent) + Send + Sync>;
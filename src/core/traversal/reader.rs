@@ -0,0 +1,279 @@
+//! reader - Reads file contents in parallel while keeping memory usage bounded.
+//!
+//! Files are read by a small pool of worker threads, but results are handed to the
+//! caller strictly in input order. A counting semaphore caps how many file contents
+//! may be in flight (read but not yet consumed) at once, so a directory full of
+//! large files cannot exhaust memory while waiting for a slow earlier file.
+
+use crate::core::errors::FileSystemError;
+use anyhow::Context;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Condvar, Mutex, mpsc};
+use std::thread;
+
+/// Reads a single file's contents as UTF-8 text.
+///
+/// # Errors
+///
+/// Returns `FileSystemError::ReadFailed` if the file cannot be read.
+pub fn read_file(path: &Path) -> anyhow::Result<String> {
+    fs::read_to_string(path)
+        .map_err(|e| FileSystemError::ReadFailed {
+            path: path.to_path_buf(),
+            source: e,
+        })
+        .with_context(|| format!("Failed to read file contents from: {}", path.display()))
+}
+
+/// Reads `paths` with a bounded worker pool and feeds the results to `sink` in order.
+///
+/// # Arguments
+///
+/// * `paths` - Files to read, in the order results should be delivered
+/// * `bound` - Maximum number of file contents held in memory at once (minimum 1)
+/// * `read` - Function used to read a single file (called from worker threads)
+/// * `sink` - Receives `(index, result)` pairs in ascending index order
+///
+/// # Errors
+///
+/// Stops reading and returns the first error produced by `sink`. Read errors are
+/// passed to `sink` rather than returned directly, so the caller decides whether
+/// they are fatal.
+pub fn read_ordered<T, R, F>(
+    paths: &[PathBuf],
+    bound: usize,
+    read: R,
+    mut sink: F,
+) -> anyhow::Result<()>
+where
+    T: Send,
+    R: Fn(&Path) -> anyhow::Result<T> + Sync,
+    F: FnMut(usize, anyhow::Result<T>) -> anyhow::Result<()>,
+{
+    if paths.is_empty() {
+        return Ok(());
+    }
+
+    let bound = bound.max(1);
+    let workers = thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(bound)
+        .min(paths.len());
+
+    let permits = Semaphore::new(bound);
+    let next_index = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            let tx = tx.clone();
+            let (permits, next_index, read) = (&permits, &next_index, &read);
+
+            scope.spawn(move || {
+                while permits.acquire() {
+                    let index = next_index.fetch_add(1, Ordering::SeqCst);
+                    if index >= paths.len() {
+                        permits.release();
+                        break;
+                    }
+
+                    if tx.send((index, read(&paths[index]))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(tx);
+
+        // Results may arrive out of order; hold them until their turn comes
+        let mut pending = BTreeMap::new();
+        let mut next_to_write = 0;
+
+        for (index, result) in rx {
+            pending.insert(index, result);
+
+            while let Some(result) = pending.remove(&next_to_write) {
+                let outcome = sink(next_to_write, result);
+                permits.release();
+                next_to_write += 1;
+
+                if let Err(e) = outcome {
+                    permits.close();
+                    return Err(e);
+                }
+            }
+        }
+
+        Ok(())
+    })
+}
+
+// -------------------------------------------- Private Helper Functions --------------------------------------------
+
+/// Minimal counting semaphore that can be closed to wake all waiters.
+struct Semaphore {
+    state: Mutex<(usize, bool)>,
+    available: Condvar,
+}
+
+impl Semaphore {
+    /// Creates a semaphore with the given number of permits.
+    fn new(permits: usize) -> Self {
+        Self {
+            state: Mutex::new((permits, false)),
+            available: Condvar::new(),
+        }
+    }
+
+    /// Blocks until a permit is available. Returns `false` if the semaphore was closed.
+    fn acquire(&self) -> bool {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        loop {
+            let (permits, closed) = &mut *state;
+            if *closed {
+                return false;
+            }
+            if *permits > 0 {
+                *permits -= 1;
+                return true;
+            }
+            state = self
+                .available
+                .wait(state)
+                .unwrap_or_else(|e| e.into_inner());
+        }
+    }
+
+    /// Returns a permit to the pool.
+    fn release(&self) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.0 += 1;
+        self.available.notify_one();
+    }
+
+    /// Closes the semaphore, waking every waiter.
+    fn close(&self) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.1 = true;
+        self.available.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod reader_tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_read_file_missing() {
+        let result = read_file(Path::new("/nonexistent/file.txt"));
+        assert!(result.is_err());
+
+        let error_msg = format!("{:?}", result.unwrap_err());
+        assert!(error_msg.contains("Failed to read file"));
+    }
+
+    #[test]
+    fn test_read_ordered_preserves_order() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let paths: Vec<PathBuf> = (0..50)
+            .map(|i| {
+                let path = temp_dir.path().join(format!("file_{i}.txt"));
+                fs::write(&path, format!("content {i}")).unwrap();
+                path
+            })
+            .collect();
+
+        let mut seen = Vec::new();
+        read_ordered(&paths, 4, read_file, |index, content| {
+            seen.push((index, content?));
+            Ok(())
+        })?;
+
+        assert_eq!(seen.len(), 50);
+        for (i, (index, content)) in seen.iter().enumerate() {
+            assert_eq!(*index, i);
+            assert_eq!(content, &format!("content {i}"));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_ordered_bounds_in_flight_contents() -> anyhow::Result<()> {
+        // Stress test: many medium files, small buffer
+        let temp_dir = TempDir::new()?;
+        let body = "x".repeat(64 * 1024);
+        let paths: Vec<PathBuf> = (0..200)
+            .map(|i| {
+                let path = temp_dir.path().join(format!("medium_{i:03}.txt"));
+                fs::write(&path, format!("{i}\n{body}")).unwrap();
+                path
+            })
+            .collect();
+
+        let bound = 3;
+        let in_flight = AtomicUsize::new(0);
+        let max_in_flight = AtomicUsize::new(0);
+
+        let mut written = 0;
+        read_ordered(
+            &paths,
+            bound,
+            |path| {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                max_in_flight.fetch_max(now, Ordering::SeqCst);
+                read_file(path)
+            },
+            |index, content| {
+                let content = content?;
+                assert!(content.starts_with(&format!("{index}\n")));
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                written += 1;
+                Ok(())
+            },
+        )?;
+
+        assert_eq!(written, 200);
+        assert!(max_in_flight.load(Ordering::SeqCst) <= bound);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_ordered_stops_on_sink_error() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let paths: Vec<PathBuf> = (0..20)
+            .map(|i| {
+                let path = temp_dir.path().join(format!("file_{i}.txt"));
+                fs::write(&path, "data").unwrap();
+                path
+            })
+            .collect();
+
+        let mut calls = 0;
+        let result = read_ordered(&paths, 2, read_file, |index, _| {
+            calls += 1;
+            if index == 5 {
+                anyhow::bail!("sink failed at {index}");
+            }
+            Ok(())
+        });
+
+        assert!(result.is_err());
+        assert_eq!(calls, 6);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_ordered_passes_read_errors_to_sink() {
+        let paths = vec![PathBuf::from("/nonexistent/a.txt")];
+        let result = read_ordered(&paths, 1, read_file, |_, content| content.map(|_| ()));
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,89 @@
+//! sloc - Approximate source lines of code per language (`--sloc`).
+//!
+//! A line counts when it is neither blank nor a comment. Comments are recognised by
+//! the language's single-line syntax from [`lang::comment_style`] at the start of a
+//! line, plus whole-line block comments for languages whose only syntax is a block
+//! (CSS, markup). Comments after code, and block comments in languages that also have
+//! line comments, are not detected, so the numbers are an approximation.
+
+use crate::core::lang;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Adds the source lines of `content` to its language's count in `counts`.
+pub fn add(counts: &mut BTreeMap<String, usize>, path: &Path, content: &str) {
+    *counts.entry(lang::language_name(path)).or_default() += count(path, content);
+}
+
+/// Counts the lines of `content` that are neither blank nor comments.
+pub fn count(path: &Path, content: &str) -> usize {
+    let Some(style) = lang::comment_style(path) else {
+        return content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .count();
+    };
+    let open = style.prefix.trim();
+    let close = style.suffix.trim();
+
+    let mut in_block = false;
+    let mut lines = 0;
+    for line in content.lines() {
+        let line = line.trim();
+        if in_block {
+            in_block = !line.contains(close);
+        } else if let Some(rest) = line.strip_prefix(open) {
+            // Block comments run until the closing delimiter
+            in_block = !close.is_empty() && !rest.contains(close);
+        } else if !line.is_empty() {
+            lines += 1;
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod sloc_tests {
+    use super::*;
+
+    #[test]
+    fn test_count_skips_blank_and_comment_lines() {
+        let rust = "// header\nfn main() {\n\n    // inner\n    run(); // trailing\n}\n";
+        assert_eq!(count(Path::new("main.rs"), rust), 3);
+
+        let python = "#!/usr/bin/env python\nimport os\n  # note\nprint(os.name)\n";
+        assert_eq!(count(Path::new("app.py"), python), 2);
+    }
+
+    #[test]
+    fn test_count_skips_block_comments() {
+        let css = "/* one line */\na { color: red; }\n/*\n  spans\n  lines\n*/\nb {}\n";
+        assert_eq!(count(Path::new("site.css"), css), 2);
+
+        let html = "<!-- note -->\n<p>hi</p>\n";
+        assert_eq!(count(Path::new("index.html"), html), 1);
+    }
+
+    #[test]
+    fn test_unknown_languages_count_non_blank_lines() {
+        assert_eq!(count(Path::new("data.json"), "{\n\n  \"a\": 1\n}\n"), 3);
+    }
+
+    #[test]
+    fn test_add_groups_by_language() {
+        let mut counts = BTreeMap::new();
+        add(&mut counts, Path::new("a.rs"), "fn a() {}\n");
+        add(&mut counts, Path::new("b.RS"), "fn b() {}\n// c\n");
+        add(&mut counts, Path::new("notes.foo"), "x\n");
+        add(&mut counts, Path::new("LICENSE"), "MIT\n");
+
+        assert_eq!(
+            counts,
+            BTreeMap::from([
+                ("foo".to_string(), 1),
+                ("other".to_string(), 1),
+                ("rust".to_string(), 2),
+            ])
+        );
+    }
+}
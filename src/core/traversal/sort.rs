@@ -0,0 +1,286 @@
+//! sort - Orders collected files before their contents are written.
+//!
+//! Every mode falls back to the file path as a secondary key, so files that compare
+//! equal on the primary key (same size, same modification time) always come out in
+//! the same order regardless of what the filesystem returned.
+
+use crate::core::utils;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Order in which files appear in the output (`--sort`).
+#[derive(clap::ValueEnum, serde::Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum SortOrder {
+    /// Directory walk order, as returned by the filesystem
+    #[default]
+    None,
+    /// Alphabetically by path
+    Path,
+    /// Smallest files first
+    Size,
+    /// Least recently modified first
+    #[value(alias = "mtime")]
+    Modified,
+}
+
+/// Sorts `files` in place according to `order`, reversed if `descending`.
+///
+/// Files whose size cannot be read are treated as empty. Files without a modification
+/// time (unreadable metadata, or a filesystem that does not record one) go after all
+/// the others in path order, whichever the direction. With `normalize_unicode`, paths
+/// are compared in NFC form, so NFD and NFC spellings of a name sort together.
+pub fn sort_files(
+    files: &mut [PathBuf],
+    order: SortOrder,
+    descending: bool,
+    normalize_unicode: bool,
+) {
+    let path_key = |path: &PathBuf| {
+        if normalize_unicode {
+            utils::nfc_path(path)
+        } else {
+            path.clone()
+        }
+    };
+
+    match order {
+        SortOrder::None => {}
+        SortOrder::Path => files.sort_by_cached_key(path_key),
+        SortOrder::Size => files.sort_by_cached_key(|path| {
+            let size = fs::metadata(path).map_or(0, |meta| meta.len());
+            (size, path_key(path))
+        }),
+        SortOrder::Modified => {
+            files.sort_by_cached_key(|path| {
+                let modified = modified_time(path);
+                (modified.is_none(), modified, path_key(path))
+            });
+            if descending {
+                // Only the timed files are reversed; the untimed ones keep path order
+                let timed = files.partition_point(|path| modified_time(path).is_some());
+                files[..timed].reverse();
+            }
+            return;
+        }
+    }
+
+    if descending {
+        files.reverse();
+    }
+}
+
+/// Files moved to the front by `--context-first`, highest priority first.
+///
+/// Introductions come before manifests, which say what the project is built with.
+pub const CONTEXT_FILES: &[&str] = &[
+    "README.md",
+    "README",
+    "README.rst",
+    "README.txt",
+    "Cargo.toml",
+    "package.json",
+    "pyproject.toml",
+    "go.mod",
+    "pom.xml",
+    "build.gradle",
+    "Gemfile",
+    "composer.json",
+    "CMakeLists.txt",
+    "Makefile",
+];
+
+/// Moves the files named in `names` to the front, in the order of `names`
+/// (`--context-first`).
+///
+/// Names are matched against the file name, ignoring case, so every directory's
+/// README counts. The sort is stable: files with the same name, and all the files
+/// that are not moved, keep the order they had.
+pub fn context_first(files: &mut [PathBuf], names: &[String]) {
+    files.sort_by_cached_key(|path| {
+        let name = path.file_name().map(|name| name.to_string_lossy());
+        name.and_then(|name| {
+            names
+                .iter()
+                .position(|wanted| wanted.eq_ignore_ascii_case(&name))
+        })
+        .unwrap_or(names.len())
+    });
+}
+
+// -------------------------------------------- Private Helper Functions --------------------------------------------
+
+/// Modification time of `path`, if the filesystem records one.
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+#[cfg(test)]
+mod sort_tests {
+    use super::*;
+    use std::fs::File;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_context_first_keeps_the_rest_in_order() {
+        let mut files: Vec<PathBuf> = [
+            "src/main.rs",
+            "Cargo.toml",
+            "docs/readme.md",
+            "build.rs",
+            "README.md",
+        ]
+        .iter()
+        .map(PathBuf::from)
+        .collect();
+        let names: Vec<String> = CONTEXT_FILES.iter().map(|name| name.to_string()).collect();
+
+        context_first(&mut files, &names);
+        assert_eq!(
+            files,
+            [
+                "docs/readme.md",
+                "README.md",
+                "Cargo.toml",
+                "src/main.rs",
+                "build.rs"
+            ]
+            .map(PathBuf::from)
+        );
+
+        context_first(&mut files, &["build.rs".to_string()]);
+        assert_eq!(files[0], PathBuf::from("build.rs"));
+        assert_eq!(files[1], PathBuf::from("docs/readme.md"));
+    }
+
+    #[test]
+    fn test_size_sort_breaks_ties_by_path() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let names = [
+            "delta.txt",
+            "alpha.txt",
+            "big.txt",
+            "charlie.txt",
+            "bravo.txt",
+        ];
+        for name in names {
+            let content = if name == "big.txt" {
+                "much larger"
+            } else {
+                "same"
+            };
+            fs::write(temp_dir.path().join(name), content)?;
+        }
+
+        // Build the same set in two different starting orders
+        let forward: Vec<PathBuf> = names.iter().map(|n| temp_dir.path().join(n)).collect();
+        let mut reversed = forward.clone();
+        reversed.reverse();
+
+        let mut sorted_forward = forward;
+        sort_files(&mut sorted_forward, SortOrder::Size, false, false);
+        sort_files(&mut reversed, SortOrder::Size, false, false);
+
+        assert_eq!(sorted_forward, reversed);
+        let order: Vec<_> = sorted_forward
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(
+            order,
+            [
+                "alpha.txt",
+                "bravo.txt",
+                "charlie.txt",
+                "delta.txt",
+                "big.txt"
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_modified_sort_both_directions() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let base = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let mut files = Vec::new();
+        for (name, offset) in [("new.txt", 300), ("old.txt", 100), ("mid.txt", 200)] {
+            let path = temp_dir.path().join(name);
+            File::create(&path)?.set_modified(base + Duration::from_secs(offset))?;
+            files.push(path);
+        }
+        // Missing files have no mtime and stay last, in path order
+        files.push(temp_dir.path().join("b-missing.txt"));
+        files.push(temp_dir.path().join("a-missing.txt"));
+
+        let names = |files: &[PathBuf]| -> Vec<String> {
+            files
+                .iter()
+                .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+                .collect()
+        };
+
+        sort_files(&mut files, SortOrder::Modified, false, false);
+        assert_eq!(
+            names(&files),
+            [
+                "old.txt",
+                "mid.txt",
+                "new.txt",
+                "a-missing.txt",
+                "b-missing.txt"
+            ]
+        );
+
+        sort_files(&mut files, SortOrder::Modified, true, false);
+        assert_eq!(
+            names(&files),
+            [
+                "new.txt",
+                "mid.txt",
+                "old.txt",
+                "a-missing.txt",
+                "b-missing.txt"
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_path_sort_normalizes_unicode() {
+        // "e\u{301}" (NFD) sorts after "f" byte-wise, but equals "\u{e9}" (NFC)
+        let mut files = vec![
+            PathBuf::from("\u{e9}b.txt"),
+            PathBuf::from("f.txt"),
+            PathBuf::from("e\u{301}a.txt"),
+        ];
+
+        sort_files(&mut files, SortOrder::Path, false, true);
+        assert_eq!(
+            files,
+            [
+                PathBuf::from("f.txt"),
+                PathBuf::from("e\u{301}a.txt"),
+                PathBuf::from("\u{e9}b.txt"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_path_sort() {
+        let mut files = vec![PathBuf::from("b/a.rs"), PathBuf::from("a/z.rs")];
+        sort_files(&mut files, SortOrder::Path, false, false);
+        assert_eq!(files, [PathBuf::from("a/z.rs"), PathBuf::from("b/a.rs")]);
+    }
+
+    #[test]
+    fn test_none_keeps_walk_order() {
+        let mut files = vec![PathBuf::from("b.rs"), PathBuf::from("a.rs")];
+        sort_files(&mut files, SortOrder::None, false, false);
+        assert_eq!(files, [PathBuf::from("b.rs"), PathBuf::from("a.rs")]);
+    }
+}
@@ -0,0 +1,91 @@
+//! toc - Renders the table of contents written before file bodies (`--toc`).
+
+use crate::core::output::format;
+use crate::core::utils;
+use std::path::Path;
+
+/// First line of every table of contents, also used by unpack to recognise one.
+pub const TOC_TITLE: &str = "Table of Contents";
+
+/// Renders a numbered list of files with their sizes.
+///
+/// # Arguments
+///
+/// * `entries` - Relative path and size in bytes of each file, in output order
+pub fn render(entries: &[(&Path, u64)]) -> String {
+    let width = entries.len().to_string().len();
+    let mut toc = format!(
+        "{TOC_TITLE} ({})\n",
+        utils::format_file_count(entries.len())
+    );
+
+    for (number, (path, size)) in entries.iter().enumerate() {
+        toc.push_str(&format!(
+            "{:>width$}. {} ({})\n",
+            number + 1,
+            path.display(),
+            utils::format_bytes(*size as usize)
+        ));
+    }
+
+    toc
+}
+
+/// Renders the table of contents as a markdown list linking to each file's heading.
+pub fn render_markdown(entries: &[(&Path, u64)]) -> String {
+    let mut toc = format!(
+        "## {TOC_TITLE} ({})\n\n",
+        utils::format_file_count(entries.len())
+    );
+
+    for (number, (path, size)) in entries.iter().enumerate() {
+        toc.push_str(&format!(
+            "{}. [{}](#{}) ({})\n",
+            number + 1,
+            path.display(),
+            format::heading_anchor(path),
+            utils::format_bytes(*size as usize)
+        ));
+    }
+
+    toc
+}
+
+#[cfg(test)]
+mod toc_tests {
+    use super::*;
+
+    #[test]
+    fn test_render_numbers_entries_with_sizes() {
+        let entries = [
+            (Path::new("src/main.rs"), 120),
+            (Path::new("README.md"), 2048),
+        ];
+        let toc = render(&entries);
+
+        assert_eq!(
+            toc,
+            "Table of Contents (2 files)\n1. src/main.rs (120 B)\n2. README.md (2.0 KB)\n"
+        );
+    }
+
+    #[test]
+    fn test_render_markdown_links_headings() {
+        let entries = [(Path::new("src/main.rs"), 120)];
+
+        assert_eq!(
+            render_markdown(&entries),
+            "## Table of Contents (1 file)\n\n1. [src/main.rs](#srcmainrs) (120 B)\n"
+        );
+    }
+
+    #[test]
+    fn test_render_aligns_numbers() {
+        let path = Path::new("f.txt");
+        let entries: Vec<_> = (0..10).map(|_| (path, 1)).collect();
+        let toc = render(&entries);
+
+        assert!(toc.contains("\n 1. f.txt"));
+        assert!(toc.contains("\n10. f.txt"));
+    }
+}
@@ -0,0 +1,477 @@
+//! transform - Content transforms applied to each file before it is written.
+
+use crate::commands::args::ContentReplace;
+use regex::{NoExpand, Regex};
+use std::borrow::Cow;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Default marker appended to lines cut short by `--max-line-length`.
+pub const TRUNCATION_MARKER: &str = "…(truncated)";
+
+/// Placeholder in a truncation marker replaced with the number of characters cut.
+pub const OMITTED_PLACEHOLDER: &str = "{n}";
+
+/// Cuts every line longer than `max_chars` characters down to `max_chars`,
+/// appending `marker`, in which `{n}` stands for the number of characters cut.
+///
+/// # Returns
+///
+/// Returns the transformed content (borrowed when nothing changed) and the number of
+/// lines that were truncated.
+pub fn truncate_long_lines<'a>(
+    content: &'a str,
+    max_chars: usize,
+    marker: &str,
+) -> (Cow<'a, str>, usize) {
+    // Cheap pre-check: no line can be over the limit if the whole file is not
+    if content.len() <= max_chars {
+        return (Cow::Borrowed(content), 0);
+    }
+
+    let mut truncated = 0;
+    let mut output = String::with_capacity(content.len().min(max_chars * 64));
+
+    for line in content.split_inclusive('\n') {
+        // A CRLF line keeps its `\r` after the marker, not counted as content
+        let (text, newline) = match line.strip_suffix("\r\n") {
+            Some(text) => (text, "\r\n"),
+            None => match line.strip_suffix('\n') {
+                Some(text) => (text, "\n"),
+                None => (line, ""),
+            },
+        };
+
+        match text.char_indices().nth(max_chars) {
+            Some((cut, _)) => {
+                output.push_str(&text[..cut]);
+                if marker.contains(OMITTED_PLACEHOLDER) {
+                    let omitted = text[cut..].chars().count();
+                    output.push_str(&marker.replace(OMITTED_PLACEHOLDER, &omitted.to_string()));
+                } else {
+                    output.push_str(marker);
+                }
+                truncated += 1;
+            }
+            None => output.push_str(text),
+        }
+        output.push_str(newline);
+    }
+
+    if truncated == 0 {
+        return (Cow::Borrowed(content), 0);
+    }
+    (Cow::Owned(output), truncated)
+}
+
+/// Marker ending each segment of a line broken by `--wrap`.
+pub const WRAP_MARKER: &str = "↩";
+
+/// Hard-wraps every line wider than `width` columns, ending each broken segment with
+/// `WRAP_MARKER` so that no output line is wider than `width`.
+///
+/// Widths are display widths, so wide characters take two columns. Whitespace is
+/// kept exactly as it was, including at the start of continuation lines, so joining
+/// the segments back together restores the original line. `width` must be at least
+/// 2, one column for content and one for the marker.
+pub fn wrap_long_lines(content: &str, width: usize) -> Cow<'_, str> {
+    // Cheap pre-check: a character takes at most two columns
+    if content.len() <= width / 2 {
+        return Cow::Borrowed(content);
+    }
+
+    let limit = width - 1;
+    let mut wrapped = false;
+    let mut output = String::with_capacity(content.len() + content.len() / width * 4);
+
+    for line in content.split_inclusive('\n') {
+        let text = line.trim_end_matches(['\r', '\n']);
+        if text.width() <= width {
+            output.push_str(line);
+            continue;
+        }
+
+        let mut column = 0;
+        for c in text.chars() {
+            let char_width = c.width().unwrap_or(0);
+            if column > 0 && column + char_width > limit {
+                output.push_str(WRAP_MARKER);
+                output.push('\n');
+                column = 0;
+            }
+            output.push(c);
+            column += char_width;
+        }
+        output.push_str(&line[text.len()..]);
+        wrapped = true;
+    }
+
+    if !wrapped {
+        return Cow::Borrowed(content);
+    }
+    Cow::Owned(output)
+}
+
+/// Expands tabs to spaces, aligning to tab stops every `width` columns.
+///
+/// Columns are counted per line in display width, so a tab after `ab` with a width
+/// of 4 becomes two spaces. A `width` of 0 keeps tabs.
+pub fn expand_tabs(content: &str, width: usize) -> Cow<'_, str> {
+    if width == 0 || !content.contains('\t') {
+        return Cow::Borrowed(content);
+    }
+
+    let mut output = String::with_capacity(content.len());
+    let mut column = 0;
+
+    for c in content.chars() {
+        match c {
+            '\t' => {
+                let spaces = width - column % width;
+                output.extend(std::iter::repeat_n(' ', spaces));
+                column += spaces;
+            }
+            '\n' => {
+                output.push(c);
+                column = 0;
+            }
+            _ => {
+                output.push(c);
+                column += c.width().unwrap_or(0);
+            }
+        }
+    }
+
+    Cow::Owned(output)
+}
+
+/// Ends every line with `ending`, whatever it ended with before (`\n`, `\r\n` or `\r`).
+pub fn convert_line_endings<'a>(content: &'a str, ending: &str) -> Cow<'a, str> {
+    let mut output = String::with_capacity(content.len());
+    let mut rest = content;
+    while let Some(index) = rest.find(['\r', '\n']) {
+        output.push_str(&rest[..index]);
+        output.push_str(ending);
+        let break_len = if rest[index..].starts_with("\r\n") {
+            2
+        } else {
+            1
+        };
+        rest = &rest[index + break_len..];
+    }
+    output.push_str(rest);
+
+    if output == content {
+        return Cow::Borrowed(content);
+    }
+    Cow::Owned(output)
+}
+
+/// Replaces control characters with visible escapes such as `\x00` or `\u{009b}`.
+///
+/// Tabs, newlines and the `\r` of a `\r\n` line ending are kept, so only characters
+/// a paste target might silently drop or act on are rewritten.
+pub fn escape_control(content: &str) -> Cow<'_, str> {
+    let next_chars = content.chars().skip(1).map(Some).chain([None]);
+    if content
+        .chars()
+        .zip(next_chars.clone())
+        .all(|(c, next)| is_kept_control(c, next))
+    {
+        return Cow::Borrowed(content);
+    }
+
+    let mut output = String::with_capacity(content.len());
+    for (c, next) in content.chars().zip(next_chars) {
+        match u32::from(c) {
+            _ if is_kept_control(c, next) => output.push(c),
+            code if code < 0x80 => output.push_str(&format!("\\x{code:02x}")),
+            code => output.push_str(&format!("\\u{{{code:04x}}}")),
+        }
+    }
+
+    Cow::Owned(output)
+}
+
+/// Collapses each run of spaces and tabs inside a line to a single space, and drops
+/// leading and trailing whitespace; with `keep_indent`, leading whitespace is kept
+/// as is. Line endings are unchanged.
+pub fn collapse_whitespace_runs(content: &str, keep_indent: bool) -> Cow<'_, str> {
+    let mut output = String::with_capacity(content.len());
+    for line in content.split_inclusive('\n') {
+        let text = line.trim_end_matches(['\r', '\n']);
+        let ending = &line[text.len()..];
+        let body = text.trim_start_matches([' ', '\t']);
+        if keep_indent {
+            output.push_str(&text[..text.len() - body.len()]);
+        }
+
+        let mut words = body.split([' ', '\t']).filter(|word| !word.is_empty());
+        if let Some(first) = words.next() {
+            output.push_str(first);
+            for word in words {
+                output.push(' ');
+                output.push_str(word);
+            }
+        }
+        output.push_str(ending);
+    }
+
+    if output == content {
+        Cow::Borrowed(content)
+    } else {
+        Cow::Owned(output)
+    }
+}
+
+/// One compiled `--content-replace` substitution.
+pub struct Replacement {
+    find: Regex,
+    replacement: String,
+    /// Whether `$1` and `${name}` in the replacement refer to groups.
+    expand: bool,
+}
+
+/// Compiles `--content-replace` pairs, in the order given.
+///
+/// FIND is matched literally unless `regex` is set (`--content-replace-regex`), in
+/// which case the replacement may also use `$1` or `${name}` for groups.
+///
+/// # Errors
+///
+/// Returns the regex error of the first FIND that does not compile.
+pub fn compile_replacements(
+    pairs: &[ContentReplace],
+    regex: bool,
+) -> Result<Vec<Replacement>, regex::Error> {
+    pairs
+        .iter()
+        .map(|pair| {
+            let find = if regex {
+                Regex::new(&pair.find)?
+            } else {
+                Regex::new(&regex::escape(&pair.find))?
+            };
+            Ok(Replacement {
+                find,
+                replacement: pair.replacement.clone(),
+                expand: regex,
+            })
+        })
+        .collect()
+}
+
+/// Applies each replacement in turn, each one seeing the result of the previous.
+///
+/// # Returns
+///
+/// Returns the transformed content (borrowed when nothing matched) and the total
+/// number of substitutions made.
+pub fn replace_content<'a>(
+    content: &'a str,
+    replacements: &[Replacement],
+) -> (Cow<'a, str>, usize) {
+    let mut output = Cow::Borrowed(content);
+    let mut count = 0;
+    for replacement in replacements {
+        let matches = replacement.find.find_iter(&output).count();
+        if matches == 0 {
+            continue;
+        }
+        count += matches;
+        let replaced = if replacement.expand {
+            replacement
+                .find
+                .replace_all(&output, replacement.replacement.as_str())
+                .into_owned()
+        } else {
+            replacement
+                .find
+                .replace_all(&output, NoExpand(&replacement.replacement))
+                .into_owned()
+        };
+        output = Cow::Owned(replaced);
+    }
+    (output, count)
+}
+
+// -------------------------------------------- Private Helper Functions --------------------------------------------
+
+/// Returns `true` if `c` is written as is by `escape_control`; `next` is the
+/// character after it.
+fn is_kept_control(c: char, next: Option<char>) -> bool {
+    !c.is_control() || c == '\t' || c == '\n' || (c == '\r' && next == Some('\n'))
+}
+
+#[cfg(test)]
+mod transform_tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_long_lines() {
+        let content = "short\nthis line is too long\nok\n";
+        let (result, count) = truncate_long_lines(content, 8, TRUNCATION_MARKER);
+
+        assert_eq!(result, "short\nthis lin…(truncated)\nok\n");
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_truncate_custom_marker_counts_omitted_chars() {
+        let (result, count) = truncate_long_lines("0123456789\nok\né€xyz", 3, " [+{n}]");
+        assert_eq!(result, "012 [+7]\nok\né€x [+2]");
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn test_truncate_keeps_crlf_line_endings() {
+        let (result, count) = truncate_long_lines("0123456789\r\nok\r\n", 3, " [+{n}]");
+        assert_eq!(result, "012 [+7]\r\nok\r\n");
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_truncate_counts_characters_not_bytes() {
+        let (result, count) = truncate_long_lines("ééééé", 5, TRUNCATION_MARKER);
+        assert_eq!(result, "ééééé");
+        assert_eq!(count, 0);
+
+        let (result, count) = truncate_long_lines("éééééé", 5, TRUNCATION_MARKER);
+        assert_eq!(result, "ééééé…(truncated)");
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_wrap_long_lines_keeps_every_character() {
+        let content = "short\n    indented line\nok\n";
+        let result = wrap_long_lines(content, 8);
+
+        assert_eq!(result, "short\n    ind↩\nented l↩\nine\nok\n");
+        assert_eq!(result.replace("↩\n", ""), content);
+        assert!(result.lines().all(|line| line.width() <= 8));
+    }
+
+    #[test]
+    fn test_wrap_long_lines_counts_display_width() {
+        // Two columns each, so three fit before the marker
+        assert_eq!(wrap_long_lines("日本語の文", 8), "日本語↩\nの文");
+        assert_eq!(wrap_long_lines("exactly8\r\n", 8), "exactly8\r\n");
+        assert!(matches!(wrap_long_lines("a\nb\n", 2), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_expand_tabs_mixed_indentation() {
+        let content = "\tfn a() {\n    \tlet x = 1;\n  \t\tdone\n";
+        assert_eq!(
+            expand_tabs(content, 4),
+            "    fn a() {\n        let x = 1;\n        done\n"
+        );
+    }
+
+    #[test]
+    fn test_expand_tabs_aligns_to_tab_stops() {
+        assert_eq!(
+            expand_tabs("a\tb\nabc\tb\nabcd\tb", 4),
+            "a   b\nabc b\nabcd    b"
+        );
+        // Wide characters take two columns
+        assert_eq!(expand_tabs("日\tx", 4), "日  x");
+        assert!(matches!(expand_tabs("a\tb", 0), Cow::Borrowed("a\tb")));
+    }
+
+    #[test]
+    fn test_convert_line_endings() {
+        assert_eq!(convert_line_endings("a\r\nb\rc\nd", "\n"), "a\nb\nc\nd");
+        assert_eq!(convert_line_endings("a\nb\r\n", "\r\n"), "a\r\nb\r\n");
+        assert!(matches!(
+            convert_line_endings("a\nb\n", "\n"),
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn test_escape_control_keeps_whitespace() {
+        assert_eq!(
+            escape_control("a\0b\x1b[31mred\x7f\u{9b}"),
+            "a\\x00b\\x1b[31mred\\x7f\\u{009b}"
+        );
+        assert_eq!(
+            escape_control("tab\tcrlf\r\nlone\rcr"),
+            "tab\tcrlf\r\nlone\\x0dcr"
+        );
+        assert!(matches!(escape_control("a\tb\r\n"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_truncate_borrows_when_unchanged() {
+        let (result, count) = truncate_long_lines("a\nb\n", 1, TRUNCATION_MARKER);
+        assert!(matches!(result, Cow::Borrowed(_)));
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn test_collapse_whitespace_runs() {
+        let content = "fn main() {\r\n    let  x =\t\t1;   \r\n\n}";
+        assert_eq!(
+            collapse_whitespace_runs(content, false),
+            "fn main() {\r\nlet x = 1;\r\n\n}"
+        );
+        assert_eq!(
+            collapse_whitespace_runs("def f():\n    return  1\n", true),
+            "def f():\n    return 1\n"
+        );
+        assert!(matches!(
+            collapse_whitespace_runs("dense text\n", false),
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn test_replace_content_runs_in_order() {
+        let pairs = [
+            ContentReplace {
+                find: "/home/alice".to_string(),
+                replacement: "$HOME".to_string(),
+            },
+            ContentReplace {
+                find: "$HOME/src".to_string(),
+                replacement: "~/src".to_string(),
+            },
+        ];
+        let replacements = compile_replacements(&pairs, false).unwrap();
+
+        let (replaced, count) =
+            replace_content("cd /home/alice/src && ls /home/alice", &replacements);
+        assert_eq!(replaced, "cd ~/src && ls $HOME");
+        assert_eq!(count, 3);
+
+        let (unchanged, count) = replace_content("nothing here", &replacements);
+        assert!(matches!(unchanged, Cow::Borrowed(_)));
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn test_replace_content_regex_expands_groups() {
+        let pairs = [ContentReplace {
+            find: r"https://(\w+)\.internal\.example".to_string(),
+            replacement: "https://$1.example.com".to_string(),
+        }];
+        let replacements = compile_replacements(&pairs, true).unwrap();
+
+        let (replaced, count) = replace_content(
+            "api = \"https://billing.internal.example/v1\"",
+            &replacements,
+        );
+        assert_eq!(replaced, "api = \"https://billing.example.com/v1\"");
+        assert_eq!(count, 1);
+        assert!(
+            compile_replacements(
+                &[ContentReplace {
+                    find: "(".to_string(),
+                    replacement: String::new(),
+                }],
+                true
+            )
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,257 @@
+//! tree - Renders the directory tree of included files (`--tree`, `--no-content`,
+//! `--format dot`).
+
+use crate::core::utils;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// First line of every rendered tree, also used by unpack to recognise one.
+pub const TREE_TITLE: &str = "Directory Tree";
+
+/// One directory level; files are nodes without children.
+#[derive(Default)]
+struct Node {
+    children: BTreeMap<String, Node>,
+    /// Set for directories given explicitly, so they stay directories when empty.
+    dir: bool,
+}
+
+impl Node {
+    fn is_dir(&self) -> bool {
+        self.dir || !self.children.is_empty()
+    }
+}
+
+/// Renders paths as an indented tree, sorted by name at each level.
+///
+/// Directories at `max_depth` are shown with a `...` line counting the files below
+/// them instead of their contents (`--tree-depth`). Directories with nothing
+/// included below them are shown with a trailing `/`.
+///
+/// # Arguments
+///
+/// * `paths` - Relative path of each included file
+/// * `dirs` - Relative path of directories to show even when no file is under them
+///   (`--include-empty-dirs-in-tree`)
+/// * `max_depth` - Deepest level shown, 1 being the entries of the root
+pub fn render(paths: &[&Path], dirs: &[&Path], max_depth: Option<usize>) -> String {
+    let mut root = build(paths);
+    for dir in dirs {
+        insert(&mut root, dir).dir = true;
+    }
+
+    let mut tree = format!(
+        "{TREE_TITLE} ({})\n.\n",
+        utils::format_file_count(paths.len())
+    );
+    render_children(&root, "", max_depth.unwrap_or(usize::MAX), &mut tree);
+    tree
+}
+
+/// Renders paths as a Graphviz DOT graph: a node per directory and file, and an edge
+/// from each directory to what it contains.
+///
+/// Node IDs are the full relative paths, so equal names in different directories
+/// stay distinct; labels show just the name.
+pub fn render_dot(paths: &[&Path]) -> String {
+    let root = build(paths);
+
+    let mut dot = String::from("digraph treeclip {\n    rankdir=LR;\n    node [shape=box];\n");
+    dot.push_str("    \".\" [label=\".\", shape=folder];\n");
+    render_dot_children(&root, ".", "", &mut dot);
+    dot.push_str("}\n");
+    dot
+}
+
+// -------------------------------------------- Private Helper Functions --------------------------------------------
+
+/// Builds the directory hierarchy from relative paths.
+fn build(paths: &[&Path]) -> Node {
+    let mut root = Node::default();
+    for path in paths {
+        insert(&mut root, path);
+    }
+    root
+}
+
+/// Returns the node for `path`, adding it and its parents as needed.
+fn insert<'a>(root: &'a mut Node, path: &Path) -> &'a mut Node {
+    let mut node = root;
+    for component in path.components() {
+        let name = component.as_os_str().to_string_lossy().into_owned();
+        node = node.children.entry(name).or_default();
+    }
+    node
+}
+
+/// Appends node and edge statements for each child of `node`.
+///
+/// `parent_id` is the DOT id of `node`; `prefix` is its path with a trailing `/`
+/// (empty at the root).
+fn render_dot_children(node: &Node, parent_id: &str, prefix: &str, dot: &mut String) {
+    for (name, child) in &node.children {
+        let id = format!("{prefix}{name}");
+        let shape = if child.is_dir() { ", shape=folder" } else { "" };
+
+        dot.push_str(&format!(
+            "    {} [label={}{shape}];\n    {} -> {};\n",
+            dot_string(&id),
+            dot_string(name),
+            dot_string(parent_id),
+            dot_string(&id)
+        ));
+        render_dot_children(child, &id, &format!("{id}/"), dot);
+    }
+}
+
+/// Quotes and escapes a DOT string.
+fn dot_string(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{escaped}\"")
+}
+
+/// Appends one line per child of `node`, recursing into directories for `depth` more
+/// levels.
+fn render_children(node: &Node, prefix: &str, depth: usize, tree: &mut String) {
+    if depth == 0 {
+        tree.push_str(&format!("{prefix}└── ... ({} files)\n", file_count(node)));
+        return;
+    }
+    let count = node.children.len();
+
+    for (index, (name, child)) in node.children.iter().enumerate() {
+        let last = index + 1 == count;
+        let (branch, indent) = if last {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+
+        if child.is_dir() && child.children.is_empty() {
+            tree.push_str(&format!("{prefix}{branch}{name}/\n"));
+            continue;
+        }
+        tree.push_str(&format!("{prefix}{branch}{name}\n"));
+        if !child.children.is_empty() {
+            render_children(child, &format!("{prefix}{indent}"), depth - 1, tree);
+        }
+    }
+}
+
+/// Number of files at or below `node`.
+fn file_count(node: &Node) -> usize {
+    node.children
+        .values()
+        .map(|child| if child.is_dir() { file_count(child) } else { 1 })
+        .sum()
+}
+
+#[cfg(test)]
+mod tree_tests {
+    use super::*;
+
+    #[test]
+    fn test_render_dot_graph() {
+        let paths = [Path::new("src/main.rs"), Path::new("say \"hi\".txt")];
+
+        assert_eq!(
+            render_dot(&paths),
+            r#"digraph treeclip {
+    rankdir=LR;
+    node [shape=box];
+    "." [label=".", shape=folder];
+    "say \"hi\".txt" [label="say \"hi\".txt"];
+    "." -> "say \"hi\".txt";
+    "src" [label="src", shape=folder];
+    "." -> "src";
+    "src/main.rs" [label="main.rs"];
+    "src" -> "src/main.rs";
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_render_stops_at_max_depth() {
+        let paths = [
+            Path::new("src/main.rs"),
+            Path::new("README.md"),
+            Path::new("src/core/mod.rs"),
+            Path::new("src/core/ui/banner.rs"),
+        ];
+
+        assert_eq!(
+            render(&paths, &[], Some(2)),
+            "Directory Tree (4 files)
+.
+├── README.md
+└── src
+    ├── core
+    │   └── ... (2 files)
+    └── main.rs
+"
+        );
+        assert_eq!(
+            render(&paths, &[], Some(1)),
+            "Directory Tree (4 files)
+.
+├── README.md
+└── src
+    └── ... (3 files)
+"
+        );
+    }
+
+    #[test]
+    fn test_render_nests_directories() {
+        let paths = [
+            Path::new("src/main.rs"),
+            Path::new("README.md"),
+            Path::new("src/core/mod.rs"),
+            Path::new("src/cli.rs"),
+        ];
+
+        assert_eq!(
+            render(&paths, &[], None),
+            "Directory Tree (4 files)
+.
+├── README.md
+└── src
+    ├── cli.rs
+    ├── core
+    │   └── mod.rs
+    └── main.rs
+"
+        );
+    }
+
+    #[test]
+    fn test_render_shows_empty_dirs() {
+        let paths = [Path::new("src/main.rs"), Path::new("src/core/mod.rs")];
+        let dirs = [Path::new("src"), Path::new("src/core"), Path::new("docs")];
+
+        assert_eq!(
+            render(&paths, &dirs, None),
+            "Directory Tree (2 files)
+.
+├── docs/
+└── src
+    ├── core
+    │   └── mod.rs
+    └── main.rs
+"
+        );
+        assert_eq!(
+            render(&paths, &dirs, Some(1)),
+            "Directory Tree (2 files)
+.
+├── docs/
+└── src
+    └── ... (2 files)
+"
+        );
+    }
+}
@@ -1,24 +1,171 @@
 //! walker - Handles directory traversal and file content extraction operations.
 
-use crate::commands::args::RunArgs;
-use crate::core::errors::{FileSystemError, TraversalError};
-use crate::core::traversal::filter;
-use crate::core::ui::animations;
-use crate::core::{exclude, utils};
+use crate::commands::args::{PathMask, RunArgs};
+use crate::core::errors::TraversalError;
+use crate::core::output::OutputSink;
+use crate::core::output::format::OutputFormat;
+use crate::core::traversal::progress::{ProgressCallback, ProgressEvent};
+use crate::core::traversal::{filter, oneline, pack, reader, sloc, sort, transform, tree};
+use crate::core::ui::animations::statusln;
+use crate::core::ui::messages::Messages;
+use crate::core::{editorconfig, exclude, git, lang, structure, utils};
 use anyhow::Context;
 use colored::Colorize;
-use std::fs;
-use std::fs::File;
-use std::io::{stdout, Write};
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::time::Instant;
 use walkdir::WalkDir;
 
+/// Which path a followed symlink is listed under (`--symlink-headers`).
+#[derive(clap::ValueEnum, serde::Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum SymlinkHeaders {
+    /// Where the file appears in the tree
+    #[default]
+    Link,
+    /// Where the symlink resolves to
+    Target,
+}
+
+/// What a traversal of a single input path wrote and left out.
+#[derive(Debug, Default)]
+pub struct TraversalSummary {
+    /// Files written, as shown in their headers, in output order.
+    pub files: Vec<PathBuf>,
+    /// Size in bytes of each of `files`, in the same order; only collected for `--dry-run`.
+    pub file_sizes: Vec<u64>,
+    /// Files left out because they looked machine-generated (`--exclude-generated`).
+    pub skipped_generated: usize,
+    /// Files left out because they looked minified (`--exclude-minified`).
+    pub skipped_minified: usize,
+    /// Symlinks left out by `--exclude-symlinks`.
+    pub skipped_symlinks: usize,
+    /// Files left out because an earlier output had them (`--exclude-from-output`).
+    pub skipped_shared: usize,
+    /// Files left out by `--content-match` or `--content-exclude`.
+    pub skipped_content: usize,
+    /// Files not in the `--baseline` directory.
+    pub baseline_added: usize,
+    /// Files whose content differs from the `--baseline` directory's.
+    pub baseline_changed: usize,
+    /// Files left out as identical in the `--baseline` directory.
+    pub baseline_unchanged: usize,
+    /// Files over their extension's `--max-file-size-by-ext` limit.
+    pub skipped_oversized: usize,
+    /// Symlinks that lead back to one of their own ancestors (`--follow-symlinks`).
+    pub symlink_loops: Vec<PathBuf>,
+    /// Symlinks whose target does not exist (`--follow-symlinks`, `--follow-symlink-dirs`).
+    pub dangling_symlinks: Vec<PathBuf>,
+    /// Directories left unread by `--exclude-large-dirs`.
+    pub pruned_dirs: Vec<PathBuf>,
+    /// Files whose shown path `--mask-paths` changed.
+    pub masked_paths: usize,
+    /// Directories that hit `--max-files-per-dir`, with how many files each lost.
+    pub omitted_per_dir: Vec<(PathBuf, usize)>,
+    /// Lines cut short by `--max-line-length`.
+    pub truncated_lines: usize,
+    /// Files containing at least one truncated line.
+    pub files_with_truncated_lines: usize,
+    /// Bytes removed by `--collapse-whitespace-runs`.
+    pub whitespace_bytes_saved: usize,
+    /// Indentation-sensitive files `--collapse-whitespace-runs` changed, as shown.
+    pub whitespace_collapsed_sensitive: Vec<PathBuf>,
+    /// Substitutions made by `--content-replace`.
+    pub content_replacements: usize,
+    /// Whether the `--timeout` deadline cut the traversal short.
+    pub timed_out: bool,
+    /// Files that could not be read, with the reason; they are left out of the output.
+    pub read_errors: Vec<(PathBuf, anyhow::Error)>,
+    /// Counts over the file contents written.
+    pub content_stats: ContentStats,
+    /// Source lines of code per language (`--sloc`); empty otherwise.
+    pub sloc: BTreeMap<String, usize>,
+    /// Content bytes per language (`--language-chart`); empty otherwise.
+    pub language_bytes: BTreeMap<String, usize>,
+}
+
+/// Lines, words, characters and bytes of the file contents written (`--stats`).
+///
+/// Counted as each body is written, so headers never inflate the numbers and the
+/// output never has to be read back. Runs without contents (`--no-content`,
+/// `--oneline`) count the tree or listing that replaces them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ContentStats {
+    pub lines: usize,
+    pub words: usize,
+    pub chars: usize,
+    pub bytes: usize,
+}
+
+impl ContentStats {
+    /// Adds the counts of one file's body.
+    pub fn add(&mut self, body: &str) {
+        self.lines += body.split('\n').count();
+        self.words += body.split_whitespace().count();
+        self.chars += body.chars().count();
+        self.bytes += body.len();
+    }
+}
+
+impl std::ops::AddAssign for ContentStats {
+    fn add_assign(&mut self, other: Self) {
+        self.lines += other.lines;
+        self.words += other.words;
+        self.chars += other.chars;
+        self.bytes += other.bytes;
+    }
+}
+
+impl std::ops::AddAssign for TraversalSummary {
+    fn add_assign(&mut self, other: Self) {
+        self.files.extend(other.files);
+        self.file_sizes.extend(other.file_sizes);
+        self.skipped_generated += other.skipped_generated;
+        self.skipped_minified += other.skipped_minified;
+        self.skipped_symlinks += other.skipped_symlinks;
+        self.skipped_shared += other.skipped_shared;
+        self.baseline_added += other.baseline_added;
+        self.baseline_changed += other.baseline_changed;
+        self.baseline_unchanged += other.baseline_unchanged;
+        self.skipped_oversized += other.skipped_oversized;
+        self.skipped_content += other.skipped_content;
+        self.symlink_loops.extend(other.symlink_loops);
+        self.dangling_symlinks.extend(other.dangling_symlinks);
+        self.pruned_dirs.extend(other.pruned_dirs);
+        self.masked_paths += other.masked_paths;
+        self.omitted_per_dir.extend(other.omitted_per_dir);
+        self.truncated_lines += other.truncated_lines;
+        self.files_with_truncated_lines += other.files_with_truncated_lines;
+        self.whitespace_bytes_saved += other.whitespace_bytes_saved;
+        self.whitespace_collapsed_sensitive
+            .extend(other.whitespace_collapsed_sensitive);
+        self.content_replacements += other.content_replacements;
+        self.timed_out |= other.timed_out;
+        self.read_errors.extend(other.read_errors);
+        self.content_stats += other.content_stats;
+        for (language, lines) in other.sloc {
+            *self.sloc.entry(language).or_default() += lines;
+        }
+        for (language, bytes) in other.language_bytes {
+            *self.language_bytes.entry(language).or_default() += bytes;
+        }
+    }
+}
+
 /// Walker handles directory traversal and content extraction to a single output file.
 pub struct Walker {
     root: PathBuf,
     input: PathBuf,
     output: PathBuf,
     exclude_patterns: Vec<String>,
+    progress: Option<ProgressCallback>,
+    deadline: Option<Instant>,
+    /// Directory headers are relative to, when not the root (`--relative-to-git-root`).
+    header_root: Option<PathBuf>,
+    /// Shown paths to leave out (`--exclude-from-output`).
+    excluded_paths: HashSet<PathBuf>,
 }
 
 impl Walker {
@@ -29,9 +176,46 @@ impl Walker {
             input: input.to_path_buf(),
             output: output.to_path_buf(),
             exclude_patterns: exclude_patterns.to_owned(),
+            progress: None,
+            deadline: None,
+            header_root: None,
+            excluded_paths: HashSet::new(),
         }
     }
 
+    /// Registers a callback that receives a `ProgressEvent` after each file is written.
+    ///
+    /// The callback may be called from worker threads if parallel reading is enabled.
+    pub fn with_progress(
+        mut self,
+        callback: impl Fn(&ProgressEvent) + Send + Sync + 'static,
+    ) -> Self {
+        self.progress = Some(Box::new(callback));
+        self
+    }
+
+    /// Stops the traversal once `deadline` passes, keeping what was written so far.
+    ///
+    /// The summary's `timed_out` flag reports whether it was hit.
+    pub fn with_deadline(mut self, deadline: Option<Instant>) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    /// Shows paths relative to `header_root` instead of the root.
+    ///
+    /// Only headers and listings change; ignore files are still read from the root.
+    pub fn with_header_root(mut self, header_root: Option<PathBuf>) -> Self {
+        self.header_root = header_root;
+        self
+    }
+
+    /// Leaves out files whose shown path is in `paths`.
+    pub fn with_excluded_paths(mut self, paths: HashSet<PathBuf>) -> Self {
+        self.excluded_paths = paths;
+        self
+    }
+
     /// Processes the directory based on the provided run arguments.
     ///
     /// # Errors
@@ -40,83 +224,173 @@ impl Walker {
     /// - Input path does not exist
     /// - Traversal fails
     /// - Output file cannot be written
-    pub fn process_dir(&self, run_args: &RunArgs) -> anyhow::Result<()> {
+    pub fn process_dir(
+        &self,
+        run_args: &RunArgs,
+        sink: &mut OutputSink,
+    ) -> anyhow::Result<TraversalSummary> {
         // Validate that the input path exists (this is the current walker's input path)
         utils::validate_path_exists(&self.input)
             .with_context(|| format!("Input path validation failed: {}", self.input.display()))?;
 
-        self.traverse(run_args)
+        let summary = self
+            .traverse(run_args, sink)
             .with_context(|| format!("Directory traversal failed for: {}", self.input.display()))?;
 
         if run_args.verbose {
-            println!(
+            statusln!(
                 "\n{} {}",
                 "🎊".green(),
                 "Extraction complete! All files gathered~".bright_green()
             );
         }
-        Ok(())
+        Ok(summary)
+    }
+
+    /// Returns every file under the input that a traversal would consider, for change
+    /// detection (`--watch-clipboard-only`).
+    ///
+    /// The same excludes, ignore files, hidden-file and symlink settings apply as in
+    /// `process_dir`, so churn in excluded directories goes unnoticed. `.git` is
+    /// skipped even with hidden files included, since git rewrites it on every
+    /// command, not on edits. Nothing is reported, and unreadable entries are left out.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the exclusion matcher cannot be built.
+    pub fn watched_files(&self, run_args: &RunArgs) -> anyhow::Result<Vec<PathBuf>> {
+        let matcher = self.exclude_matcher(run_args)?;
+        let paths = WalkDir::new(&self.input)
+            .follow_links(run_args.follow_symlinks || run_args.follow_symlink_dirs)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| {
+                entry.file_name() != ".git"
+                    && classify_entry(entry, run_args, &matcher, false) == EntryFilter::Kept
+            })
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().is_file())
+            .map(walkdir::DirEntry::into_path)
+            .filter(|path| *path != self.output)
+            .collect();
+        Ok(paths)
     }
 }
 
 // -------------------------------------------- Private Helper Functions --------------------------------------------
 
+/// Why the walk keeps or drops an entry, before any file-level filter.
+#[derive(Debug, PartialEq, Eq)]
+enum EntryFilter {
+    Kept,
+    /// A symlink left out by the symlink settings.
+    SkippedLink,
+    /// A directory over the `--prune-dir-over` threshold.
+    Pruned,
+    /// Excluded by a pattern or ignore file, or hidden.
+    Excluded,
+}
+
 impl Walker {
-    /// Traverses the directory tree and writes file contents to the output file.
-    fn traverse(&self, run_args: &RunArgs) -> anyhow::Result<()> {
-        let matcher = exclude::ExcludeMatcher::new(&self.root, &self.exclude_patterns)
-            .with_context(|| {
-                format!(
-                    "Failed to create exclusion matcher for root: {}",
-                    self.root.display()
-                )
-            })?;
-
-        // NOTE: Consider parallelizing this traversal for large directories (rayon crate)
-        let walker = WalkDir::new(&self.input).into_iter().filter_entry(|entry| {
-            let excluded = matcher.is_excluded(entry.path());
-            let non_hidden_path =
-                !run_args.skip_hidden || !filter::is_hidden(entry, run_args.verbose);
-            !excluded && non_hidden_path
-        });
-
-        // Determine if this is the first traversal (to decide whether to truncate or append)
-        let is_first_traversal = !self.output.exists();
-
-        // TODO: Consider using BufWriter for better I/O performance on large outputs
-        let mut file = File::options()
-            .write(true)
-            .truncate(is_first_traversal) // Only truncate on first traversal
-            .create(true)
-            .open(&self.output)
-            .map_err(|e| FileSystemError::WriteFailed {
-                path: self.output.clone(),
-                source: e,
-            })
-            .with_context(|| {
-                format!(
-                    "Failed to create or open output file: {}",
-                    self.output.display()
-                )
-            })?;
-
-        // If not the first traversal, move to the end of the file to append
-        if !is_first_traversal {
-            use std::io::Seek;
-            file.seek(std::io::SeekFrom::End(0)).with_context(|| {
-                format!(
-                    "Failed to seek to end of output file: {}",
-                    self.output.display()
-                )
-            })?;
-        }
+    /// Builds the matcher for the excludes, presets and ignore files of the run.
+    fn exclude_matcher(&self, run_args: &RunArgs) -> anyhow::Result<exclude::ExcludeMatcher> {
+        let presets = preset_patterns(run_args);
+        exclude::ExcludeMatcher::with_presets(
+            &self.root,
+            &presets,
+            &run_args.exclude_from,
+            run_args.ignore_missing,
+            &self.exclude_patterns,
+        )
+        .and_then(|matcher| {
+            matcher.with_typed_patterns(&self.root, &run_args.exclude_dir, &run_args.exclude_file)
+        })
+        .map(|matcher| {
+            if run_args.resolve_gitignore_in_parent_dirs {
+                matcher.with_parent_gitignores(&self.root)
+            } else {
+                matcher
+            }
+        })
+        .with_context(|| {
+            format!(
+                "Failed to create exclusion matcher for root: {}",
+                self.root.display()
+            )
+        })
+    }
 
-        let mut file_count = 0;
-        let mut first = is_first_traversal; // Only true for first traversal
+    /// Traverses the directory tree and writes file contents to the output sink.
+    fn traverse(
+        &self,
+        run_args: &RunArgs,
+        sink: &mut OutputSink,
+    ) -> anyhow::Result<TraversalSummary> {
+        let matcher = self.exclude_matcher(run_args)?;
+
+        // Counted inside `filter_entry`, which only gets shared access
+        let skipped_symlinks = Cell::new(0);
+        let pruned_dirs = RefCell::new(Vec::new());
+        let walker = WalkDir::new(&self.input)
+            .follow_links(run_args.follow_symlinks || run_args.follow_symlink_dirs)
+            .into_iter()
+            .filter_entry(|entry| {
+                match classify_entry(entry, run_args, &matcher, run_args.verbose) {
+                    EntryFilter::Kept => true,
+                    EntryFilter::SkippedLink => {
+                        skipped_symlinks.set(skipped_symlinks.get() + 1);
+                        false
+                    }
+                    EntryFilter::Pruned => {
+                        pruned_dirs.borrow_mut().push(entry.path().to_path_buf());
+                        false
+                    }
+                    EntryFilter::Excluded => false,
+                }
+            });
+
+        let mut files = Vec::new();
+        // Files listed in the TOC whose content is left out (`--content-max-depth`)
+        let mut structure_only = HashSet::new();
+        // Files grouped into one block at the end (`--min-significant-size`)
+        let mut small_files = HashSet::new();
+        // Directories entered, shown in the tree even if empty (`--include-empty-dirs-in-tree`)
+        let mut dirs = Vec::new();
+        let mut summary = TraversalSummary::default();
+        let content_match = run_args.content_match.as_ref();
+        let content_exclude = run_args.content_exclude.as_ref();
+        // The TOC, tree and structure-only listings are written before contents are
+        // read, so content filters have to be applied while collecting for them
+        let filter_while_collecting = (content_match.is_some() || content_exclude.is_some())
+            && (run_args.toc
+                || run_args.tree
+                || run_args.no_content
+                || run_args.oneline
+                || run_args.content_max_depth.is_some());
+        for entry in walker {
+            if self.past_deadline() {
+                summary.timed_out = true;
+                break;
+            }
 
-        let tree_emojis = vec!["🌱", "🌿", "🍃", "🌳", "🌲", "🎄"];
+            // A loop only means the linked directory was already entered, so the rest
+            // of the tree is still worth collecting
+            if let Err(e) = &entry
+                && e.loop_ancestor().is_some()
+            {
+                summary
+                    .symlink_loops
+                    .extend(e.path().map(Path::to_path_buf));
+                continue;
+            }
+            // Nor should one link to a deleted target stop the run
+            if let Err(e) = &entry
+                && let Some(path) = dangling_symlink(e)
+            {
+                summary.dangling_symlinks.push(path);
+                continue;
+            }
 
-        for entry in walker {
             let entry = entry
                 .map_err(|e| TraversalError::WalkFailed {
                     path: self.input.clone(),
@@ -136,31 +410,409 @@ impl Walker {
                 continue;
             }
 
-            if entry_path.is_file() {
-                file_count += 1;
+            if !entry_path.is_file() {
+                if run_args.include_empty_dirs_in_tree
+                    && entry.depth() > 0
+                    && entry.file_type().is_dir()
+                {
+                    dirs.push(entry_path.to_path_buf());
+                }
+                continue;
+            }
+
+            // Output saved by an earlier run, wherever it ended up
+            if filter::is_previous_output(entry_path, run_args.verbose) {
+                continue;
+            }
+
+            if !self.excluded_paths.is_empty()
+                && self
+                    .excluded_paths
+                    .contains(&self.display_path(entry_path, run_args))
+            {
+                summary.skipped_shared += 1;
+                continue;
+            }
+
+            if let Some(baseline) = &run_args.baseline {
+                let counterpart = baseline.join(self.baseline_relative(entry_path));
+                match filter::baseline_status(entry_path, &counterpart) {
+                    filter::BaselineStatus::Added => summary.baseline_added += 1,
+                    filter::BaselineStatus::Changed => summary.baseline_changed += 1,
+                    filter::BaselineStatus::Unchanged => {
+                        summary.baseline_unchanged += 1;
+                        continue;
+                    }
+                }
+            }
+
+            if let Some(limit) = size_limit(entry_path, &run_args.max_file_size_by_ext)
+                && entry_size(entry_path) > limit
+            {
+                summary.skipped_oversized += 1;
+                continue;
+            }
+
+            if run_args.exclude_generated && filter::is_generated(entry_path, run_args.verbose) {
+                summary.skipped_generated += 1;
+                continue;
+            }
+
+            if run_args.exclude_minified
+                && filter::is_minified(
+                    entry_path,
+                    run_args.minified_line_length,
+                    run_args.minified_newline_ratio,
+                    run_args.verbose,
+                )
+            {
+                summary.skipped_minified += 1;
+                continue;
+            }
+
+            // Unreadable files are kept, so the read error is reported later
+            if filter_while_collecting
+                && reader::read_file(entry_path).is_ok_and(|content| {
+                    !filter::matches_content(&content, content_match, content_exclude)
+                })
+            {
+                summary.skipped_content += 1;
+                continue;
+            }
+
+            if packs_small_files(run_args)
+                && run_args
+                    .min_significant_size
+                    .is_some_and(|min_size| entry_size(entry_path) < min_size as u64)
+            {
+                small_files.insert(entry_path.to_path_buf());
+            }
+
+            if run_args
+                .content_max_depth
+                .is_some_and(|max_depth| entry.depth() > max_depth)
+            {
+                structure_only.insert(entry_path.to_path_buf());
+            }
+
+            files.push(entry_path.to_path_buf());
+        }
+        summary.skipped_symlinks = skipped_symlinks.get();
+        summary.pruned_dirs = pruned_dirs.into_inner();
+
+        sort::sort_files(
+            &mut files,
+            run_args.sort,
+            run_args.reverse_sort,
+            run_args.normalize_unicode,
+        );
+        if run_args.reverse {
+            files.reverse();
+        }
+        if run_args.context_first {
+            sort::context_first(&mut files, &run_args.context_files);
+        }
+
+        // Capped after sorting, so each directory keeps its first files in output order
+        if let Some(max_per_dir) = run_args.max_files_per_dir {
+            summary.omitted_per_dir = cap_files_per_dir(&mut files, max_per_dir)
+                .into_iter()
+                .map(|(dir, omitted)| (self.display_path(&dir, run_args), omitted))
+                .collect();
+        }
+
+        let file_count = files.len();
+        let mut written = 0;
+        let mut packed: Vec<(PathBuf, String)> = Vec::new();
+        // Files dropped by the content filters once read, as shown in their headers
+        let mut unmatched = HashSet::new();
+        summary.files = files
+            .iter()
+            .map(|path| self.display_path(path, run_args))
+            .collect();
+        if run_args.dry_run {
+            summary.file_sizes = files.iter().map(|path| entry_size(path)).collect();
+        }
+        if !run_args.mask_paths.is_empty() {
+            summary.masked_paths = files
+                .iter()
+                .filter(|path| {
+                    mask_path(&self.unmasked_path(path, run_args), &run_args.mask_paths).is_some()
+                })
+                .count();
+        }
+
+        // Stripped from every shown path (`--collapse-paths`); empty when not collapsing.
+        // JSON and DOT have nowhere to note it, so they keep full paths
+        let collapsed = if run_args.collapse_paths
+            && matches!(run_args.format, OutputFormat::Text | OutputFormat::Markdown)
+        {
+            common_dir_prefix(&summary.files)
+        } else {
+            PathBuf::new()
+        };
+        if !collapsed.as_os_str().is_empty() {
+            sink.write_path_prefix(&collapsed)?;
+        }
+
+        if (run_args.tree || run_args.no_content) && file_count > 0 {
+            let shown: Vec<&Path> = summary
+                .files
+                .iter()
+                .map(|path| path.strip_prefix(&collapsed).unwrap_or(path))
+                .collect();
+            // Directories outside the collapsed prefix hold included files anyway
+            let shown_dirs: Vec<PathBuf> = dirs
+                .iter()
+                .map(|dir| self.display_path(dir, run_args))
+                .filter_map(|dir| dir.strip_prefix(&collapsed).ok().map(Path::to_path_buf))
+                .filter(|dir| !dir.as_os_str().is_empty())
+                .collect();
+            let shown_dirs: Vec<&Path> = shown_dirs.iter().map(PathBuf::as_path).collect();
+            let tree = tree::render(&shown, &shown_dirs, run_args.tree_depth);
+            // Without contents the tree is the whole output, so it is what --stats counts
+            if run_args.no_content {
+                summary.content_stats.add(tree.trim_end());
+            }
+            sink.write_tree(&tree)?;
+        }
+
+        // The full file list is known up front, so the TOC can precede the bodies
+        if run_args.toc && file_count > 0 {
+            self.write_toc(sink, &files, &collapsed, run_args)?;
+        }
+
+        if run_args.oneline && file_count > 0 {
+            self.write_oneline(
+                sink,
+                &files,
+                &collapsed,
+                run_args,
+                &mut summary.content_stats,
+            )?;
+        }
+
+        let files: Vec<PathBuf> = files
+            .into_iter()
+            .filter(|path| {
+                !run_args.no_content && !run_args.oneline && !structure_only.contains(path)
+            })
+            .collect();
+
+        let replacements = transform::compile_replacements(
+            &run_args.content_replace,
+            run_args.content_replace_regex,
+        )
+        .with_context(|| "Invalid --content-replace pattern")?;
+        let mut editorconfig = run_args
+            .respect_editorconfig
+            .then(editorconfig::EditorConfig::default);
+        // Files before this index reached the sink (or were left out) before any timeout
+        let mut reached = 0;
+        // Contents are read in parallel but arrive here in traversal order
+        let read_result = reader::read_ordered(
+            &files,
+            run_args.read_buffer,
+            reader::read_file,
+            |index, content| {
+                if self.past_deadline() {
+                    return Err(TraversalError::DeadlineReached.into());
+                }
+                reached = index + 1;
+
+                let entry_path = &files[index];
+                // One unreadable file should not cost the rest of the output
+                let mut content = match content {
+                    Ok(content) => content,
+                    Err(e) => {
+                        summary.read_errors.push((entry_path.clone(), e));
+                        return Ok(());
+                    }
+                };
+
+                if !filter_while_collecting
+                    && !filter::matches_content(&content, content_match, content_exclude)
+                {
+                    summary.skipped_content += 1;
+                    unmatched.insert(self.display_path(entry_path, run_args));
+                    return Ok(());
+                }
+
+                // Counted on the file as it is on disk, before any transformation
+                if run_args.sloc {
+                    sloc::add(&mut summary.sloc, entry_path, &content);
+                }
+                if run_args.language_chart {
+                    *summary
+                        .language_bytes
+                        .entry(lang::language_name(entry_path))
+                        .or_default() += content.len();
+                }
+
+                if run_args.context_only
+                    && let Some(outline) = structure::extract(entry_path, &content)
+                {
+                    content = outline;
+                }
+
+                // A changed file's diff replaces its content, and its outline
+                if let Some(rev) = &run_args.as_diff
+                    && let Some(diff) = git::diff(entry_path, rev)
+                {
+                    content = diff;
+                }
+
+                let (replaced, count) = transform::replace_content(&content, &replacements);
+                if let Cow::Owned(replaced) = replaced {
+                    content = replaced;
+                    summary.content_replacements += count;
+                }
+
+                // An explicit --tab-width wins over the file's own settings
+                let properties = editorconfig
+                    .as_mut()
+                    .map(|editorconfig| editorconfig.properties(entry_path))
+                    .unwrap_or_default();
+                let tab_width = match run_args.tab_width {
+                    0 => properties.expand_tabs_width().unwrap_or(0),
+                    width => width,
+                };
+                if let Cow::Owned(expanded) = transform::expand_tabs(&content, tab_width) {
+                    content = expanded;
+                }
+                if let Some(ending) = properties.end_of_line
+                    && let Cow::Owned(converted) =
+                        transform::convert_line_endings(&content, ending.as_str())
+                {
+                    content = converted;
+                }
+
+                if run_args.collapse_whitespace_runs {
+                    let keep_indent = lang::is_indentation_sensitive(entry_path);
+                    if let Cow::Owned(squeezed) =
+                        transform::collapse_whitespace_runs(&content, keep_indent)
+                    {
+                        summary.whitespace_bytes_saved += content.len() - squeezed.len();
+                        if keep_indent {
+                            summary
+                                .whitespace_collapsed_sensitive
+                                .push(self.display_path(entry_path, run_args));
+                        }
+                        content = squeezed;
+                    }
+                }
+
+                if run_args.escape_control
+                    && let Cow::Owned(escaped) = transform::escape_control(&content)
+                {
+                    content = escaped;
+                }
 
-                // Progress indicator (only in verbose mode and not fast mode)
-                if run_args.verbose && !run_args.fast_mode && file_count % 5 == 0 {
-                    if let Some(msg) = animations::progress_counter(&tree_emojis, file_count, 5) {
-                        print!("\r{msg}");
-                        stdout().flush().with_context(|| "Failed to flush stdout")?;
+                if let Some(max_chars) = run_args.max_line_length {
+                    let (shortened, truncated) = transform::truncate_long_lines(
+                        &content,
+                        max_chars,
+                        &run_args.truncate_marker,
+                    );
+                    if truncated > 0 {
+                        content = shortened.into_owned();
+                        summary.truncated_lines += truncated;
+                        summary.files_with_truncated_lines += 1;
                     }
                 }
 
-                self.write_file_content(&mut file, entry_path, &mut first)
+                if let Some(width) = run_args.wrap
+                    && let Cow::Owned(wrapped) = transform::wrap_long_lines(&content, width)
+                {
+                    content = wrapped;
+                }
+
+                if run_args.git_context
+                    && let Some(annotated) = git::with_context_line(entry_path, &content)
+                {
+                    content = annotated;
+                }
+
+                if small_files.contains(entry_path) && pack::can_pack(&content) {
+                    let body = trim_body(&content, run_args);
+                    summary.content_stats.add(body);
+                    packed.push((
+                        self.shown_path(entry_path, &collapsed, run_args),
+                        body.to_string(),
+                    ));
+                } else {
+                    self.write_file_content(
+                        sink,
+                        entry_path,
+                        &content,
+                        &collapsed,
+                        run_args,
+                        &mut summary.content_stats,
+                    )
                     .with_context(|| {
                         format!("Failed to write content for file: {}", entry_path.display())
                     })?;
+                }
+                written += 1;
+
+                if let Some(progress) = &self.progress {
+                    progress(&ProgressEvent {
+                        files_processed: written,
+                        bytes_written: sink.bytes_written(),
+                    });
+                }
+
+                Ok(())
+            },
+        );
+        match read_result {
+            Err(e) if matches!(e.downcast_ref(), Some(TraversalError::DeadlineReached)) => {
+                summary.timed_out = true;
+                // Only what was written counts; the rest never made it into the output
+                let unread: HashSet<PathBuf> = files[reached..]
+                    .iter()
+                    .map(|path| self.display_path(path, run_args))
+                    .collect();
+                summary.files.retain(|path| !unread.contains(path));
             }
+            other => other?,
+        }
+
+        if !summary.read_errors.is_empty() || !unmatched.is_empty() {
+            let left_out: HashSet<PathBuf> = summary
+                .read_errors
+                .iter()
+                .map(|(path, _)| self.display_path(path, run_args))
+                .chain(unmatched)
+                .collect();
+            summary.files.retain(|path| !left_out.contains(path));
+        }
+
+        if !packed.is_empty() {
+            let entries: Vec<(&Path, &str)> = packed
+                .iter()
+                .map(|(path, body)| (path.as_path(), body.as_str()))
+                .collect();
+            let title = Path::new(pack::SMALL_FILES_TITLE);
+            sink.write_file(
+                title,
+                &format_header(title, run_args),
+                &pack::render(&entries),
+            )
+            .with_context(|| "Failed to write the small files block")?;
         }
 
-        // Check if any files were found
-        if file_count == 0 {
+        // Check if any files were found (a timeout is reported instead)
+        if file_count == 0 && !summary.timed_out {
             return Err(TraversalError::NoFilesFound(self.input.clone()).into());
         }
 
+        if run_args.verbose && summary.masked_paths > 0 {
+            statusln!("{}", Messages::masked_paths(summary.masked_paths));
+        }
+
         if run_args.verbose {
-            println!(
+            statusln!(
                 "\r{} Collected {} files from {}! {}",
                 "✨".green(),
                 file_count,
@@ -169,92 +821,350 @@ impl Walker {
             );
         }
 
-        Ok(())
+        Ok(summary)
+    }
+
+    /// Writes the table of contents for `files`.
+    fn write_toc(
+        &self,
+        sink: &mut OutputSink,
+        files: &[PathBuf],
+        collapsed: &Path,
+        run_args: &RunArgs,
+    ) -> anyhow::Result<()> {
+        let displayed: Vec<(PathBuf, u64)> = files
+            .iter()
+            .map(|path| (self.shown_path(path, collapsed, run_args), entry_size(path)))
+            .collect();
+        let entries: Vec<(&Path, u64)> = displayed
+            .iter()
+            .map(|(path, size)| (path.as_path(), *size))
+            .collect();
+
+        sink.write_toc(&entries)
+    }
+
+    /// Writes the `path: first line` listing for `files` (`--oneline`), counting it in
+    /// `stats` as it stands in for the contents.
+    fn write_oneline(
+        &self,
+        sink: &mut OutputSink,
+        files: &[PathBuf],
+        collapsed: &Path,
+        run_args: &RunArgs,
+        stats: &mut ContentStats,
+    ) -> anyhow::Result<()> {
+        let previews: Vec<(PathBuf, Option<String>)> = files
+            .iter()
+            .map(|path| {
+                (
+                    self.shown_path(path, collapsed, run_args),
+                    oneline::first_line(path),
+                )
+            })
+            .collect();
+        let entries: Vec<(&Path, Option<&str>)> = previews
+            .iter()
+            .map(|(path, preview)| (path.as_path(), preview.as_deref()))
+            .collect();
+
+        let listing = oneline::render(&entries, None);
+        stats.add(listing.trim_end());
+        sink.write_tree(&listing)
+    }
+
+    /// Returns `true` once the `--timeout` deadline has passed.
+    fn past_deadline(&self) -> bool {
+        self.deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
     }
 
-    /// Writes a single file's content to the output file with proper formatting.
+    /// Returns the path shown for a file in headers and the table of contents.
+    ///
+    /// Paths are relative to the root, or to the header root when one is set. With
+    /// `--symlink-headers target`, files reached through a symlink are shown at their
+    /// resolved location instead; targets outside the root are shown as absolute paths.
+    /// With `--normalize-unicode`, the path is shown in NFC form, and `--mask-paths`
+    /// substitutions are applied last.
+    fn display_path(&self, entry_path: &Path, run_args: &RunArgs) -> PathBuf {
+        let displayed = self.unmasked_path(entry_path, run_args);
+        mask_path(&displayed, &run_args.mask_paths).unwrap_or(displayed)
+    }
+
+    /// The path `entry_path` is compared under in the `--baseline` directory: relative
+    /// to the root, or to the input for an input outside the root.
+    fn baseline_relative<'a>(&self, entry_path: &'a Path) -> &'a Path {
+        if let Ok(relative) = entry_path.strip_prefix(&self.root) {
+            return relative;
+        }
+        // A file input is matched by its name
+        let base = match self.input.parent() {
+            Some(parent) if self.input.is_file() => parent,
+            _ => &self.input,
+        };
+        entry_path.strip_prefix(base).unwrap_or(entry_path)
+    }
+
+    /// The display path before `--mask-paths` is applied.
+    fn unmasked_path(&self, entry_path: &Path, run_args: &RunArgs) -> PathBuf {
+        let base = self.header_root.as_deref().unwrap_or(&self.root);
+        let displayed = if run_args.follow_symlinks
+            && run_args.symlink_headers == SymlinkHeaders::Target
+            && let (Ok(target), Ok(root)) = (entry_path.canonicalize(), base.canonicalize())
+        {
+            target
+                .strip_prefix(&root)
+                .map_or_else(|_| target.clone(), Path::to_path_buf)
+        } else if let Some(header_root) = &self.header_root {
+            // The header root is absolute, while inputs may be given relative
+            let absolute = std::path::absolute(entry_path).unwrap_or_else(|_| entry_path.into());
+            absolute
+                .strip_prefix(header_root)
+                .map_or_else(|_| absolute.clone(), Path::to_path_buf)
+        } else {
+            entry_path
+                .strip_prefix(&self.root)
+                .unwrap_or(entry_path)
+                .to_path_buf()
+        };
+
+        if run_args.normalize_unicode {
+            utils::nfc_path(&displayed)
+        } else {
+            displayed
+        }
+    }
+
+    /// Returns the display path with the `--collapse-paths` prefix removed.
+    fn shown_path(&self, entry_path: &Path, collapsed: &Path, run_args: &RunArgs) -> PathBuf {
+        let displayed = self.display_path(entry_path, run_args);
+        displayed
+            .strip_prefix(collapsed)
+            .map_or_else(|_| displayed.clone(), Path::to_path_buf)
+    }
+
+    /// Writes a single file's content to the output sink with proper formatting, adding
+    /// the body to `stats`.
     fn write_file_content(
         &self,
-        output_file: &mut File,
+        sink: &mut OutputSink,
         entry_path: &Path,
-        first: &mut bool,
+        content: &str,
+        collapsed: &Path,
+        run_args: &RunArgs,
+        stats: &mut ContentStats,
     ) -> anyhow::Result<()> {
-        let relative_path = entry_path.strip_prefix(&self.root).unwrap_or(entry_path);
+        let relative_path = self.shown_path(entry_path, collapsed, run_args);
+        let header = format_header(&relative_path, run_args);
+        let body = trim_body(content, run_args);
 
-        if !*first {
-            writeln!(output_file)
-                .map_err(|e| FileSystemError::WriteFailed {
-                    path: self.output.clone(),
-                    source: e,
-                })
-                .with_context(|| {
-                    format!(
-                        "Failed to write newline separator to: {}",
-                        self.output.display()
-                    )
-                })?;
+        sink.write_file(&relative_path, &header, body)?;
+        stats.add(body);
+        Ok(())
+    }
+}
+
+/// Trims a file's content for output.
+///
+/// Trailing whitespace is trimmed unless the user asked for verbatim content.
+fn trim_body<'a>(content: &'a str, run_args: &RunArgs) -> &'a str {
+    if run_args.no_trim {
+        // Only the final line break goes, CRLF included; the renderer adds its own
+        content
+            .strip_suffix("\r\n")
+            .or_else(|| content.strip_suffix('\n'))
+            .unwrap_or(content)
+    } else {
+        content.trim_end()
+    }
+}
+
+/// Applies each `--mask-paths` substitution in turn, returning `None` if the path is
+/// left unchanged.
+fn mask_path(path: &Path, masks: &[PathMask]) -> Option<PathBuf> {
+    let original = path.to_string_lossy();
+    let mut masked = original.to_string();
+    for mask in masks {
+        if let Cow::Owned(replaced) = mask.pattern.replace_all(&masked, &mask.replacement) {
+            masked = replaced;
         }
+    }
+    (masked != original).then(|| PathBuf::from(masked))
+}
 
-        // Write the header: ==> relative/path
-        writeln!(output_file, "==> {}", relative_path.display())
-            .map_err(|e| FileSystemError::WriteFailed {
-                path: self.output.clone(),
-                source: e,
-            })
-            .with_context(|| {
-                format!(
-                    "Failed to write path header for: {}",
-                    relative_path.display()
-                )
-            })?;
-
-        // TODO: Switch to buffered streaming (BufReader::read_line or copy) for large files
-        // Read and write content
-        let content = fs::read_to_string(entry_path)
-            .map_err(|e| FileSystemError::ReadFailed {
-                path: entry_path.to_path_buf(),
-                source: e,
-            })
-            .with_context(|| {
-                format!(
-                    "Failed to read file contents from: {}",
-                    entry_path.display()
-                )
-            })?;
+/// Keeps at most `max_per_dir` files from each directory, in order.
+///
+/// Returns each directory that lost files, with how many, sorted by directory.
+fn cap_files_per_dir(files: &mut Vec<PathBuf>, max_per_dir: usize) -> Vec<(PathBuf, usize)> {
+    let mut kept: HashMap<PathBuf, usize> = HashMap::new();
+    let mut omitted: BTreeMap<PathBuf, usize> = BTreeMap::new();
+
+    files.retain(|path| {
+        let dir = path.parent().unwrap_or(Path::new("")).to_path_buf();
+        let count = kept.entry(dir.clone()).or_default();
+        if *count < max_per_dir {
+            *count += 1;
+            true
+        } else {
+            *omitted.entry(dir).or_default() += 1;
+            false
+        }
+    });
 
-        output_file
-            .write_all(content.trim_end().as_bytes())
-            .map_err(|e| FileSystemError::WriteFailed {
-                path: self.output.clone(),
-                source: e,
-            })
-            .with_context(|| {
-                format!(
-                    "Failed to write file content to output: {}",
-                    self.output.display()
-                )
-            })?;
+    omitted.into_iter().collect()
+}
 
-        // Add newline between files
-        writeln!(output_file)
-            .map_err(|e| FileSystemError::WriteFailed {
-                path: self.output.clone(),
-                source: e,
-            })
-            .with_context(|| "Failed to write trailing newline to output file")?;
+/// Longest directory shared by every path, e.g. `src/main` for `src/main/a.rs` and
+/// `src/main/b/c.rs`; empty when the paths share none.
+fn common_dir_prefix(paths: &[PathBuf]) -> PathBuf {
+    let mut dirs = paths
+        .iter()
+        .map(|path| path.parent().unwrap_or(Path::new("")));
+    let Some(first) = dirs.next() else {
+        return PathBuf::new();
+    };
+
+    let mut common: Vec<_> = first.components().collect();
+    for dir in dirs {
+        let shared = common
+            .iter()
+            .zip(dir.components())
+            .take_while(|(a, b)| *a == b)
+            .count();
+        common.truncate(shared);
+    }
+    common.iter().collect()
+}
 
-        *first = false;
+/// Size of a file in bytes, or 0 if it cannot be read.
+fn entry_size(path: &Path) -> u64 {
+    std::fs::metadata(path).map_or(0, |meta| meta.len())
+}
 
-        Ok(())
+/// Size limit for `path` from the `--max-file-size-by-ext` pairs; the last match wins.
+fn size_limit(path: &Path, limits: &[(String, u64)]) -> Option<u64> {
+    let extension = path.extension()?.to_str()?;
+    limits
+        .iter()
+        .rev()
+        .find(|(wanted, _)| wanted.eq_ignore_ascii_case(extension))
+        .map(|(_, limit)| *limit)
+}
+
+/// Returns `true` if small files are grouped (`--min-significant-size`): only when
+/// both the output and any clipboard copy are plain text.
+fn packs_small_files(run_args: &RunArgs) -> bool {
+    let mirror = run_args.clipboard_format.filter(|_| run_args.clipboard);
+    run_args.format == OutputFormat::Text
+        && mirror.is_none_or(|format| format == OutputFormat::Text)
+}
+
+/// Decides whether the walk descends into or yields `entry`.
+fn classify_entry(
+    entry: &walkdir::DirEntry,
+    run_args: &RunArgs,
+    matcher: &exclude::ExcludeMatcher,
+    verbose: bool,
+) -> EntryFilter {
+    // Links are followed, so a file link's type is that of the file
+    let skipped_link = if run_args.follow_symlink_dirs {
+        entry.depth() > 0 && entry.path_is_symlink() && !entry.file_type().is_dir()
+    } else {
+        run_args.exclude_symlinks && filter::is_symlink(entry, verbose)
+    };
+    if skipped_link {
+        return EntryFilter::SkippedLink;
+    }
+    let excluded = matcher.is_excluded(entry.path());
+    let non_hidden_path = !run_args.skips_hidden() || !filter::is_hidden(entry, verbose);
+    if excluded || !non_hidden_path {
+        return EntryFilter::Excluded;
+    }
+    // The input itself is never pruned; it was asked for by name
+    if run_args.exclude_large_dirs && is_large_dir(entry, run_args.prune_dir_over) {
+        return EntryFilter::Pruned;
     }
+    EntryFilter::Kept
+}
+
+/// Returns the path of a followed symlink whose target is missing, if that is what
+/// the walk error is about.
+fn dangling_symlink(error: &walkdir::Error) -> Option<PathBuf> {
+    let path = error.path()?;
+    let not_found = error
+        .io_error()
+        .is_some_and(|e| e.kind() == std::io::ErrorKind::NotFound);
+    let is_link = std::fs::symlink_metadata(path).is_ok_and(|m| m.file_type().is_symlink());
+    (not_found && is_link).then(|| path.to_path_buf())
+}
+
+/// Returns `true` for a directory below the input with more than `threshold` direct
+/// entries (`--exclude-large-dirs`).
+///
+/// Counting stops one past the threshold, so a huge directory costs no more than a
+/// small one. Unreadable directories are left to the walk, which reports them.
+fn is_large_dir(entry: &walkdir::DirEntry, threshold: usize) -> bool {
+    if entry.depth() == 0 || !entry.file_type().is_dir() {
+        return false;
+    }
+    std::fs::read_dir(entry.path())
+        .is_ok_and(|entries| entries.take(threshold + 1).count() > threshold)
+}
+
+/// Builds the `==> path` header line, wrapped in any requested prefix and suffix.
+fn format_header(relative_path: &Path, run_args: &RunArgs) -> String {
+    let (prefix, suffix) = if run_args.comment_headers {
+        lang::comment_style(relative_path).map_or(("", ""), |style| (style.prefix, style.suffix))
+    } else {
+        (
+            run_args.header_prefix.as_deref().unwrap_or(""),
+            run_args.header_suffix.as_deref().unwrap_or(""),
+        )
+    };
+
+    format!("{prefix}==> {}{suffix}", relative_path.display())
+}
+
+/// Collects the built-in exclusion presets enabled by the run arguments.
+fn preset_patterns(run_args: &RunArgs) -> Vec<&'static str> {
+    let mut patterns = Vec::new();
+    if run_args.exclude_test_files {
+        patterns.extend_from_slice(exclude::presets::TEST_FILES);
+    }
+    if run_args.exclude_lockfiles {
+        patterns.extend_from_slice(exclude::presets::LOCKFILES);
+    }
+    patterns
 }
 
 #[cfg(test)]
 mod walker_tests {
     use super::*;
+    use crate::commands::args::ContentReplace;
+    use regex::Regex;
     use std::fs;
     use tempfile::TempDir;
 
+    /// Arguments for a quiet run over `root` into `output`, hidden files included.
+    fn args_for(root: &Path, output: &Path) -> RunArgs {
+        RunArgs {
+            input_paths: vec![root.to_path_buf()],
+            output_path: Some(output.to_path_buf()),
+            root: Some(root.to_path_buf()),
+            skip_hidden: false,
+            fast_mode: true,
+            ..RunArgs::default()
+        }
+    }
+
+    /// Runs a single traversal into a fresh sink on the walker's output file.
+    fn traverse_to_file(walker: &Walker, args: &RunArgs) -> anyhow::Result<TraversalSummary> {
+        let mut sink = OutputSink::create(&walker.output)?;
+        let summary = walker.traverse(args, &mut sink);
+        sink.finish()?;
+        summary
+    }
+
     #[test]
     fn test_walker_creation() {
         let temp_dir = TempDir::new().unwrap();
@@ -264,7 +1174,7 @@ mod walker_tests {
             temp_dir.path(),
             temp_dir.path(),
             &output,
-            &vec!["node_modules".to_string()],
+            &["node_modules".to_string()],
         );
 
         assert_eq!(walker.root, temp_dir.path());
@@ -282,7 +1192,7 @@ mod walker_tests {
         let test_file = temp_dir.path().join("test.txt");
         fs::write(&test_file, "test content")?;
 
-        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output, &vec![]);
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output, &[]);
 
         let args = RunArgs {
             input_paths: vec![temp_dir.path().to_path_buf()],
@@ -297,9 +1207,10 @@ mod walker_tests {
             skip_hidden: false,
             raw: true,
             fast_mode: true,
+            ..RunArgs::default()
         };
 
-        walker.traverse(&args)?;
+        traverse_to_file(&walker, &args)?;
 
         assert!(output.exists());
         Ok(())
@@ -321,7 +1232,7 @@ mod walker_tests {
         let output_path = temp_dir.path().join("output.txt");
 
         // Run traversal
-        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output_path, &vec![]);
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output_path, &[]);
 
         let args = RunArgs {
             input_paths: vec![temp_dir.path().to_path_buf()],
@@ -336,9 +1247,10 @@ mod walker_tests {
             skip_hidden: false,
             raw: true,
             fast_mode: true,
+            ..RunArgs::default()
         };
 
-        walker.traverse(&args)?;
+        traverse_to_file(&walker, &args)?;
 
         // Read and verify output
         let output_content = fs::read_to_string(&output_path)?;
@@ -356,49 +1268,1459 @@ mod walker_tests {
     }
 
     #[test]
-    fn test_process_dir_validates_path() {
-        let temp_dir = TempDir::new().unwrap();
+    fn test_no_trim_preserves_trailing_whitespace() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
         let output = temp_dir.path().join("output.txt");
 
-        let walker = Walker::new(
-            temp_dir.path(),
-            &PathBuf::from("/nonexistent/path"),
-            &output,
-            &vec![],
-        );
+        // Two trailing spaces form a Markdown hard break
+        fs::write(temp_dir.path().join("notes.md"), "line one  \nline two  \n")?;
 
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output, &[]);
         let args = RunArgs {
-            input_paths: vec![PathBuf::from("/nonexistent/path")],
-            output_path: Some(output),
-            root: Some(temp_dir.path().to_path_buf()),
-            exclude: vec![],
-            clipboard: false,
-            stats: false,
-            editor: false,
-            delete: false,
-            verbose: false,
-            skip_hidden: true,
-            raw: true,
-            fast_mode: true,
+            no_trim: true,
+            ..args_for(temp_dir.path(), &output)
         };
 
-        let result = walker.process_dir(&args);
-        assert!(result.is_err());
+        traverse_to_file(&walker, &args)?;
 
-        let error_msg = format!("{:?}", result.unwrap_err());
-        assert!(error_msg.contains("does not exist") || error_msg.contains("validation failed"));
+        let output_content = fs::read_to_string(&output)?;
+        assert_eq!(output_content, "==> notes.md\nline one  \nline two  \n");
+
+        Ok(())
     }
 
     #[test]
-    fn test_no_files_found_error() -> anyhow::Result<()> {
-        let temp_dir = TempDir::new()?;
-        let output = temp_dir.path().join("output.txt");
+    fn test_no_trim_drops_a_final_crlf() {
+        let args = RunArgs {
+            no_trim: true,
+            ..RunArgs::default()
+        };
+
+        assert_eq!(
+            trim_body("line one  \r\nline two  \r\n", &args),
+            "line one  \r\nline two  "
+        );
+        assert_eq!(trim_body("line one  \n", &args), "line one  ");
+        assert_eq!(trim_body("no break  ", &args), "no break  ");
+    }
+
+    #[test]
+    fn test_default_trims_trailing_whitespace() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("output.txt");
+
+        fs::write(
+            temp_dir.path().join("notes.md"),
+            "line one  \nline two  \n\n",
+        )?;
+
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output, &[]);
+        let args = args_for(temp_dir.path(), &output);
+
+        traverse_to_file(&walker, &args)?;
+
+        let output_content = fs::read_to_string(&output)?;
+        assert_eq!(output_content, "==> notes.md\nline one  \nline two\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_content_stats_count_the_tree() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("output.txt");
+
+        fs::write(temp_dir.path().join("a.txt"), "one two\nthree\n")?;
+
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output, &[]);
+        let args = RunArgs {
+            no_content: true,
+            stats: true,
+            ..args_for(temp_dir.path(), &output)
+        };
+
+        let summary = traverse_to_file(&walker, &args)?;
+
+        let tree = fs::read_to_string(&output)?;
+        let mut expected = ContentStats::default();
+        expected.add(tree.trim_end());
+        assert!(expected.lines > 0);
+        assert_eq!(summary.content_stats, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_content_stats_count_bodies_only() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("output.txt");
+
+        fs::write(temp_dir.path().join("a.txt"), "one two\nthree\n")?;
+        fs::write(temp_dir.path().join("b.txt"), "four")?;
+
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output, &[]);
+        let args = args_for(temp_dir.path(), &output);
+
+        let summary = traverse_to_file(&walker, &args)?;
+
+        assert_eq!(
+            summary.content_stats,
+            ContentStats {
+                lines: 3,
+                words: 4,
+                chars: 17,
+                bytes: 17,
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_exclude_test_files_flag() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("output.txt");
+
+        fs::write(temp_dir.path().join("lib.rs"), "pub fn lib() {}")?;
+        fs::write(temp_dir.path().join("lib_test.rs"), "fn test_lib() {}")?;
+        fs::create_dir(temp_dir.path().join("tests"))?;
+        fs::write(temp_dir.path().join("tests").join("it.rs"), "fn it() {}")?;
+
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output, &[]);
+        let args = RunArgs {
+            exclude_test_files: true,
+            ..args_for(temp_dir.path(), &output)
+        };
+
+        traverse_to_file(&walker, &args)?;
+
+        let output_content = fs::read_to_string(&output)?;
+        assert!(output_content.contains("==> lib.rs"));
+        assert!(!output_content.contains("lib_test.rs"));
+        assert!(!output_content.contains("it.rs"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_exclude_test_files_flag_with_override() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("output.txt");
+
+        fs::write(temp_dir.path().join("lib_test.rs"), "fn test_lib() {}")?;
+        fs::write(temp_dir.path().join("fixtures_test.rs"), "fn fixtures() {}")?;
+        fs::create_dir(temp_dir.path().join("tests"))?;
+        fs::write(temp_dir.path().join("tests").join("it.rs"), "fn it() {}")?;
+
+        let patterns = vec!["!fixtures_test.rs".to_string(), "!tests/".to_string()];
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output, &patterns);
+        let args = RunArgs {
+            exclude_test_files: true,
+            ..args_for(temp_dir.path(), &output)
+        };
+
+        traverse_to_file(&walker, &args)?;
+
+        let output_content = fs::read_to_string(&output)?;
+        assert!(output_content.contains("==> fixtures_test.rs"));
+        assert!(output_content.contains("==> tests/it.rs"));
+        assert!(!output_content.contains("lib_test.rs"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_exclude_lockfiles_flag_with_override() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("output.txt");
+
+        fs::write(temp_dir.path().join("main.rs"), "fn main() {}")?;
+        fs::write(temp_dir.path().join("Cargo.lock"), "# lock")?;
+        fs::create_dir(temp_dir.path().join("web"))?;
+        fs::write(temp_dir.path().join("web").join("package-lock.json"), "{}")?;
+        fs::write(temp_dir.path().join("web").join("yarn.lock"), "# yarn")?;
+
+        let patterns = vec!["!yarn.lock".to_string()];
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output, &patterns);
+        let args = RunArgs {
+            exclude_lockfiles: true,
+            ..args_for(temp_dir.path(), &output)
+        };
+
+        traverse_to_file(&walker, &args)?;
+
+        let output_content = fs::read_to_string(&output)?;
+        assert!(output_content.contains("==> main.rs"));
+        assert!(!output_content.contains("Cargo.lock"));
+        assert!(!output_content.contains("package-lock.json"));
+        assert!(output_content.contains("yarn.lock"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_progress_callback_reports_each_file() -> anyhow::Result<()> {
+        use std::sync::{Arc, Mutex};
+
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("output.txt");
+
+        fs::write(temp_dir.path().join("a.txt"), "alpha")?;
+        fs::write(temp_dir.path().join("b.txt"), "beta")?;
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&events);
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output, &[]).with_progress(
+            move |event| {
+                recorded
+                    .lock()
+                    .unwrap()
+                    .push((event.files_processed, event.bytes_written));
+            },
+        );
+        let args = args_for(temp_dir.path(), &output);
+
+        traverse_to_file(&walker, &args)?;
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].0, 1);
+        assert_eq!(events[1].0, 2);
+        assert!(events[0].1 < events[1].1);
+        assert_eq!(events[1].1, fs::metadata(&output)?.len());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_header_prefix_and_suffix() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("output.txt");
+        fs::write(temp_dir.path().join("style.css"), "body {}")?;
+
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output, &[]);
+        let args = RunArgs {
+            header_prefix: Some("/* ".to_string()),
+            header_suffix: Some(" */".to_string()),
+            ..args_for(temp_dir.path(), &output)
+        };
+
+        traverse_to_file(&walker, &args)?;
+
+        let output_content = fs::read_to_string(&output)?;
+        assert_eq!(output_content, "/* ==> style.css */\nbody {}\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_comment_headers_per_language() {
+        let args = RunArgs {
+            comment_headers: true,
+            ..RunArgs::default()
+        };
+
+        assert_eq!(
+            format_header(Path::new("src/main.rs"), &args),
+            "// ==> src/main.rs"
+        );
+        assert_eq!(format_header(Path::new("run.py"), &args), "# ==> run.py");
+        assert_eq!(
+            format_header(Path::new("index.html"), &args),
+            "<!-- ==> index.html -->"
+        );
+        assert_eq!(
+            format_header(Path::new("data.json"), &args),
+            "==> data.json"
+        );
+    }
+
+    #[test]
+    fn test_collapse_paths_strips_shared_directory() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("output.txt");
+        let deep = temp_dir.path().join("src/main/java");
+        fs::create_dir_all(deep.join("util"))?;
+        fs::write(deep.join("App.java"), "class App {}")?;
+        fs::write(deep.join("util/Io.java"), "class Io {}")?;
+
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output, &[]);
+        let args = RunArgs {
+            sort: sort::SortOrder::Path,
+            collapse_paths: true,
+            ..args_for(temp_dir.path(), &output)
+        };
+
+        let summary = traverse_to_file(&walker, &args)?;
+
+        assert_eq!(
+            fs::read_to_string(&output)?,
+            "Path prefix: src/main/java/\n\n==> App.java\nclass App {}\n\n==> util/Io.java\nclass Io {}\n"
+        );
+        // Everything else still sees full paths
+        assert_eq!(summary.files[0], PathBuf::from("src/main/java/App.java"));
+
+        // Nothing shared: headers stay as they are
+        fs::write(temp_dir.path().join("README.md"), "hi")?;
+        traverse_to_file(&walker, &args)?;
+        let output_content = fs::read_to_string(&output)?;
+        assert!(!output_content.contains("Path prefix"));
+        assert!(output_content.contains("==> src/main/java/App.java"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_common_dir_prefix() {
+        let paths = [PathBuf::from("a/b/c.rs"), PathBuf::from("a/b/d/e.rs")];
+        assert_eq!(common_dir_prefix(&paths), PathBuf::from("a/b"));
+
+        let paths = [PathBuf::from("a/b.rs"), PathBuf::from("c.rs")];
+        assert_eq!(common_dir_prefix(&paths), PathBuf::new());
+        assert_eq!(common_dir_prefix(&[]), PathBuf::new());
+    }
+
+    #[test]
+    fn test_toc_precedes_file_bodies() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("output.txt");
+
+        fs::write(temp_dir.path().join("a.txt"), "alpha")?;
+        fs::write(temp_dir.path().join("b.txt"), "beta")?;
+
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output, &[]);
+        let args = RunArgs {
+            sort: sort::SortOrder::Path,
+            toc: true,
+            ..args_for(temp_dir.path(), &output)
+        };
+
+        traverse_to_file(&walker, &args)?;
+
+        let output_content = fs::read_to_string(&output)?;
+        assert_eq!(
+            output_content,
+            "Table of Contents (2 files)\n1. a.txt (5 B)\n2. b.txt (4 B)\n\n==> a.txt\nalpha\n\n==> b.txt\nbeta\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_content_filters_select_files() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("output.txt");
+
+        fs::write(temp_dir.path().join("a.rs"), "fn parse_args() {}")?;
+        fs::write(temp_dir.path().join("b.rs"), "fn parse_tests() {} // TODO")?;
+        fs::write(temp_dir.path().join("c.rs"), "fn main() {}")?;
+
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output, &[]);
+        let content_match = Regex::new(r"fn parse_\w+")?;
+        let content_exclude = Regex::new("TODO")?;
+        for toc in [false, true] {
+            let args = RunArgs {
+                sort: sort::SortOrder::Path,
+                toc,
+                content_match: Some(content_match.clone()),
+                content_exclude: Some(content_exclude.clone()),
+                ..args_for(temp_dir.path(), &output)
+            };
+
+            let summary = traverse_to_file(&walker, &args)?;
+
+            let output_content = fs::read_to_string(&output)?;
+            assert!(output_content.contains("==> a.rs"));
+            assert!(!output_content.contains("b.rs"));
+            assert!(!output_content.contains("c.rs"));
+            assert_eq!(summary.skipped_content, 2);
+            assert_eq!(summary.files, [PathBuf::from("a.rs")]);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_exclude_generated_counts_skipped_files() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("output.txt");
+
+        fs::write(temp_dir.path().join("main.go"), "package main")?;
+        fs::write(
+            temp_dir.path().join("api.pb.go"),
+            "// Code generated by protoc-gen-go. DO NOT EDIT.\npackage api",
+        )?;
+
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output, &[]);
+        let args = RunArgs {
+            exclude_generated: true,
+            ..args_for(temp_dir.path(), &output)
+        };
+
+        let summary = traverse_to_file(&walker, &args)?;
+
+        let output_content = fs::read_to_string(&output)?;
+        assert!(output_content.contains("==> main.go"));
+        assert!(!output_content.contains("api.pb.go"));
+        assert_eq!(summary.skipped_generated, 1);
+        assert_eq!(summary.files, [PathBuf::from("main.go")]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_header_root_replaces_root_in_headers() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let app = temp_dir.path().join("app");
+        let output = temp_dir.path().join("output.txt");
+        fs::create_dir(&app)?;
+        fs::write(app.join("main.rs"), "fn main() {}")?;
+
+        let walker = Walker::new(&app, &app, &output, &[])
+            .with_header_root(Some(temp_dir.path().to_path_buf()));
+        let args = args_for(&app, &output);
+
+        let summary = traverse_to_file(&walker, &args)?;
+
+        assert_eq!(summary.files, [PathBuf::from("app/main.rs")]);
+        assert!(fs::read_to_string(&output)?.starts_with("==> app/main.rs\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sloc_counts_per_language() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("output.txt");
+        fs::write(
+            temp_dir.path().join("main.rs"),
+            "// entry
+fn main() {
+}
+",
+        )?;
+        fs::write(
+            temp_dir.path().join("run.py"),
+            "# run
+
+main()
+",
+        )?;
+
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output, &[]);
+        let args = RunArgs {
+            sloc: true,
+            language_chart: true,
+            ..args_for(temp_dir.path(), &output)
+        };
+
+        let summary = traverse_to_file(&walker, &args)?;
+        assert_eq!(
+            summary.sloc,
+            BTreeMap::from([("python".to_string(), 1), ("rust".to_string(), 2)])
+        );
+        assert_eq!(
+            summary.language_bytes,
+            BTreeMap::from([("python".to_string(), 14), ("rust".to_string(), 23)])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mask_paths_rewrites_headers_not_content() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("output.txt");
+        fs::create_dir(temp_dir.path().join("acme-billing"))?;
+        fs::write(
+            temp_dir.path().join("acme-billing").join("lib.rs"),
+            "// acme-billing",
+        )?;
+        fs::write(temp_dir.path().join("main.rs"), "fn main() {}")?;
+
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output, &[]);
+        let args = RunArgs {
+            sort: sort::SortOrder::Path,
+            toc: true,
+            mask_paths: vec![PathMask {
+                pattern: Regex::new(r"acme-(\w+)")?,
+                replacement: "client-$1".to_string(),
+            }],
+            ..args_for(temp_dir.path(), &output)
+        };
+
+        let summary = traverse_to_file(&walker, &args)?;
+
+        let output_content = fs::read_to_string(&output)?;
+        assert!(output_content.contains("==> client-billing/lib.rs\n// acme-billing\n"));
+        assert_eq!(output_content.matches("acme").count(), 1);
+        assert_eq!(summary.masked_paths, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_excluded_paths_are_skipped_and_counted() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("output.txt");
+        fs::create_dir(temp_dir.path().join("src"))?;
+        fs::write(temp_dir.path().join("src").join("old.rs"), "// shared")?;
+        fs::write(temp_dir.path().join("src").join("new.rs"), "// not yet")?;
+
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output, &[])
+            .with_excluded_paths(HashSet::from([PathBuf::from("src/old.rs")]));
+        let args = args_for(temp_dir.path(), &output);
+
+        let summary = traverse_to_file(&walker, &args)?;
+
+        assert_eq!(summary.files, [PathBuf::from("src/new.rs")]);
+        assert_eq!(summary.skipped_shared, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_max_file_size_by_ext_skips_large_files() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("output.txt");
+        fs::write(temp_dir.path().join("big.json"), "x".repeat(20))?;
+        fs::write(temp_dir.path().join("small.JSON"), "{}")?;
+        fs::write(temp_dir.path().join("big.rs"), "x".repeat(20))?;
+
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output, &[]);
+        let args = RunArgs {
+            sort: sort::SortOrder::Path,
+            max_file_size_by_ext: vec![("json".to_string(), 1), ("json".to_string(), 10)],
+            ..args_for(temp_dir.path(), &output)
+        };
+
+        let summary = traverse_to_file(&walker, &args)?;
+        assert_eq!(
+            summary.files,
+            [PathBuf::from("big.rs"), PathBuf::from("small.JSON")]
+        );
+        assert_eq!(summary.skipped_oversized, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_max_files_per_dir_samples_each_directory() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("output.txt");
+
+        fs::create_dir(temp_dir.path().join("fixtures"))?;
+        for name in ["a", "b", "c", "d"] {
+            fs::write(temp_dir.path().join("fixtures").join(name), name)?;
+        }
+        fs::write(temp_dir.path().join("main.rs"), "fn main() {}")?;
+
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output, &[]);
+        let args = RunArgs {
+            sort: sort::SortOrder::Path,
+            max_files_per_dir: Some(2),
+            ..args_for(temp_dir.path(), &output)
+        };
+
+        let summary = traverse_to_file(&walker, &args)?;
+
+        assert_eq!(
+            summary.files,
+            [
+                PathBuf::from("fixtures/a"),
+                PathBuf::from("fixtures/b"),
+                PathBuf::from("main.rs")
+            ]
+        );
+        assert_eq!(summary.omitted_per_dir, [(PathBuf::from("fixtures"), 2)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_exclude_minified_counts_skipped_files() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("output.txt");
+
+        fs::write(temp_dir.path().join("app.js"), "export const a = 1;\n")?;
+        fs::write(temp_dir.path().join("app.min.js"), "var a=1;".repeat(500))?;
+
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output, &[]);
+        let args = RunArgs {
+            exclude_minified: true,
+            ..args_for(temp_dir.path(), &output)
+        };
+
+        let summary = traverse_to_file(&walker, &args)?;
+
+        assert_eq!(summary.skipped_minified, 1);
+        assert_eq!(summary.files, [PathBuf::from("app.js")]);
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_headers_link_and_target() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path().join("project");
+        fs::create_dir_all(root.join("shared"))?;
+        fs::write(root.join("shared").join("config.toml"), "key = 1")?;
+        fs::create_dir(root.join("app"))?;
+        std::os::unix::fs::symlink(root.join("shared"), root.join("app").join("linked"))?;
+
+        let run_with = |mode: SymlinkHeaders| -> anyhow::Result<String> {
+            let output = temp_dir.path().join(format!("{mode:?}.txt"));
+            let walker = Walker::new(&root, &root.join("app"), &output, &[]);
+            let args = RunArgs {
+                follow_symlinks: true,
+                symlink_headers: mode,
+                ..args_for(&root, &output)
+            };
+            traverse_to_file(&walker, &args)?;
+            Ok(fs::read_to_string(&output)?)
+        };
+
+        let link = run_with(SymlinkHeaders::Link)?;
+        assert!(link.contains("==> app/linked/config.toml"));
+
+        let target = run_with(SymlinkHeaders::Target)?;
+        assert!(target.contains("==> shared/config.toml"));
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_exclude_symlinks_skips_linked_files_and_dirs() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path().join("project");
+        fs::create_dir_all(root.join("shared"))?;
+        fs::write(root.join("shared").join("config.toml"), "key = 1")?;
+        std::os::unix::fs::symlink(root.join("shared"), root.join("linked"))?;
+        std::os::unix::fs::symlink(
+            root.join("shared").join("config.toml"),
+            root.join("config.toml"),
+        )?;
+        let output = temp_dir.path().join("output.txt");
+
+        let walker = Walker::new(&root, &root, &output, &[]);
+        let args = RunArgs {
+            follow_symlinks: true,
+            exclude_symlinks: true,
+            ..args_for(&root, &output)
+        };
+
+        let summary = traverse_to_file(&walker, &args)?;
+
+        assert_eq!(summary.files, [PathBuf::from("shared/config.toml")]);
+        assert_eq!(summary.skipped_symlinks, 2);
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_follow_symlink_dirs_skips_file_links() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path().join("project");
+        let shared = temp_dir.path().join("shared");
+        fs::create_dir_all(root.join("app"))?;
+        fs::create_dir_all(&shared)?;
+        fs::write(shared.join("util.rs"), "pub fn util() {}")?;
+        fs::write(root.join("app").join("main.rs"), "fn main() {}")?;
+        std::os::unix::fs::symlink(&shared, root.join("app").join("shared"))?;
+        std::os::unix::fs::symlink(root.join("app").join("main.rs"), root.join("main.rs"))?;
+        let output = temp_dir.path().join("output.txt");
+
+        let walker = Walker::new(&root, &root, &output, &[]);
+        let args = RunArgs {
+            sort: sort::SortOrder::Path,
+            follow_symlink_dirs: true,
+            ..args_for(&root, &output)
+        };
+
+        let summary = traverse_to_file(&walker, &args)?;
+
+        assert_eq!(
+            summary.files,
+            [
+                PathBuf::from("app/main.rs"),
+                PathBuf::from("app/shared/util.rs")
+            ]
+        );
+        assert_eq!(summary.skipped_symlinks, 1);
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_follow_symlink_dirs_skips_dangling_links() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path().join("project");
+        fs::create_dir_all(root.join("app"))?;
+        fs::write(root.join("app").join("main.rs"), "fn main() {}")?;
+        std::os::unix::fs::symlink(
+            temp_dir.path().join("removed"),
+            root.join("app").join("vendor"),
+        )?;
+        let output = temp_dir.path().join("output.txt");
+
+        let walker = Walker::new(&root, &root, &output, &[]);
+        let args = RunArgs {
+            follow_symlink_dirs: true,
+            ..args_for(&root, &output)
+        };
+
+        let summary = traverse_to_file(&walker, &args)?;
+
+        assert_eq!(summary.files, [PathBuf::from("app/main.rs")]);
+        assert_eq!(summary.dangling_symlinks, [root.join("app").join("vendor")]);
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_loops_are_skipped() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path().join("project");
+        fs::create_dir_all(root.join("src"))?;
+        fs::write(root.join("src").join("lib.rs"), "pub fn lib() {}")?;
+        std::os::unix::fs::symlink(&root, root.join("src").join("back"))?;
+        let output = temp_dir.path().join("output.txt");
+
+        let walker = Walker::new(&root, &root, &output, &[]);
+        let args = RunArgs {
+            follow_symlinks: true,
+            ..args_for(&root, &output)
+        };
+
+        let summary = traverse_to_file(&walker, &args)?;
+
+        assert_eq!(summary.files, [PathBuf::from("src/lib.rs")]);
+        assert_eq!(summary.symlink_loops, [root.join("src").join("back")]);
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_dangling_symlinks_are_skipped() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path().join("project");
+        fs::create_dir_all(root.join("src"))?;
+        fs::write(root.join("src").join("lib.rs"), "pub fn lib() {}")?;
+        std::os::unix::fs::symlink(root.join("deleted.rs"), root.join("src").join("gone.rs"))?;
+        let output = temp_dir.path().join("output.txt");
+
+        let walker = Walker::new(&root, &root, &output, &[]);
+        let args = RunArgs {
+            follow_symlinks: true,
+            ..args_for(&root, &output)
+        };
+
+        let summary = traverse_to_file(&walker, &args)?;
+
+        assert_eq!(summary.files, [PathBuf::from("src/lib.rs")]);
+        assert_eq!(
+            summary.dangling_symlinks,
+            [root.join("src").join("gone.rs")]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_baseline_keeps_added_and_changed_files() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path().join("feature");
+        let baseline = temp_dir.path().join("main");
+        for dir in [&root, &baseline] {
+            fs::create_dir_all(dir.join("src"))?;
+            fs::write(dir.join("src").join("same.rs"), "fn same() {}")?;
+        }
+        fs::write(root.join("src").join("edited.rs"), "fn new() {}")?;
+        fs::write(baseline.join("src").join("edited.rs"), "fn old() {}")?;
+        fs::write(root.join("added.rs"), "fn added() {}")?;
+        let output = temp_dir.path().join("output.txt");
+
+        let walker = Walker::new(&root, &root, &output, &[]);
+        let args = RunArgs {
+            sort: sort::SortOrder::Path,
+            baseline: Some(baseline),
+            ..args_for(&root, &output)
+        };
+
+        let summary = traverse_to_file(&walker, &args)?;
+
+        assert_eq!(
+            summary.files,
+            [PathBuf::from("added.rs"), PathBuf::from("src/edited.rs")]
+        );
+        assert_eq!(
+            (
+                summary.baseline_added,
+                summary.baseline_changed,
+                summary.baseline_unchanged
+            ),
+            (1, 1, 1)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_baseline_compares_inputs_outside_root_from_the_input() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path().join("project");
+        let input = temp_dir.path().join("feature");
+        let baseline = temp_dir.path().join("main");
+        fs::create_dir_all(&root)?;
+        for dir in [&input, &baseline] {
+            fs::create_dir_all(dir.join("src"))?;
+            fs::write(dir.join("src").join("same.rs"), "fn same() {}")?;
+        }
+        fs::write(input.join("src").join("edited.rs"), "fn new() {}")?;
+        fs::write(baseline.join("src").join("edited.rs"), "fn old() {}")?;
+        let output = temp_dir.path().join("output.txt");
+
+        let walker = Walker::new(&root, &input, &output, &[]);
+        let args = RunArgs {
+            baseline: Some(baseline),
+            ..args_for(&root, &output)
+        };
+
+        let summary = traverse_to_file(&walker, &args)?;
+
+        assert_eq!(summary.files.len(), 1);
+        assert!(summary.files[0].ends_with("src/edited.rs"));
+        assert_eq!(
+            (
+                summary.baseline_added,
+                summary.baseline_changed,
+                summary.baseline_unchanged
+            ),
+            (0, 1, 1)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_exclude_large_dirs_prunes_dirs_over_threshold() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path().join("project");
+        fs::create_dir_all(root.join("vendor"))?;
+        fs::create_dir_all(root.join("src"))?;
+        for i in 0..4 {
+            fs::write(root.join("vendor").join(format!("dep{i}.rs")), "")?;
+        }
+        fs::write(root.join("src").join("main.rs"), "fn main() {}")?;
+        fs::write(root.join("README.md"), "# readme")?;
+        let output = temp_dir.path().join("output.txt");
+
+        let walker = Walker::new(&root, &root, &output, &[]);
+        let args = RunArgs {
+            sort: sort::SortOrder::Path,
+            exclude_large_dirs: true,
+            prune_dir_over: 3,
+            ..args_for(&root, &output)
+        };
+
+        let summary = traverse_to_file(&walker, &args)?;
+
+        // The input has three entries, so only `vendor` is over the threshold
+        assert_eq!(
+            summary.files,
+            [PathBuf::from("README.md"), PathBuf::from("src/main.rs")]
+        );
+        assert_eq!(summary.pruned_dirs, [root.join("vendor")]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_respect_editorconfig_normalizes_per_file() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path().join("project");
+        fs::create_dir_all(&root)?;
+        fs::write(
+            root.join(".editorconfig"),
+            "root = true\n[*.py]\nindent_style = space\nindent_size = 2\nend_of_line = lf\n",
+        )?;
+        fs::write(root.join("app.py"), "if x:\r\n\treturn\r\n")?;
+        fs::write(root.join("Makefile"), "all:\n\tbuild\n")?;
+        let output = temp_dir.path().join("output.txt");
+
+        let walker = Walker::new(&root, &root, &output, &[".editorconfig".to_string()]);
+        let mut args = RunArgs {
+            sort: sort::SortOrder::Path,
+            respect_editorconfig: true,
+            ..args_for(&root, &output)
+        };
+
+        traverse_to_file(&walker, &args)?;
+        assert_eq!(
+            fs::read_to_string(&output)?,
+            "==> Makefile\nall:\n\tbuild\n\n==> app.py\nif x:\n  return\n"
+        );
+
+        // An explicit width applies to every file
+        args.tab_width = 4;
+        traverse_to_file(&walker, &args)?;
+        assert_eq!(
+            fs::read_to_string(&output)?,
+            "==> Makefile\nall:\n    build\n\n==> app.py\nif x:\n    return\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_reverse_flips_the_sorted_order() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("output.txt");
+        fs::create_dir_all(temp_dir.path().join("src"))?;
+        fs::write(temp_dir.path().join("src/lib.rs"), "")?;
+        fs::write(temp_dir.path().join("build.rs"), "")?;
+        fs::write(temp_dir.path().join("README.md"), "")?;
+
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output, &[]);
+        let mut args = RunArgs {
+            sort: sort::SortOrder::Path,
+            reverse: true,
+            ..args_for(temp_dir.path(), &output)
+        };
+
+        let summary = traverse_to_file(&walker, &args)?;
+        assert_eq!(
+            summary.files,
+            [
+                PathBuf::from("src/lib.rs"),
+                PathBuf::from("build.rs"),
+                PathBuf::from("README.md"),
+            ]
+        );
+
+        // Context files still lead; the rest keep the reversed order
+        args.context_first = true;
+        let summary = traverse_to_file(&walker, &args)?;
+        assert_eq!(
+            summary.files,
+            [
+                PathBuf::from("README.md"),
+                PathBuf::from("src/lib.rs"),
+                PathBuf::from("build.rs"),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_context_only_elides_supported_languages() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("output.txt");
+
+        fs::write(
+            temp_dir.path().join("lib.rs"),
+            "pub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n",
+        )?;
+        fs::write(temp_dir.path().join("notes.txt"), "kept in full")?;
+
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output, &[]);
+        let args = RunArgs {
+            sort: sort::SortOrder::Path,
+            context_only: true,
+            ..args_for(temp_dir.path(), &output)
+        };
+
+        traverse_to_file(&walker, &args)?;
+
+        let output_content = fs::read_to_string(&output)?;
+        assert_eq!(
+            output_content,
+            "==> lib.rs\npub fn add(a: i32, b: i32) -> i32 { ... }\n\n==> notes.txt\nkept in full\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_max_line_length_truncates_and_counts() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("output.txt");
+
+        fs::write(temp_dir.path().join("app.min.js"), "x".repeat(500))?;
+        fs::write(temp_dir.path().join("main.js"), "let a = 1;")?;
+
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output, &[]);
+        let args = RunArgs {
+            sort: sort::SortOrder::Path,
+            max_line_length: Some(20),
+            ..args_for(temp_dir.path(), &output)
+        };
+
+        let summary = traverse_to_file(&walker, &args)?;
+
+        let output_content = fs::read_to_string(&output)?;
+        assert!(output_content.contains(&format!("{}…(truncated)", "x".repeat(20))));
+        assert!(output_content.contains("let a = 1;"));
+        assert_eq!(summary.truncated_lines, 1);
+        assert_eq!(summary.files_with_truncated_lines, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_collapse_whitespace_runs_keeps_python_indentation() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("output.txt");
+
+        fs::write(temp_dir.path().join("app.py"), "def f():\n    return  1")?;
+        fs::write(
+            temp_dir.path().join("main.rs"),
+            "fn main() {\n    run(  1);\n}",
+        )?;
+
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output, &[]);
+        let args = RunArgs {
+            sort: sort::SortOrder::Path,
+            collapse_whitespace_runs: true,
+            ..args_for(temp_dir.path(), &output)
+        };
+
+        let summary = traverse_to_file(&walker, &args)?;
+
+        let output_content = fs::read_to_string(&output)?;
+        assert!(output_content.contains("def f():\n    return 1"));
+        assert!(output_content.contains("fn main() {\nrun( 1);\n}"));
+        assert_eq!(summary.whitespace_bytes_saved, 6);
+        assert_eq!(
+            summary.whitespace_collapsed_sensitive,
+            [PathBuf::from("app.py")]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_content_replace_rewrites_contents() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("output.txt");
+
+        fs::write(
+            temp_dir.path().join("config.toml"),
+            "root = \"/home/alice/app\"\nlogs = \"/home/alice/logs\"",
+        )?;
+
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output, &[]);
+        let args = RunArgs {
+            content_replace: vec![ContentReplace {
+                find: "/home/alice".to_string(),
+                replacement: "~".to_string(),
+            }],
+            ..args_for(temp_dir.path(), &output)
+        };
+
+        let summary = traverse_to_file(&walker, &args)?;
+
+        let output_content = fs::read_to_string(&output)?;
+        assert!(output_content.contains("root = \"~/app\"\nlogs = \"~/logs\""));
+        assert!(!output_content.contains("alice"));
+        assert_eq!(summary.content_replacements, 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_content_max_depth_lists_deeper_files_in_toc() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("output.txt");
+
+        fs::create_dir_all(temp_dir.path().join("src").join("deep"))?;
+        fs::write(temp_dir.path().join("top.txt"), "top")?;
+        fs::write(temp_dir.path().join("src").join("mid.txt"), "mid")?;
+        fs::write(
+            temp_dir.path().join("src").join("deep").join("low.txt"),
+            "low",
+        )?;
+
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output, &[]);
+        let args = RunArgs {
+            toc: true,
+            sort: sort::SortOrder::Path,
+            content_max_depth: Some(2),
+            ..args_for(temp_dir.path(), &output)
+        };
+
+        let summary = traverse_to_file(&walker, &args)?;
+
+        let output_content = fs::read_to_string(&output)?;
+        assert!(output_content.contains("src/deep/low.txt (3 B)"));
+        assert!(!output_content.contains("==> src/deep/low.txt"));
+        assert!(!output_content.contains("low\n"));
+        assert!(output_content.contains("==> src/mid.txt\nmid\n"));
+        assert!(output_content.contains("==> top.txt\ntop\n"));
+        assert_eq!(summary.files.len(), 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_min_significant_size_groups_small_files() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("output.txt");
+
+        fs::write(temp_dir.path().join("a.rs"), "pub mod a;\n")?;
+        fs::write(
+            temp_dir.path().join("big.rs"),
+            "fn big() {\n    todo!()\n}\n",
+        )?;
+        fs::write(temp_dir.path().join("c.rs"), "pub mod c;\n")?;
+
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output, &[]);
+        let args = RunArgs {
+            sort: sort::SortOrder::Path,
+            min_significant_size: Some(16),
+            ..args_for(temp_dir.path(), &output)
+        };
+
+        traverse_to_file(&walker, &args)?;
+
+        assert_eq!(
+            fs::read_to_string(&output)?,
+            "==> big.rs\nfn big() {\n    todo!()\n}\n\n==> (small files)\n--- a.rs\npub mod a;\n--- c.rs\npub mod c;\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_packed_output_unpacks_to_the_same_bytes() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("p");
+        let output = temp_dir.path().join("output.txt");
+        fs::create_dir(&input)?;
+        let originals = [("b.txt", "x\n"), ("c.yml", "a: 1\n--- y\n")];
+        for (name, content) in originals {
+            fs::write(input.join(name), content)?;
+        }
+
+        let walker = Walker::new(&input, &input, &output, &[]);
+        let args = RunArgs {
+            sort: sort::SortOrder::Path,
+            min_significant_size: Some(64),
+            ..args_for(&input, &output)
+        };
+
+        traverse_to_file(&walker, &args)?;
+
+        // The YAML file's `--- y` line would split the block, so it keeps its header
+        let dump = fs::read_to_string(&output)?;
+        assert!(dump.contains("==> (small files)\n--- b.txt\n"), "{dump}");
+        assert!(dump.contains("==> c.yml\n"), "{dump}");
+        let mut unpacked: Vec<(String, String)> = crate::core::unpack::parse(&dump)?
+            .into_iter()
+            .map(|file| (file.path.display().to_string(), file.content))
+            .collect();
+        unpacked.sort();
+        let expected: Vec<(String, String)> = originals
+            .iter()
+            .map(|(name, content)| (name.to_string(), content.to_string()))
+            .collect();
+        assert_eq!(unpacked, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_min_significant_size_packs_only_text_output() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("output.json");
+        fs::write(temp_dir.path().join("a.txt"), "x")?;
+
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output, &[]);
+        for format in [OutputFormat::Json, OutputFormat::Markdown] {
+            let args = RunArgs {
+                format,
+                min_significant_size: Some(64),
+                ..args_for(temp_dir.path(), &output)
+            };
+
+            let mut sink = OutputSink::create(&output)?.with_format(format);
+            walker.traverse(&args, &mut sink)?;
+            sink.finish()?;
+
+            let content = fs::read_to_string(&output)?;
+            assert!(!content.contains(pack::SMALL_FILES_TITLE), "{content}");
+            assert!(content.contains("a.txt"), "{content}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_content_writes_only_the_tree() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("output.txt");
+
+        fs::create_dir(temp_dir.path().join("src"))?;
+        fs::write(temp_dir.path().join("src").join("main.rs"), "fn main() {}")?;
+        fs::write(temp_dir.path().join("README.md"), "# Title")?;
+
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output, &[]);
+        let args = RunArgs {
+            no_content: true,
+            ..args_for(temp_dir.path(), &output)
+        };
+
+        let summary = traverse_to_file(&walker, &args)?;
+
+        assert_eq!(
+            fs::read_to_string(&output)?,
+            "Directory Tree (2 files)\n.\n├── README.md\n└── src\n    └── main.rs\n"
+        );
+        assert_eq!(summary.files.len(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_include_empty_dirs_in_tree() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("output.txt");
+
+        fs::create_dir(temp_dir.path().join("src"))?;
+        fs::write(temp_dir.path().join("src").join("main.rs"), "fn main() {}")?;
+        fs::create_dir(temp_dir.path().join("logs"))?;
+        fs::write(temp_dir.path().join("logs").join("run.log"), "ok")?;
+        fs::create_dir(temp_dir.path().join("assets"))?;
+
+        let walker = Walker::new(
+            temp_dir.path(),
+            temp_dir.path(),
+            &output,
+            &["*.log".to_string()],
+        );
+        let mut args = RunArgs {
+            no_content: true,
+            ..args_for(temp_dir.path(), &output)
+        };
+
+        traverse_to_file(&walker, &args)?;
+        assert_eq!(
+            fs::read_to_string(&output)?,
+            "Directory Tree (1 file)\n.\n└── src\n    └── main.rs\n"
+        );
+
+        args.include_empty_dirs_in_tree = true;
+        traverse_to_file(&walker, &args)?;
+        assert_eq!(
+            fs::read_to_string(&output)?,
+            "Directory Tree (1 file)\n.\n├── assets/\n├── logs/\n└── src\n    └── main.rs\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_oneline_lists_first_lines() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("output.txt");
+        fs::create_dir(temp_dir.path().join("src"))?;
+        fs::write(temp_dir.path().join("README.md"), "\n# Project\n\nDetails")?;
+        fs::write(temp_dir.path().join("src/main.rs"), "fn main() {}\n")?;
+        fs::write(temp_dir.path().join("empty.txt"), "")?;
+
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output, &[]);
+        let args = RunArgs {
+            sort: sort::SortOrder::Path,
+            oneline: true,
+            ..args_for(temp_dir.path(), &output)
+        };
+
+        let summary = traverse_to_file(&walker, &args)?;
+
+        assert_eq!(
+            fs::read_to_string(&output)?,
+            "README.md: # Project\nempty.txt:\nsrc/main.rs: fn main() {}\n"
+        );
+        assert_eq!(summary.files.len(), 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_deadline_stops_traversal() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("output.txt");
+        fs::write(temp_dir.path().join("a.txt"), "alpha")?;
+
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output, &[])
+            .with_deadline(Some(Instant::now()));
+        let args = args_for(temp_dir.path(), &output);
+
+        // Nothing was collected before the deadline, so there is nothing to write
+        let summary = traverse_to_file(&walker, &args)?;
+        assert!(summary.timed_out);
+        assert_eq!(fs::read_to_string(&output)?, "");
+
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output, &[])
+            .with_deadline(Some(Instant::now() + std::time::Duration::from_secs(60)));
+        let summary = traverse_to_file(&walker, &args)?;
+        assert!(!summary.timed_out);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_deadline_during_reading_lists_only_written_files() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("output.txt");
+        fs::write(temp_dir.path().join("a.txt"), "alpha")?;
+        fs::write(temp_dir.path().join("b.txt"), "beta")?;
+        fs::write(temp_dir.path().join("c.txt"), "gamma")?;
+
+        // The first file written waits out the deadline, so the rest are cut off
+        let deadline = Instant::now() + std::time::Duration::from_millis(500);
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output, &[])
+            .with_deadline(Some(deadline))
+            .with_progress(move |_| {
+                while Instant::now() <= deadline {
+                    std::thread::sleep(std::time::Duration::from_millis(10));
+                }
+            });
+        let args = RunArgs {
+            sort: sort::SortOrder::Path,
+            ..args_for(temp_dir.path(), &output)
+        };
+
+        let summary = traverse_to_file(&walker, &args)?;
+
+        assert!(summary.timed_out);
+        assert_eq!(summary.files, [PathBuf::from("a.txt")]);
+        let content = fs::read_to_string(&output)?;
+        assert!(content.contains("alpha"));
+        assert!(!content.contains("beta"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_previous_output_is_skipped_on_rerun() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let first_output = temp_dir.path().join("first.txt");
+        let second_output = temp_dir.path().join("second.txt");
+        fs::write(temp_dir.path().join("main.rs"), "fn main() {}")?;
+
+        let args = RunArgs {
+            input_paths: vec![temp_dir.path().to_path_buf()],
+            root: Some(temp_dir.path().to_path_buf()),
+            skip_hidden: false,
+            fast_mode: true,
+            ..RunArgs::default()
+        };
+
+        // First run, saved inside the traversed directory
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &first_output, &[]);
+        let mut sink = OutputSink::create(&first_output)?;
+        sink.write_marker()?;
+        walker.traverse(&args, &mut sink)?;
+        sink.finish()?;
+
+        // Second run to a different file must not ingest the first one
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &second_output, &[]);
+        let summary = traverse_to_file(&walker, &args)?;
+
+        assert_eq!(summary.files, [PathBuf::from("main.rs")]);
+        assert_eq!(
+            fs::read_to_string(&second_output)?,
+            "==> main.rs\nfn main() {}\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_errors_are_collected_not_fatal() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("output.txt");
+        fs::write(temp_dir.path().join("a.txt"), "alpha")?;
+        fs::write(temp_dir.path().join("b.bin"), [0xff, 0xfe, 0x00])?;
+        fs::write(temp_dir.path().join("c.bin"), [0xc3, 0x28])?;
+
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output, &[]);
+        let args = RunArgs {
+            sort: sort::SortOrder::Path,
+            ..args_for(temp_dir.path(), &output)
+        };
+
+        let summary = traverse_to_file(&walker, &args)?;
+
+        let failed: Vec<_> = summary
+            .read_errors
+            .iter()
+            .map(|(path, _)| path.clone())
+            .collect();
+        assert_eq!(
+            failed,
+            [temp_dir.path().join("b.bin"), temp_dir.path().join("c.bin")]
+        );
+        assert_eq!(summary.files, [PathBuf::from("a.txt")]);
+        assert_eq!(fs::read_to_string(&output)?, "==> a.txt\nalpha\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_process_dir_validates_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let output = temp_dir.path().join("output.txt");
+
+        let walker = Walker::new(
+            temp_dir.path(),
+            &PathBuf::from("/nonexistent/path"),
+            &output,
+            &[],
+        );
+        let mut sink = OutputSink::create(&output).unwrap();
+
+        let args = RunArgs {
+            input_paths: vec![PathBuf::from("/nonexistent/path")],
+            output_path: Some(output),
+            root: Some(temp_dir.path().to_path_buf()),
+            exclude: vec![],
+            clipboard: false,
+            stats: false,
+            editor: false,
+            delete: false,
+            verbose: false,
+            skip_hidden: true,
+            raw: true,
+            fast_mode: true,
+            ..RunArgs::default()
+        };
+
+        let result = walker.process_dir(&args, &mut sink);
+        assert!(result.is_err());
+
+        let error_msg = format!("{:?}", result.unwrap_err());
+        assert!(error_msg.contains("does not exist") || error_msg.contains("validation failed"));
+    }
+
+    #[test]
+    fn test_no_files_found_error() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("output.txt");
 
         // Create an empty directory
         let empty_dir = temp_dir.path().join("empty");
         fs::create_dir(&empty_dir)?;
 
-        let walker = Walker::new(temp_dir.path(), &empty_dir, &output, &vec![]);
+        let walker = Walker::new(temp_dir.path(), &empty_dir, &output, &[]);
 
         let args = RunArgs {
             input_paths: vec![empty_dir.clone()],
@@ -413,9 +2735,10 @@ mod walker_tests {
             skip_hidden: false,
             raw: true,
             fast_mode: true,
+            ..RunArgs::default()
         };
 
-        let result = walker.traverse(&args);
+        let result = traverse_to_file(&walker, &args);
         assert!(result.is_err());
 
         let error_msg = format!("{:?}", result.unwrap_err());
@@ -443,12 +2766,7 @@ mod walker_tests {
         )?;
 
         let exclude_patterns = vec!["*_test.rs".to_string(), "*.go".to_string()];
-        let walker = Walker::new(
-            temp_dir.path(),
-            &temp_dir.path(),
-            &output,
-            &exclude_patterns,
-        );
+        let walker = Walker::new(temp_dir.path(), temp_dir.path(), &output, &exclude_patterns);
         let args = RunArgs {
             input_paths: vec![temp_dir.path().to_path_buf()],
             output_path: Some(output.to_path_buf()),
@@ -462,9 +2780,10 @@ mod walker_tests {
             skip_hidden: false,
             raw: true,
             fast_mode: true,
+            ..RunArgs::default()
         };
 
-        let result = walker.traverse(&args);
+        let result = traverse_to_file(&walker, &args);
         assert!(result.is_ok());
 
         // Read and verify output
@@ -1,8 +1,34 @@
 //! animations - Provides terminal animation utilities for visual feedback.
 
 use colored::Colorize;
-use std::io::{stdout, Write};
-use std::{thread, time};
+use std::io::{self, IsTerminal, Write, stderr, stdout};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::{panic, process, thread, time};
+
+/// Time each spinner frame is shown.
+const FRAME_INTERVAL: time::Duration = time::Duration::from_millis(100);
+
+/// Whether decorative output goes to stderr (`--progress-to-stderr`).
+static PROGRESS_TO_STDERR: AtomicBool = AtomicBool::new(false);
+
+/// `println!` for banners, progress and status messages.
+///
+/// Goes through [`print_flush`], so it follows `--progress-to-stderr` and exits
+/// quietly on a broken pipe.
+macro_rules! statusln {
+    () => {
+        $crate::core::ui::animations::print_flush("\n")
+    };
+    ($($arg:tt)*) => {
+        $crate::core::ui::animations::print_flush(&format!("{}\n", format_args!($($arg)*)))
+    };
+}
+pub(crate) use statusln;
+
+/// Sends all decorative output to stderr instead of stdout, keeping stdout clean.
+pub fn set_progress_to_stderr(enabled: bool) {
+    PROGRESS_TO_STDERR.store(enabled, Ordering::Relaxed);
+}
 
 /// Spinner provides animated loading indicators with customizable frames and colors.
 pub struct Spinner {
@@ -37,33 +63,45 @@ impl Spinner {
         }
     }
 
-    /// Displays the spinner animation for the specified duration.
+    /// Runs `work`, animating the spinner on a background thread until it returns.
+    ///
+    /// The spinner line is cleared afterwards, so the caller reports the outcome. Without
+    /// a terminal to draw on, `work` just runs.
     ///
     /// # Arguments
     ///
     /// * `message` - The message to display alongside the spinner
-    /// * `duration_ms` - Total duration of the animation in milliseconds
-    pub fn spin(&self, message: &str, duration_ms: u64) {
-        let frame_duration = duration_ms / self.frames.len() as u64;
-
-        for (i, frame) in self.frames.iter().enumerate() {
-            let color = &self.colors[i % self.colors.len()];
-            print!(
-                "\r{} {} {}",
-                frame.color(*color),
-                message.bright_cyan(),
-                "...".dimmed()
-            );
-            stdout().flush().unwrap();
-            thread::sleep(time::Duration::from_millis(frame_duration));
+    /// * `work` - The work the spinner waits for
+    pub fn run_while<T>(&self, message: &str, work: impl FnOnce() -> T) -> T {
+        if !progress_is_terminal() {
+            return work();
         }
 
-        println!(
-            "\r{} {} {}",
-            "✓".bright_green(),
-            message.bright_green(),
-            "Done!".dimmed()
-        );
+        let done = AtomicBool::new(false);
+        thread::scope(|scope| {
+            let ticker = scope.spawn(|| {
+                let mut i = 0;
+                while !done.load(Ordering::Relaxed) {
+                    let frame = self.frames[i % self.frames.len()];
+                    let color = self.colors[i % self.colors.len()];
+                    print_flush(&format!(
+                        "\r{} {} {}",
+                        frame.color(color),
+                        message.bright_cyan(),
+                        "...".dimmed()
+                    ));
+                    thread::park_timeout(FRAME_INTERVAL);
+                    i += 1;
+                }
+                print_flush("\r\x1b[2K");
+            });
+
+            // Stop the ticker even if the work panics, or the scope would never end
+            let result = panic::catch_unwind(panic::AssertUnwindSafe(work));
+            done.store(true, Ordering::Relaxed);
+            ticker.thread().unpark();
+            result.unwrap_or_else(|payload| panic::resume_unwind(payload))
+        })
     }
 }
 
@@ -75,13 +113,29 @@ impl Spinner {
 /// * `count` - Number of dots to animate
 /// * `delay_ms` - Delay between each dot in milliseconds
 pub fn animated_dots(text: &str, count: usize, delay_ms: u64) {
-    print!("{}", text.bright_yellow());
+    print_flush(&text.bright_yellow().to_string());
     for _ in 0..count {
-        print!("{}", ".".bright_yellow());
-        stdout().flush().unwrap();
+        print_flush(&".".bright_yellow().to_string());
         thread::sleep(time::Duration::from_millis(delay_ms));
     }
-    println!();
+    print_flush("\n");
+}
+
+/// Writes text to stdout (stderr with `--progress-to-stderr`) without a trailing
+/// newline and flushes it immediately.
+///
+/// Animations are cosmetic, so write errors are ignored. A broken pipe (e.g.
+/// `treeclip run | head`) means nobody is reading anymore, so the process exits
+/// quietly instead of panicking.
+pub fn print_flush(text: &str) {
+    let result = if PROGRESS_TO_STDERR.load(Ordering::Relaxed) {
+        let mut out = stderr().lock();
+        out.write_all(text.as_bytes()).and_then(|()| out.flush())
+    } else {
+        let mut out = stdout().lock();
+        out.write_all(text.as_bytes()).and_then(|()| out.flush())
+    };
+    exit_on_broken_pipe(result);
 }
 
 /// Generates a progress counter message at specified intervals.
@@ -96,7 +150,7 @@ pub fn animated_dots(text: &str, count: usize, delay_ms: u64) {
 ///
 /// Returns `Some(message)` if counter should be displayed, `None` otherwise.
 pub fn progress_counter(emoji_set: &[&str], current: usize, interval: usize) -> Option<String> {
-    if current % interval == 0 {
+    if current.is_multiple_of(interval) {
         let idx = (current / interval) % emoji_set.len();
         Some(format!(
             "{} Collected {} files so far...",
@@ -107,6 +161,26 @@ pub fn progress_counter(emoji_set: &[&str], current: usize, interval: usize) ->
     }
 }
 
+// -------------------------------------------- Private Helper Functions --------------------------------------------
+
+/// Returns `true` if decorative output goes to a terminal, where animations render.
+fn progress_is_terminal() -> bool {
+    if PROGRESS_TO_STDERR.load(Ordering::Relaxed) {
+        stderr().is_terminal()
+    } else {
+        stdout().is_terminal()
+    }
+}
+
+/// Exits with status 0 if the write failed because stdout was closed.
+fn exit_on_broken_pipe(result: io::Result<()>) {
+    if let Err(e) = result
+        && e.kind() == io::ErrorKind::BrokenPipe
+    {
+        process::exit(0);
+    }
+}
+
 #[cfg(test)]
 mod animations_tests {
     use super::*;
@@ -125,6 +199,12 @@ mod animations_tests {
         assert_eq!(spinner.colors.len(), 4);
     }
 
+    #[test]
+    fn test_run_while_returns_the_work_result() {
+        let spinner = Spinner::new_loading();
+        assert_eq!(spinner.run_while("working", || 6 * 7), 42);
+    }
+
     #[test]
     fn test_progress_counter_at_interval() {
         let emojis = vec!["🌱", "🌿", "🍃"];
@@ -157,6 +237,13 @@ mod animations_tests {
         assert!(result3.is_some());
     }
 
+    #[test]
+    fn test_exit_on_broken_pipe_ignores_other_errors() {
+        // Returning at all proves the process did not exit
+        exit_on_broken_pipe(Ok(()));
+        exit_on_broken_pipe(Err(io::Error::other("disk on fire")));
+    }
+
     #[test]
     fn test_progress_counter_zero() {
         let emojis = vec!["🌱", "🌿"];
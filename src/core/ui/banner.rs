@@ -1,5 +1,6 @@
 //! banner - Provides welcome and goodbye banner displays for the application.
 
+use crate::core::ui::animations::statusln;
 use crate::core::ui::table::{Align, BorderStyle, FormattedBox};
 use colored::Colorize;
 use rand::Rng;
@@ -37,6 +38,23 @@ pub static BANNERS: LazyLock<Vec<String>> = LazyLock::new(|| {
     ]
 });
 
+/// Welcome banner to show (`--banner`).
+#[derive(clap::ValueEnum, serde::Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum BannerChoice {
+    /// Double-bordered banner
+    Double,
+    /// Rounded banner
+    Rounded,
+    /// Sharp-cornered banner
+    Sharp,
+    /// A different banner each run
+    #[default]
+    Random,
+    /// No welcome banner
+    None,
+}
+
 /// Goodbye messages to display on exit.
 const GOODBYE_MESSAGES: &[&str] = &[
     "✨ Mission accomplished! Time to shine!",
@@ -73,27 +91,27 @@ const KAOMOJIS: &[&str] = &[
     "(ﾉ^ヮ^)ﾉ*:・ﾟ✧",
 ];
 
-/// Displays a randomly selected welcome banner.
-pub fn print_welcome() {
-    let mut rng = rand::rng();
-    let banner = &BANNERS[rng.random_range(0..BANNERS.len())];
-    println!("{}", banner.bright_magenta());
+/// Displays the chosen welcome banner.
+pub fn print_welcome(choice: BannerChoice) {
+    if let Some(banner) = banner_for(choice) {
+        statusln!("{}", banner.bright_magenta());
+    }
 }
 
 /// Displays a goodbye message with a random kaomoji.
 pub fn print_goodbye() {
-    println!("\n{}", "━".repeat(55).bright_cyan());
+    statusln!("\n{}", "━".repeat(55).bright_cyan());
 
     let mut rng = rand::rng();
     let message = GOODBYE_MESSAGES[rng.random_range(0..GOODBYE_MESSAGES.len())];
 
-    println!("    {}", message.bright_green().bold());
-    println!(
+    statusln!("    {}", message.bright_green().bold());
+    statusln!(
         "    {} {}",
         get_random_kaomoji(),
         "Have a wonderful day!".bright_yellow()
     );
-    println!("{}\n", "━".repeat(55).bright_cyan());
+    statusln!("{}\n", "━".repeat(55).bright_cyan());
 }
 
 /// Returns a random kaomoji from the collection.
@@ -102,6 +120,20 @@ pub fn get_random_kaomoji() -> &'static str {
     KAOMOJIS[rng.random_range(0..KAOMOJIS.len())]
 }
 
+// -------------------------------------------- Private Helper Functions --------------------------------------------
+
+/// Resolves a banner choice to its design, picking one at random for `Random`.
+fn banner_for(choice: BannerChoice) -> Option<&'static str> {
+    let index = match choice {
+        BannerChoice::Double => 0,
+        BannerChoice::Rounded => 1,
+        BannerChoice::Sharp => 2,
+        BannerChoice::Random => rand::rng().random_range(0..BANNERS.len()),
+        BannerChoice::None => return None,
+    };
+    Some(BANNERS[index].as_str())
+}
+
 #[cfg(test)]
 mod banner_tests {
     use super::*;
@@ -119,6 +151,28 @@ mod banner_tests {
         }
     }
 
+    #[test]
+    fn test_banner_choice_pins_design() {
+        assert!(
+            banner_for(BannerChoice::Double)
+                .unwrap()
+                .contains("Traverse & Extract")
+        );
+        assert!(
+            banner_for(BannerChoice::Rounded)
+                .unwrap()
+                .contains("friendly")
+        );
+        assert!(
+            banner_for(BannerChoice::Sharp)
+                .unwrap()
+                .contains("Fast • Simple • Cute")
+        );
+        let random = banner_for(BannerChoice::Random);
+        assert!(BANNERS.iter().any(|b| Some(b.as_str()) == random));
+        assert_eq!(banner_for(BannerChoice::None), None);
+    }
+
     #[test]
     fn test_goodbye_messages_not_empty() {
         assert!(!GOODBYE_MESSAGES.is_empty());
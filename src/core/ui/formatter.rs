@@ -1,13 +1,21 @@
 //! formatter - Provides formatting utilities for configuration display and statistics.
 
-use crate::core::ui::table::FormattedBox;
+use crate::core::ui::table::{Align, FormattedBox};
 use crate::core::utils;
 use colored::{ColoredString, Colorize};
+use std::collections::BTreeMap;
 use std::path::Path;
+use terminal_size::{Width, terminal_size};
 
 const LABEL_WIDTH: usize = 18;
 const BOX_WIDTH: usize = 55;
 
+/// Chart width when the output is not a terminal.
+const CHART_DEFAULT_WIDTH: usize = 80;
+/// Bounds on the bar length of the language chart, so it stays readable.
+const CHART_MIN_BAR_WIDTH: usize = 10;
+const CHART_MAX_BAR_WIDTH: usize = 60;
+
 /// ConfigFormatter handles formatting of configuration settings display.
 pub struct ConfigFormatter;
 
@@ -56,12 +64,50 @@ impl ConfigFormatter {
     }
 }
 
+/// A single metric shown in the statistics box.
+#[derive(clap::ValueEnum, serde::Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum StatsField {
+    /// Character count
+    #[value(alias = "characters")]
+    Chars,
+    /// Line count
+    Lines,
+    /// Word count
+    Words,
+    /// Human-readable byte size
+    Size,
+}
+
+impl StatsField {
+    /// Every field, in display order.
+    pub const ALL: [StatsField; 4] = [
+        StatsField::Chars,
+        StatsField::Lines,
+        StatsField::Words,
+        StatsField::Size,
+    ];
+}
+
+/// Alignment of the values in the statistics box (`--stats-align`).
+#[derive(clap::ValueEnum, serde::Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum StatsAlign {
+    /// Values start right after the longest label
+    Left,
+    /// Values line up against the right border
+    #[default]
+    Right,
+}
+
 /// StatsBox displays content statistics in a formatted box.
 pub struct StatsBox {
     lines: usize,
     chars: usize,
     words: usize,
     bytes: usize,
+    fields: Vec<StatsField>,
+    align: StatsAlign,
 }
 
 impl StatsBox {
@@ -72,35 +118,33 @@ impl StatsBox {
             chars,
             words,
             bytes,
+            fields: StatsField::ALL.to_vec(),
+            align: StatsAlign::default(),
         }
     }
 
+    /// Restricts the rendered rows to the given fields (builder pattern).
+    ///
+    /// Rows keep their standard order regardless of the order given here.
+    pub fn fields(mut self, fields: &[StatsField]) -> Self {
+        self.fields = fields.to_vec();
+        self
+    }
+
+    /// Sets the alignment of the values (builder pattern).
+    pub fn align(mut self, align: StatsAlign) -> Self {
+        self.align = align;
+        self
+    }
+
     /// Renders the statistics box as a formatted string.
     pub fn render(&self) -> String {
-        FormattedBox::new("Content Statistics")
-            .row(
-                "📝 Characters:",
-                utils::format_number(self.chars as i64)
-                    .bright_white()
-                    .to_string(),
-            )
-            .row(
-                "📄 Lines:",
-                utils::format_number(self.lines as i64)
-                    .bright_white()
-                    .to_string(),
-            )
-            .row(
-                "💬 Words:",
-                utils::format_number(self.words as i64)
-                    .bright_white()
-                    .to_string(),
-            )
-            .row(
-                "💾 Size:",
-                utils::format_bytes(self.bytes).bright_white().to_string(),
-            )
-            .render()
+        self.render_with(true)
+    }
+
+    /// Renders the statistics box without ANSI colors, for writing into files.
+    pub fn render_plain(&self) -> String {
+        self.render_with(false)
     }
 
     /// Returns an emoji and message based on file size.
@@ -128,6 +172,192 @@ impl StatsBox {
     }
 }
 
+/// SlocBox displays source lines of code per language in a formatted box (`--sloc`).
+pub struct SlocBox {
+    /// Language and line count, most lines first.
+    rows: Vec<(String, usize)>,
+    align: StatsAlign,
+}
+
+impl SlocBox {
+    /// Creates a SlocBox from per-language counts.
+    pub fn new(counts: &BTreeMap<String, usize>) -> Self {
+        let mut rows: Vec<(String, usize)> = counts
+            .iter()
+            .map(|(language, lines)| (language.clone(), *lines))
+            .collect();
+        // Ties keep alphabetical order, as the sort is stable
+        rows.sort_by_key(|(_, lines)| std::cmp::Reverse(*lines));
+        Self {
+            rows,
+            align: StatsAlign::default(),
+        }
+    }
+
+    /// Sets the alignment of the values (builder pattern).
+    pub fn align(mut self, align: StatsAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Renders the box, with a total row when there is more than one language.
+    pub fn render(&self) -> String {
+        let mut sloc_box =
+            FormattedBox::new("Source Lines of Code (approx.)").value_align(match self.align {
+                StatsAlign::Left => Align::Left,
+                StatsAlign::Right => Align::Right,
+            });
+
+        for (language, lines) in &self.rows {
+            sloc_box = sloc_box.row(
+                format!("{language}:"),
+                utils::format_number(*lines as i64)
+                    .bright_white()
+                    .to_string(),
+            );
+        }
+        if self.rows.len() > 1 {
+            let total: usize = self.rows.iter().map(|(_, lines)| lines).sum();
+            sloc_box = sloc_box.row(
+                "Σ Total:",
+                utils::format_number(total as i64)
+                    .bright_white()
+                    .to_string(),
+            );
+        }
+
+        sloc_box.render()
+    }
+}
+
+/// LanguageChart shows each language's share of the content bytes as horizontal bars
+/// (`--language-chart`).
+pub struct LanguageChart {
+    /// Language and bytes, most bytes first.
+    rows: Vec<(String, usize)>,
+    /// Total width in columns; `None` uses the terminal width.
+    width: Option<usize>,
+    /// Draw `#` bars without color instead of colored block characters.
+    ascii: bool,
+}
+
+impl LanguageChart {
+    /// Creates a chart from per-language byte counts.
+    ///
+    /// Bars are plain ASCII when colors are disabled (NO_COLOR, not a terminal).
+    pub fn new(bytes: &BTreeMap<String, usize>) -> Self {
+        let mut rows: Vec<(String, usize)> = bytes
+            .iter()
+            .map(|(language, bytes)| (language.clone(), *bytes))
+            .collect();
+        rows.sort_by_key(|(_, bytes)| std::cmp::Reverse(*bytes));
+        Self {
+            rows,
+            width: None,
+            ascii: !colored::control::SHOULD_COLORIZE.should_colorize(),
+        }
+    }
+
+    /// Sets the total width, overriding terminal detection (builder pattern).
+    #[cfg(test)]
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Chooses ASCII bars without color (builder pattern).
+    #[cfg(test)]
+    pub fn ascii(mut self, ascii: bool) -> Self {
+        self.ascii = ascii;
+        self
+    }
+
+    /// Renders a title line and one bar per language.
+    pub fn render(&self) -> String {
+        let total: usize = self.rows.iter().map(|(_, bytes)| bytes).sum();
+        let label_width = self
+            .rows
+            .iter()
+            .map(|(language, _)| language.len())
+            .max()
+            .unwrap_or(0);
+        let width = self.width.unwrap_or_else(|| {
+            terminal_size()
+                .map(|(Width(w), _)| w as usize)
+                .unwrap_or(CHART_DEFAULT_WIDTH)
+        });
+        // Two columns of indent, a space either side of the bar and `100.0%`
+        let bar_width = width
+            .saturating_sub(label_width + 10)
+            .clamp(CHART_MIN_BAR_WIDTH, CHART_MAX_BAR_WIDTH);
+
+        let mut chart = format!("{} {}", "📊", "Language Breakdown (by bytes)".bold());
+        for (language, bytes) in &self.rows {
+            let share = if total == 0 {
+                0.0
+            } else {
+                *bytes as f64 / total as f64
+            };
+            let bar = if self.ascii {
+                "#".repeat((share * bar_width as f64).round() as usize)
+            } else {
+                block_bar(share, bar_width)
+            };
+            let padding = " ".repeat(bar_width.saturating_sub(bar.chars().count()));
+            let bar = if self.ascii {
+                bar
+            } else {
+                bar.bright_cyan().to_string()
+            };
+            chart.push_str(&format!(
+                "\n  {language:<label_width$} {bar}{padding} {:>5.1}%",
+                share * 100.0
+            ));
+        }
+        chart
+    }
+}
+
+// -------------------------------------------- Private Helper Functions --------------------------------------------
+
+/// A bar of `share` times `width` columns in eighth-block characters.
+fn block_bar(share: f64, width: usize) -> String {
+    const PARTIAL: [&str; 8] = ["", "▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+    let eighths = (share * width as f64 * 8.0).round() as usize;
+    format!("{}{}", "█".repeat(eighths / 8), PARTIAL[eighths % 8])
+}
+
+impl StatsBox {
+    /// Builds and renders the box, with or without colored values.
+    fn render_with(&self, color: bool) -> String {
+        let mut stats_box = FormattedBox::new("Content Statistics").value_align(match self.align {
+            StatsAlign::Left => Align::Left,
+            StatsAlign::Right => Align::Right,
+        });
+
+        for field in StatsField::ALL {
+            if !self.fields.contains(&field) {
+                continue;
+            }
+
+            let (label, value) = match field {
+                StatsField::Chars => ("📝 Characters:", utils::format_number(self.chars as i64)),
+                StatsField::Lines => ("📄 Lines:", utils::format_number(self.lines as i64)),
+                StatsField::Words => ("💬 Words:", utils::format_number(self.words as i64)),
+                StatsField::Size => ("💾 Size:", utils::format_bytes(self.bytes)),
+            };
+            let value = if color {
+                value.bright_white().to_string()
+            } else {
+                value
+            };
+            stats_box = stats_box.row(label, value);
+        }
+
+        stats_box.render()
+    }
+}
+
 #[cfg(test)]
 mod formatter_tests {
     use super::*;
@@ -203,6 +433,66 @@ mod formatter_tests {
         assert!(rendered.contains("Size:"));
     }
 
+    #[test]
+    fn test_stats_box_plain_render() {
+        let stats = StatsBox::new(2, 13, 3, 13);
+        let plain = stats.render_plain();
+
+        assert!(!plain.contains('\u{1b}'));
+        assert!(plain.contains("Content Statistics"));
+        assert!(plain.contains("13 B"));
+    }
+
+    #[test]
+    fn test_stats_box_render_selected_fields() {
+        let stats =
+            StatsBox::new(1, 100, 1_000, 1_000_000).fields(&[StatsField::Size, StatsField::Lines]);
+        let rendered = stats.render();
+
+        assert!(rendered.contains("Lines:"));
+        assert!(rendered.contains("Size:"));
+        assert!(!rendered.contains("Characters:"));
+        assert!(!rendered.contains("Words:"));
+    }
+
+    #[test]
+    fn test_sloc_box_orders_by_lines_with_total() {
+        let counts = BTreeMap::from([("python".to_string(), 20), ("rust".to_string(), 1_500)]);
+        let rendered = SlocBox::new(&counts).render();
+
+        let rust = rendered.find("rust:").unwrap();
+        let python = rendered.find("python:").unwrap();
+        assert!(rust < python);
+        assert!(rendered.contains("1,520"));
+    }
+
+    #[test]
+    fn test_language_chart_scales_bars_to_width() {
+        let bytes = BTreeMap::from([("python".to_string(), 250), ("rust".to_string(), 750)]);
+
+        let chart = LanguageChart::new(&bytes).width(36).ascii(true).render();
+        let lines: Vec<&str> = chart.lines().collect();
+        // 36 columns leave 20 for bars
+        assert_eq!(
+            lines[1],
+            format!("  rust   {}  75.0%", "#".repeat(15) + "     ")
+        );
+        assert_eq!(
+            lines[2],
+            format!("  python {}  25.0%", "#".repeat(5) + &" ".repeat(15))
+        );
+
+        let wide = LanguageChart::new(&bytes).width(66).ascii(true).render();
+        assert!(wide.contains(&"#".repeat(38)));
+    }
+
+    #[test]
+    fn test_block_bar_uses_partial_blocks() {
+        assert_eq!(block_bar(0.5, 3), "█▌");
+        assert_eq!(block_bar(1.0, 2), "██");
+        assert_eq!(block_bar(0.0, 5), "");
+    }
+
     #[test]
     fn test_get_size_message_tiny() {
         let stats = StatsBox::new(1, 10, 2, 500);
@@ -52,6 +52,250 @@ impl Messages {
         )
     }
 
+    /// Returns the stats note about files skipped as generated code.
+    pub fn skipped_generated(count: usize) -> String {
+        format!(
+            "  {} {}",
+            "🤖".yellow(),
+            format!("Skipped {count} generated files").dimmed()
+        )
+    }
+
+    /// Returns the stats note about files skipped as already in an earlier output.
+    pub fn skipped_shared(count: usize, previous: &str) -> String {
+        format!(
+            "  {} {}",
+            "📦".yellow(),
+            format!("Skipped {count} files already in {previous}").dimmed()
+        )
+    }
+
+    /// Returns the note on how the run compared with the `--baseline` directory.
+    pub fn baseline_compared(
+        added: usize,
+        changed: usize,
+        unchanged: usize,
+        baseline: &str,
+    ) -> String {
+        format!(
+            "{} {}",
+            "🔍".bright_cyan(),
+            format!(
+                "Against {baseline}: {added} added, {changed} changed, {unchanged} unchanged left out"
+            )
+            .bright_cyan()
+        )
+    }
+
+    /// Returns the stats note about files over their `--max-file-size-by-ext` limit.
+    pub fn skipped_oversized(count: usize) -> String {
+        format!(
+            "  {} {}",
+            "📏".yellow(),
+            format!("Skipped {count} files over their extension's size limit").dimmed()
+        )
+    }
+
+    /// Returns the stats note about symlinks skipped with `--exclude-symlinks`.
+    pub fn skipped_symlinks(count: usize) -> String {
+        format!(
+            "  {} {}",
+            "🔗".yellow(),
+            format!("Skipped {count} symlinks").dimmed()
+        )
+    }
+
+    /// Returns the stats note about files skipped as minified.
+    pub fn skipped_minified(count: usize) -> String {
+        format!(
+            "  {} {}",
+            "🗜️".yellow(),
+            format!("Skipped {count} minified files").dimmed()
+        )
+    }
+
+    /// Returns the verbose note about how many paths `--mask-paths` rewrote.
+    pub fn masked_paths(count: usize) -> String {
+        format!(
+            "{} {}",
+            "🎭".cyan(),
+            format!("Masked {count} paths").bright_cyan()
+        )
+    }
+
+    /// Returns the note about files one directory lost to `--max-files-per-dir`.
+    pub fn omitted_in_dir(dir: &str, count: usize) -> String {
+        format!(
+            "  {} {}",
+            "✂️".yellow(),
+            format!("{dir}: ... ({count} more files in this dir omitted)").dimmed()
+        )
+    }
+
+    /// Returns the summary of files kept and dropped by the content filters.
+    pub fn content_filtered(matched: usize, skipped: usize) -> String {
+        format!(
+            "  {} {}",
+            "🔎".cyan(),
+            format!("{matched} files matched the content filters, {skipped} skipped").dimmed()
+        )
+    }
+
+    /// Returns the heading of the report of files that could not be read.
+    pub fn read_errors(count: usize) -> String {
+        format!(
+            "{} {}",
+            "⚠️".yellow(),
+            format!("{count} files could not be read and were left out:").yellow()
+        )
+    }
+
+    /// Returns the warning about a symlink leading back to one of its ancestors.
+    pub fn symlink_loop(path: &str) -> String {
+        format!(
+            "{} {}",
+            "⚠️".yellow(),
+            format!("Symlink loop detected at: {path}").yellow()
+        )
+    }
+
+    /// Returns the warning for a followed symlink whose target does not exist.
+    pub fn dangling_symlink(path: &str) -> String {
+        format!(
+            "{} {}",
+            "⚠️".yellow(),
+            format!("Skipped dangling symlink: {path}").yellow()
+        )
+    }
+
+    /// Returns the heading of the list of directories pruned by `--exclude-large-dirs`.
+    pub fn pruned_dirs(count: usize, threshold: usize) -> String {
+        format!(
+            "{} {}",
+            "⚠️".yellow(),
+            format!("Pruned {count} directories with more than {threshold} entries:").yellow()
+        )
+    }
+
+    /// Returns the warning printed when `--clipboard-html` has no markdown to render.
+    pub fn clipboard_html_needs_markdown() -> String {
+        format!(
+            "{} {}",
+            "⚠️".yellow(),
+            "--clipboard-html needs markdown on the clipboard; copied plain text instead.".yellow()
+        )
+    }
+
+    /// Returns the warning printed when `--page` could not show the output.
+    pub fn pager_failed(reason: &str) -> String {
+        format!(
+            "{} {}",
+            "⚠️".yellow(),
+            format!("Could not page the output: {reason}").yellow()
+        )
+    }
+
+    /// Returns the warning printed when `--timeout` cut the run short.
+    pub fn timeout_reached(seconds: u64) -> String {
+        format!(
+            "{} {}",
+            "⏱️".yellow(),
+            format!("Timed out after {seconds}s; the output holds what was collected so far")
+                .yellow()
+        )
+    }
+
+    /// Returns the note on how much `--collapse-whitespace-runs` removed.
+    pub fn whitespace_collapsed(bytes: &str) -> String {
+        format!(
+            "{} {}",
+            "🗜️".bright_cyan(),
+            format!("Collapsing whitespace saved {bytes}").bright_cyan()
+        )
+    }
+
+    /// Returns the warning heading the indentation-sensitive files that had whitespace
+    /// collapsed inside their lines.
+    pub fn whitespace_collapsed_sensitive(count: usize) -> String {
+        format!(
+            "{} {}",
+            "⚠️".yellow(),
+            format!(
+                "Collapsed whitespace inside lines of {count} indentation-sensitive files (indentation kept):"
+            )
+            .yellow()
+        )
+    }
+
+    /// Returns the verbose note on how many substitutions `--content-replace` made.
+    pub fn content_replaced(count: usize) -> String {
+        format!(
+            "{} {}",
+            "🔁".bright_cyan(),
+            format!("Made {count} content replacements").bright_cyan()
+        )
+    }
+
+    /// Returns the error printed when fewer files matched than `--min-files` asks for.
+    pub fn too_few_files(found: usize, min_files: usize) -> String {
+        format!(
+            "{} {}",
+            "❌".red(),
+            format!("Only {found} files matched, but --min-files requires {min_files}").red()
+        )
+    }
+
+    /// Returns the stats note for `--no-content` output.
+    pub fn structure_only() -> String {
+        format!(
+            "  {} {}",
+            "🌲".green(),
+            "Structure only: the numbers above are the tree alone".dimmed()
+        )
+    }
+
+    /// Returns the stats note about lines cut by `--max-line-length`.
+    pub fn truncated_lines(lines: usize, files: usize) -> String {
+        format!(
+            "  {} {}",
+            "✂️".yellow(),
+            format!("Truncated {lines} long lines in {files} files").dimmed()
+        )
+    }
+
+    /// Returns the message shown when clipboard content was split at the limit.
+    pub fn clipboard_split(rest_path: &str) -> String {
+        format!(
+            "  {} {} {}",
+            "✂️".yellow(),
+            "Output exceeded the clipboard limit; the rest was saved to".yellow(),
+            rest_path.bright_white()
+        )
+    }
+
+    /// Returns the warning shown before a large copy on Wayland.
+    pub fn wayland_clipboard_warning(size: &str, output: &str) -> String {
+        format!(
+            "{} {}\n   {} {}",
+            "⚠️".yellow(),
+            format!("Copying {size} on Wayland; the compositor may reject or truncate it.")
+                .yellow(),
+            "If the paste comes up short, use the output file instead:".dimmed(),
+            output.bright_white()
+        )
+    }
+
+    /// Returns the warning that no clipboard manager will keep the copy after exit.
+    pub fn clipboard_may_not_persist(output: &str) -> String {
+        format!(
+            "{} {}\n   {} {}",
+            "⚠️".yellow(),
+            "No clipboard manager found; the copy may be gone once treeclip exits.".yellow(),
+            "If nothing pastes, use the output file instead:".dimmed(),
+            output.bright_white()
+        )
+    }
+
     /// Returns the clipboard skipped message.
     pub fn clipboard_skipped() -> String {
         format!(
@@ -74,6 +318,69 @@ impl Messages {
         "👀 Hope you like what you see!".bright_cyan().to_string()
     }
 
+    /// Returns the prompt asking whether to overwrite an existing output file.
+    pub fn confirm_overwrite(path: &str) -> String {
+        format!(
+            "{} {} ",
+            "⚠️".yellow(),
+            format!("{path} already exists. Overwrite it? [y/N]").yellow()
+        )
+    }
+
+    /// Returns the summary of the parts written with `--split-size`.
+    pub fn split_parts(count: usize, first: &str) -> String {
+        format!(
+            "{} {}",
+            "✂️".bright_cyan(),
+            format!("Output split into {count} parts, starting with {first}").bright_cyan()
+        )
+    }
+
+    /// Returns the note that only the first part went to the clipboard.
+    pub fn clipboard_first_part(count: usize) -> String {
+        format!(
+            "{} {}",
+            "⚠️".yellow(),
+            format!("Only part 1 of {count} was copied to the clipboard").yellow()
+        )
+    }
+
+    /// Returns the warning that an option needing an output file is off for `-o -`.
+    pub fn ignored_for_stdout(flag: &str) -> String {
+        format!(
+            "{} {}",
+            "⚠️".yellow(),
+            format!("{flag} needs an output file and is ignored with --output-path -").yellow()
+        )
+    }
+
+    /// Returns the warning that a desktop launcher may not have loaded the file before `--delete`.
+    pub fn delete_without_wait() -> String {
+        format!(
+            "{} {}",
+            "⚠️".yellow(),
+            "The editor may not have loaded the file before it is deleted; add --wait to be sure"
+                .yellow()
+        )
+    }
+
+    /// Returns the header of the `--extensions-report` listing.
+    pub fn unrecognized_extensions(count: usize) -> String {
+        if count == 0 {
+            format!(
+                "{} {}",
+                "🏷️".green(),
+                "Every extension mapped to a fence language".green()
+            )
+        } else {
+            format!(
+                "{} {}",
+                "🏷️".yellow(),
+                format!("{count} extensions have no fence language:").yellow()
+            )
+        }
+    }
+
     /// Returns the cleaning up message.
     pub fn cleaning_up() -> String {
         "🗑️  Cleaning up after the party..."
@@ -89,6 +396,42 @@ impl Messages {
             .to_string()
     }
 
+    /// Returns the message that `--watch-clipboard-only` is waiting for changes.
+    pub fn watching_for_changes() -> String {
+        format!(
+            "{} {}",
+            "👀".bright_cyan(),
+            "Watching for changes, press Ctrl-C to stop".bright_cyan()
+        )
+    }
+
+    /// Returns the message that a `--watch-clipboard-only` copy finished.
+    pub fn watch_copied(files: usize) -> String {
+        format!(
+            "{} {}",
+            "📋".bright_green(),
+            format!("Copied {files} files to the clipboard").bright_green()
+        )
+    }
+
+    /// Returns the warning that a `--watch-clipboard-only` refresh failed.
+    pub fn watch_refresh_failed(error: &str) -> String {
+        format!(
+            "{} {}",
+            "⚠️".yellow(),
+            format!("Refresh failed, still watching: {error}").yellow()
+        )
+    }
+
+    /// Returns the warning that `--cleanup` left a user-named output in place.
+    pub fn cleanup_kept_named_output(path: &str) -> String {
+        format!(
+            "{} {}",
+            "⚠️".yellow(),
+            format!("Kept {path}: --cleanup only removes the default treeclip_temp.txt").yellow()
+        )
+    }
+
     /// Returns the showing stats message.
     pub fn showing_stats() -> String {
         "📊 Let's see what we've collected!"
@@ -106,6 +449,68 @@ impl Messages {
         )
     }
 
+    /// Returns the message shown after saving the list of included files.
+    pub fn input_list_written(count: usize, path: &str) -> String {
+        format!(
+            "{} {} {}",
+            "🧾".green(),
+            format!("Saved the list of {count} included files to").bright_green(),
+            path.bright_white()
+        )
+    }
+
+    /// Returns the heading printed above the `--preview` lines.
+    pub fn preview_header(lines: usize) -> String {
+        format!(
+            "{} {}",
+            "👀".cyan(),
+            format!("First {lines} lines of the output:").bright_cyan()
+        )
+    }
+
+    /// Returns the note printed when the output continues past the preview.
+    pub fn preview_truncated() -> String {
+        "  … (output continues)".dimmed().to_string()
+    }
+
+    /// Returns the output digest message.
+    pub fn output_hash(digest: &str) -> String {
+        format!(
+            "{} {} {}",
+            "🔏".green(),
+            "SHA-256:".bright_cyan(),
+            digest.bright_white()
+        )
+    }
+
+    /// Returns the message shown after writing the hash sidecar file.
+    pub fn hash_sidecar_written(path: &str) -> String {
+        format!("  {} {}", "Digest saved to".dimmed(), path.bright_white())
+    }
+
+    // -------------------- Unpack Messages --------------------
+
+    /// Returns the reading from clipboard message.
+    pub fn reading_clipboard() -> String {
+        "📋 Reading treeclip output from clipboard..."
+            .bright_cyan()
+            .to_string()
+    }
+
+    /// Returns a formatted message for a restored file.
+    pub fn unpacked_file(path: &str) -> String {
+        format!("  {} {}", "📄".green(), path.bright_white())
+    }
+
+    /// Returns the unpack complete message.
+    pub fn unpack_complete(count: usize, dir: &str) -> String {
+        format!(
+            "\n{} {}",
+            "📦".green(),
+            format!("Unpacked {count} files into {dir}").bright_green()
+        )
+    }
+
     // -------------------- Ignore File Messages --------------------
 
     /// Returns a formatted message for finding an ignore file.
@@ -121,9 +526,26 @@ impl Messages {
 
     /// Returns the applying ignore rules message.
     pub fn applying_ignore_rules() -> String {
-        "  📝 Applying rules from .treeclipignore"
-            .dimmed()
-            .to_string()
+        "  📝 Applying rules from ignore files".dimmed().to_string()
+    }
+
+    /// Returns the warning that `--relative-to-git-root` found no repository above `root`.
+    pub fn not_in_git_repo(root: &str) -> String {
+        format!(
+            "{} {}",
+            "⚠️".yellow(),
+            format!("Not inside a git repository: {root}; paths stay relative to the root")
+                .yellow()
+        )
+    }
+
+    /// Returns the warning for an ignore file that was skipped because it could not be used.
+    pub fn skipped_ignore_file(reason: &str) -> String {
+        format!(
+            "{} {}",
+            "⚠️".yellow(),
+            format!("Skipping ignore file: {reason}").yellow()
+        )
     }
 }
 
@@ -148,14 +570,78 @@ mod messages_tests {
         assert!(!Messages::copying_clipboard().is_empty());
         assert!(!Messages::clipboard_ready().is_empty());
         assert!(!Messages::clipboard_skipped().is_empty());
+        assert!(Messages::clipboard_split("out.rest.txt").contains("out.rest.txt"));
+        assert!(Messages::skipped_generated(2).contains("2 generated"));
+        assert!(Messages::skipped_minified(2).contains("2 minified files"));
+        assert!(Messages::skipped_symlinks(3).contains("3 symlinks"));
+        assert!(Messages::cleanup_kept_named_output("out.txt").contains("Kept out.txt"));
+        assert!(Messages::skipped_shared(4, "old.txt").contains("4 files already in old.txt"));
+        assert!(Messages::skipped_oversized(2).contains("Skipped 2 files over"));
+        assert!(Messages::masked_paths(2).contains("Masked 2 paths"));
+        assert!(
+            Messages::omitted_in_dir("data", 4)
+                .contains("data: ... (4 more files in this dir omitted)")
+        );
+        assert!(Messages::truncated_lines(3, 1).contains("3 long lines in 1 files"));
+        assert!(Messages::structure_only().contains("Structure only"));
+        assert!(Messages::timeout_reached(30).contains("after 30s"));
+        assert!(Messages::read_errors(3).contains("3 files could not be read"));
+        assert!(Messages::whitespace_collapsed("1.2 KB").contains("saved 1.2 KB"));
+        assert!(Messages::whitespace_collapsed_sensitive(2).contains("lines of 2 indentation"));
+        assert!(Messages::content_replaced(4).contains("Made 4 content replacements"));
+        assert!(
+            Messages::too_few_files(2, 10)
+                .contains("Only 2 files matched, but --min-files requires 10")
+        );
+        assert!(
+            Messages::pager_failed("not found").contains("Could not page the output: not found")
+        );
+        assert!(
+            Messages::baseline_compared(1, 2, 3, "../main")
+                .contains("Against ../main: 1 added, 2 changed, 3 unchanged left out")
+        );
+        assert!(Messages::clipboard_html_needs_markdown().contains("copied plain text"));
+        assert!(Messages::symlink_loop("src/back").contains("Symlink loop detected at: src/back"));
+        assert!(Messages::dangling_symlink("src/gone").contains("dangling symlink: src/gone"));
+        assert!(
+            Messages::pruned_dirs(2, 1000).contains("Pruned 2 directories with more than 1000")
+        );
+        assert!(Messages::wayland_clipboard_warning("5.0 MB", "out.txt").contains("5.0 MB"));
+        assert!(Messages::clipboard_may_not_persist("out.txt").contains("out.txt"));
         assert!(!Messages::opening_editor().is_empty());
         assert!(!Messages::editor_opened().is_empty());
         assert!(!Messages::cleaning_up().is_empty());
+        assert!(!Messages::delete_without_wait().is_empty());
+        assert!(Messages::split_parts(3, "out.001.txt").contains("3 parts"));
+        assert!(Messages::clipboard_first_part(3).contains("part 1 of 3"));
+        assert!(Messages::ignored_for_stdout("--editor").contains("--editor needs"));
+        assert!(Messages::confirm_overwrite("notes.txt").contains("notes.txt already exists"));
+        assert!(Messages::content_filtered(3, 2).contains("3 files matched"));
+        assert!(Messages::unrecognized_extensions(2).contains("2 extensions"));
         assert!(!Messages::cleaned_up().is_empty());
+        assert!(Messages::watching_for_changes().contains("Ctrl-C"));
+        assert!(Messages::watch_copied(3).contains("Copied 3 files"));
+        assert!(Messages::watch_refresh_failed("gone").contains("still watching: gone"));
         assert!(!Messages::showing_stats().is_empty());
         assert!(!Messages::ready_to_launch().is_empty());
     }
 
+    #[test]
+    fn test_output_hash_messages() {
+        assert!(Messages::output_hash("abc123").contains("abc123"));
+        assert!(Messages::input_list_written(4, "sel.txt").contains("4 included"));
+        assert!(Messages::hash_sidecar_written("out.txt.sha256").contains("out.txt.sha256"));
+        assert!(Messages::preview_header(20).contains("First 20 lines"));
+        assert!(!Messages::preview_truncated().is_empty());
+    }
+
+    #[test]
+    fn test_unpack_messages() {
+        assert!(!Messages::reading_clipboard().is_empty());
+        assert!(Messages::unpacked_file("src/main.rs").contains("src/main.rs"));
+        assert!(Messages::unpack_complete(3, "./out").contains("3 files"));
+    }
+
     #[test]
     fn test_ignore_file_messages() {
         let path = "/home/user/.treeclipignore";
@@ -164,6 +650,8 @@ mod messages_tests {
         assert!(!message.is_empty());
 
         assert!(!Messages::applying_ignore_rules().is_empty());
+        assert!(Messages::not_in_git_repo("/tmp/x").contains("Not inside a git repository"));
+        assert!(Messages::skipped_ignore_file("gone").contains("Skipping ignore file: gone"));
     }
 
     #[test]
@@ -32,7 +32,14 @@
 //! └──────────────────────────────────────────────────┘
 //! ```
 
-use unicode_width::UnicodeWidthStr;
+use terminal_size::{Width, terminal_size};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Fallback width used when the output is not a terminal.
+const DEFAULT_TERMINAL_WIDTH: usize = 80;
+
+/// Smallest total width of a statistics box, including borders.
+const STATS_MIN_WIDTH: usize = 52;
 
 // -------------------------------------------- Public Structs and Enums --------------------------------------------
 
@@ -63,9 +70,9 @@ pub enum BorderStyle {
 /// Text alignment options.
 #[derive(Clone, Copy)]
 pub enum Align {
-    #[allow(dead_code)]
     Left,
     Center,
+    Right,
 }
 
 /// Theme configuration for box appearance.
@@ -74,6 +81,10 @@ pub struct BoxTheme {
     pub padding: usize,
     pub border: BorderStyle,
     pub align: Align,
+    /// Alignment of the values in a statistics box.
+    pub value_align: Align,
+    /// Maximum total box width including borders. `None` uses the terminal width.
+    pub max_width: Option<usize>,
 }
 
 impl Default for BoxTheme {
@@ -82,6 +93,8 @@ impl Default for BoxTheme {
             padding: 2,
             border: BorderStyle::Sharp,
             align: Align::Center,
+            value_align: Align::Right,
+            max_width: None,
         }
     }
 }
@@ -94,6 +107,9 @@ struct BorderChars {
     bottom_right: &'static str,
     h: &'static str,
     v: &'static str,
+    /// Left and right ends of a horizontal separator.
+    left_tee: &'static str,
+    right_tee: &'static str,
 }
 
 impl FormattedBox {
@@ -146,6 +162,19 @@ impl FormattedBox {
         self
     }
 
+    /// Sets the alignment of statistics values (builder pattern).
+    pub fn value_align(mut self, align: Align) -> Self {
+        self.theme.value_align = align;
+        self
+    }
+
+    /// Sets the maximum total box width, overriding terminal detection (builder pattern).
+    #[cfg(test)]
+    pub fn max_width(mut self, width: usize) -> Self {
+        self.theme.max_width = Some(width);
+        self
+    }
+
     /// Renders the box as a formatted string.
     pub fn render(&self) -> String {
         let is_stats = self.rows.iter().any(|r| matches!(r, RowKind::Stat { .. }));
@@ -161,48 +190,99 @@ impl FormattedBox {
 // -------------------------------------------- Private Helper Functions --------------------------------------------
 
 impl FormattedBox {
-    /// Renders a statistics-style box with fixed width.
+    /// Renders a statistics-style box.
+    ///
+    /// Columns are sized to the longest label and value, and the box is never narrower
+    /// than [`STATS_MIN_WIDTH`]; any extra room goes to the value column.
     fn render_stats_box(&self) -> String {
+        let border = border_chars(self.theme.border);
+        let pad = self.theme.padding;
+
+        let stats: Vec<(&str, &str)> = self
+            .rows
+            .iter()
+            .filter_map(|row| match row {
+                RowKind::Stat { label, value } => Some((label.as_str(), value.as_str())),
+                RowKind::Message(_) => None,
+            })
+            .collect();
+        let label_width = stats
+            .iter()
+            .map(|(label, _)| visible_width(label))
+            .max()
+            .unwrap_or(0);
+        let value_width = stats
+            .iter()
+            .map(|(_, value)| visible_width(value))
+            .max()
+            .unwrap_or(0);
+
+        // Label and value are separated by the padding, like the borders
+        let title_width = UnicodeWidthStr::width(self.title.as_str());
+        let inner_width = (pad * 3 + label_width + value_width)
+            .max(pad * 2 + title_width)
+            .max(STATS_MIN_WIDTH - 2);
+        let value_width = inner_width - pad * 3 - label_width;
+
         let mut out = String::new();
 
         // Top border
-        out.push_str("┌──────────────────────────────────────────────────┐\n");
-
-        // Title (centered)
-        let title_width = UnicodeWidthStr::width(self.title.as_str());
-        let total_width = 51;
-        let padding = (total_width - title_width) / 2;
+        out.push_str(&format!(
+            "{}{}{}\n",
+            border.top_left,
+            border.h.repeat(inner_width),
+            border.top_right
+        ));
 
+        // Title
+        let title = match self.theme.align {
+            Align::Left => format!("{}{}", " ".repeat(pad), self.title),
+            Align::Center => self.title.clone(),
+            Align::Right => format!("{}{}", self.title, " ".repeat(pad)),
+        };
         out.push_str(&format!(
-            "│{}{}{}│\n",
-            " ".repeat(padding),
-            self.title,
-            " ".repeat(total_width - padding - title_width - 1)
+            "{}{}{}\n",
+            border.v,
+            align_text(&title, inner_width, self.theme.align),
+            border.v
         ));
 
         // Separator
-        out.push_str("├──────────────────────────────────────────────────┤\n");
+        out.push_str(&format!(
+            "{}{}{}\n",
+            border.left_tee,
+            border.h.repeat(inner_width),
+            border.right_tee
+        ));
 
         // Rows
-        let label_width = 18;
-        let value_width = 25;
-
-        for row in &self.rows {
-            if let RowKind::Stat { label, value } = row {
-                out.push_str(&format!(
-                    "│  {}  {}  │\n",
-                    pad_left(label, label_width),
-                    pad_right(value, value_width + 1)
-                ));
-            }
+        let gap = " ".repeat(pad);
+        for (label, value) in stats {
+            let value = match self.theme.value_align {
+                Align::Left => pad_left(value, value_width),
+                Align::Center => align_text(value, value_width, Align::Center),
+                Align::Right => pad_right(value, value_width),
+            };
+            out.push_str(&format!(
+                "{}{gap}{}{gap}{}{gap}{}\n",
+                border.v,
+                pad_left(label, label_width),
+                value,
+                border.v
+            ));
         }
 
         // Bottom border
-        out.push_str("└──────────────────────────────────────────────────┘");
+        out.push_str(&format!(
+            "{}{}{}",
+            border.bottom_left,
+            border.h.repeat(inner_width),
+            border.bottom_right
+        ));
         out
     }
 
-    /// Renders a message-style box with dynamic width.
+    /// Renders a message-style box with dynamic width, capped to the available width.
     fn render_message_box(&self) -> String {
         let border = border_chars(self.theme.border);
         let pad = self.theme.padding;
@@ -215,7 +295,10 @@ impl FormattedBox {
             }
         }
 
-        let inner_width = max_width + pad * 2;
+        // Two columns are taken by the vertical borders
+        let available = self.available_width().saturating_sub(2).max(pad * 2 + 1);
+        let inner_width = (max_width + pad * 2).min(available);
+        let text_width = inner_width - pad;
 
         let mut out = String::new();
 
@@ -232,7 +315,11 @@ impl FormattedBox {
             "{}{}{}\n",
             border.v,
             align_text(
-                &format!("{}{}", " ".repeat(pad), self.title),
+                &format!(
+                    "{}{}",
+                    " ".repeat(pad),
+                    truncate_to_width(&self.title, text_width)
+                ),
                 inner_width,
                 self.theme.align
             ),
@@ -242,7 +329,7 @@ impl FormattedBox {
         // Message lines
         for row in &self.rows {
             if let RowKind::Message(line) = row {
-                let content = format!("{}{}", " ".repeat(pad), line);
+                let content = format!("{}{}", " ".repeat(pad), truncate_to_width(line, text_width));
                 out.push_str(&format!(
                     "{}{}{}\n",
                     border.v,
@@ -262,6 +349,18 @@ impl FormattedBox {
 
         out
     }
+
+    /// Returns the total width the box may occupy.
+    ///
+    /// Uses the theme's `max_width` when set, otherwise the current terminal width,
+    /// falling back to a fixed default when stdout is not a TTY.
+    fn available_width(&self) -> usize {
+        self.theme.max_width.unwrap_or_else(|| {
+            terminal_size()
+                .map(|(Width(w), _)| w as usize)
+                .unwrap_or(DEFAULT_TERMINAL_WIDTH)
+        })
+    }
 }
 
 /// Returns border characters for the specified style.
@@ -274,6 +373,8 @@ fn border_chars(style: BorderStyle) -> BorderChars {
             bottom_right: "┘",
             h: "─",
             v: "│",
+            left_tee: "├",
+            right_tee: "┤",
         },
         BorderStyle::Rounded => BorderChars {
             top_left: "╭",
@@ -282,6 +383,8 @@ fn border_chars(style: BorderStyle) -> BorderChars {
             bottom_right: "╯",
             h: "─",
             v: "│",
+            left_tee: "├",
+            right_tee: "┤",
         },
         BorderStyle::Double => BorderChars {
             top_left: "╔",
@@ -290,22 +393,62 @@ fn border_chars(style: BorderStyle) -> BorderChars {
             bottom_right: "╝",
             h: "═",
             v: "║",
+            left_tee: "╠",
+            right_tee: "╣",
         },
     }
 }
 
 /// Left-pads a string to the specified visible width.
 fn pad_left(s: &str, width: usize) -> String {
-    let w = UnicodeWidthStr::width(s);
+    let w = visible_width(s);
     format!("{}{}", s, " ".repeat(width.saturating_sub(w)))
 }
 
 /// Right-pads a string to the specified visible width.
 fn pad_right(s: &str, width: usize) -> String {
-    let w = UnicodeWidthStr::width(s);
+    let w = visible_width(s);
     format!("{}{}", " ".repeat(width.saturating_sub(w)), s)
 }
 
+/// Visible width of a string, ignoring ANSI color sequences such as `\x1b[97m`.
+fn visible_width(s: &str) -> usize {
+    let mut width = 0;
+    let mut chars = s.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '\x1b' {
+            // Skip up to and including the final letter of the sequence
+            chars.by_ref().find(char::is_ascii_alphabetic);
+        } else {
+            width += UnicodeWidthChar::width(ch).unwrap_or(0);
+        }
+    }
+    width
+}
+
+/// Truncates a string to the specified visible width, ending with an ellipsis if shortened.
+pub fn truncate_to_width(s: &str, width: usize) -> String {
+    if UnicodeWidthStr::width(s) <= width {
+        return s.to_string();
+    }
+
+    let mut out = String::new();
+    let mut used = 0;
+    for ch in s.chars() {
+        let w = UnicodeWidthChar::width(ch).unwrap_or(0);
+        // Reserve one column for the ellipsis
+        if used + w + 1 > width {
+            break;
+        }
+        out.push(ch);
+        used += w;
+    }
+    if width > 0 {
+        out.push('…');
+    }
+    out
+}
+
 /// Aligns text within the specified width according to alignment mode.
 fn align_text(s: &str, width: usize, align: Align) -> String {
     let w = UnicodeWidthStr::width(s);
@@ -317,6 +460,7 @@ fn align_text(s: &str, width: usize, align: Align) -> String {
             let right = width - w - left;
             format!("{}{}{}", " ".repeat(left), s, " ".repeat(right))
         }
+        Align::Right => format!("{}{}", " ".repeat(width - w), s),
     }
 }
 
@@ -421,6 +565,95 @@ mod table_tests {
         assert!(!box_left.is_empty());
     }
 
+    #[test]
+    fn test_message_box_respects_max_width() {
+        let output = FormattedBox::new("Title")
+            .padding(3)
+            .max_width(30)
+            .message_line("This message line is definitely far too long for thirty columns")
+            .render();
+
+        for line in output.lines() {
+            assert!(UnicodeWidthStr::width(line) <= 30, "line too wide: {line}");
+        }
+        assert!(output.contains('…'));
+    }
+
+    #[test]
+    fn test_message_box_short_lines_not_truncated() {
+        let output = FormattedBox::new("Title")
+            .max_width(80)
+            .message_line("Short")
+            .render();
+
+        assert!(output.contains("Short"));
+        assert!(!output.contains('…'));
+    }
+
+    #[test]
+    fn test_truncate_to_width() {
+        assert_eq!(truncate_to_width("hello", 10), "hello");
+        assert_eq!(truncate_to_width("hello world", 6), "hello…");
+        assert_eq!(
+            UnicodeWidthStr::width(truncate_to_width("🌳🌳🌳🌳", 5).as_str()),
+            5
+        );
+    }
+
+    #[test]
+    fn test_stats_box_value_alignment() {
+        let render = |align| {
+            FormattedBox::new("Stats")
+                .value_align(align)
+                .row("Lines:", "7")
+                .row("Size:", "976.6 KB")
+                .render()
+        };
+
+        let right = render(Align::Right);
+        assert!(right.contains("│  Lines:                                       7  │"));
+        assert!(right.contains("│  Size:                                 976.6 KB  │"));
+
+        let left = render(Align::Left);
+        assert!(left.contains("│  Lines:  7                                       │"));
+        assert!(left.contains("│  Size:   976.6 KB                                │"));
+    }
+
+    #[test]
+    fn test_stats_box_grows_with_long_values() {
+        let value = "9".repeat(60);
+        let output = FormattedBox::new("Stats")
+            .row("Lines:", "1")
+            .row("Chars:", value.as_str())
+            .render();
+
+        let widths: Vec<usize> = output.lines().map(UnicodeWidthStr::width).collect();
+        assert!(
+            widths.iter().all(|&w| w == widths[0]),
+            "ragged box: {output}"
+        );
+        assert!(output.contains(&format!("  {value}  │")));
+    }
+
+    #[test]
+    fn test_stats_box_honors_theme() {
+        let output = FormattedBox::new("Stats")
+            .border_style(BorderStyle::Double)
+            .padding(1)
+            .row("Lines:", "\x1b[97m7\x1b[0m")
+            .render();
+
+        assert!(output.starts_with("╔"));
+        assert!(output.ends_with("╝"));
+        // Color codes take no columns
+        let row = output.lines().nth(3).unwrap();
+        assert_eq!(
+            visible_width(row),
+            visible_width(output.lines().next().unwrap())
+        );
+        assert!(row.starts_with("║ Lines:"));
+    }
+
     #[test]
     fn test_unicode_width_handling() {
         // Test with emoji and unicode characters
@@ -0,0 +1,288 @@
+//! unpack - Parses treeclip output back into individual files and restores them to disk.
+//!
+//! A treeclip dump is a sequence of blocks, each starting with a `==> relative/path`
+//! header followed by the file content, with a single blank line between blocks.
+//! Lines inside a file that themselves begin with `==> ` are indistinguishable from
+//! headers, so such files cannot be round-tripped exactly. The output marker line,
+//! a leading directory tree (`--tree`) and table of contents (`--toc`) are skipped,
+//! and a `(small files)` block (`--min-significant-size`) is split back into its
+//! `--- path` entries. A leading `Path prefix:` line (`--collapse-paths`) is joined
+//! back onto every path. A `(stats)` block (`--footer-stats`) is dropped.
+
+use crate::core::errors::{FileSystemError, UnpackError};
+use crate::core::output::format;
+use crate::core::traversal::pack;
+use crate::core::traversal::toc::TOC_TITLE;
+use crate::core::traversal::tree::TREE_TITLE;
+use anyhow::Context;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Prefix marking the start of each file block.
+pub const HEADER_PREFIX: &str = "==> ";
+
+/// A single file recovered from a treeclip dump.
+#[derive(Debug, PartialEq, Eq)]
+pub struct UnpackedFile {
+    /// Path relative to the unpack destination.
+    pub path: PathBuf,
+    /// File content, ending with a newline.
+    pub content: String,
+}
+
+/// Parses a treeclip dump into its individual files.
+///
+/// # Errors
+///
+/// Returns `UnpackError::NotATreeclipDump` if the text contains no headers or has
+/// content before the first header, and `UnpackError::UnsafePath` if a header
+/// points outside the destination (absolute paths or `..` components).
+pub fn parse(dump: &str) -> Result<Vec<UnpackedFile>, UnpackError> {
+    let mut files: Vec<UnpackedFile> = Vec::new();
+    let dump = format::strip_marker(dump);
+    let (prefix, dump) = split_path_prefix(dump)?;
+    let start = dump.trim_start();
+    let has_preamble = start.starts_with(TOC_TITLE) || start.starts_with(TREE_TITLE);
+    let mut in_small_files = false;
+    let mut in_stats = false;
+
+    for line in dump.lines() {
+        if let Some(header) = line.strip_prefix(HEADER_PREFIX) {
+            // The blank line before a header is a separator, not content
+            if let Some(previous) = files.last_mut() {
+                strip_separator(&mut previous.content);
+            }
+            in_small_files = header.trim() == pack::SMALL_FILES_TITLE;
+            in_stats = header.trim() == format::STATS_TITLE;
+            if !in_small_files && !in_stats {
+                files.push(UnpackedFile {
+                    path: prefix.join(sanitize_path(header.trim())?),
+                    content: String::new(),
+                });
+            }
+        } else if let Some(entry) = line
+            .strip_prefix(pack::ENTRY_PREFIX)
+            .filter(|_| in_small_files)
+        {
+            files.push(UnpackedFile {
+                path: prefix.join(sanitize_path(entry.trim())?),
+                content: String::new(),
+            });
+        } else if in_stats {
+            continue;
+        } else if let Some(current) = files.last_mut() {
+            current.content.push_str(line);
+            current.content.push('\n');
+        } else if !line.trim().is_empty() && !has_preamble {
+            return Err(UnpackError::NotATreeclipDump);
+        }
+    }
+
+    if files.is_empty() {
+        return Err(UnpackError::NotATreeclipDump);
+    }
+
+    Ok(files)
+}
+
+/// Writes unpacked files below `output_dir`, creating parent directories as needed.
+///
+/// Existing files at the same paths are overwritten.
+///
+/// # Errors
+///
+/// Returns `FileSystemError` if a directory cannot be created or a file cannot be written.
+pub fn write_all(files: &[UnpackedFile], output_dir: &Path) -> anyhow::Result<()> {
+    for file in files {
+        let target = output_dir.join(&file.path);
+
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| FileSystemError::CreateDirFailed {
+                    path: parent.to_path_buf(),
+                    source: e,
+                })
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+
+        fs::write(&target, &file.content)
+            .map_err(|e| FileSystemError::WriteFailed {
+                path: target.clone(),
+                source: e,
+            })
+            .with_context(|| format!("Failed to write unpacked file: {}", target.display()))?;
+    }
+
+    Ok(())
+}
+
+// -------------------------------------------- Private Helper Functions --------------------------------------------
+
+/// Splits off a leading `--collapse-paths` prefix line, returning the prefix (empty
+/// if there is none) and the rest of the dump.
+fn split_path_prefix(dump: &str) -> Result<(PathBuf, &str), UnpackError> {
+    match dump.split_once('\n') {
+        Some((first, rest)) if first.starts_with(format::PATH_PREFIX_TITLE) => {
+            let prefix = first[format::PATH_PREFIX_TITLE.len()..].trim();
+            Ok((sanitize_path(prefix)?, rest))
+        }
+        _ => Ok((PathBuf::new(), dump)),
+    }
+}
+
+/// Removes the trailing blank separator line from a finished block.
+fn strip_separator(content: &mut String) {
+    if content.ends_with("\n\n") {
+        content.pop();
+    }
+}
+
+/// Ensures a header path stays inside the destination directory.
+fn sanitize_path(raw: &str) -> Result<PathBuf, UnpackError> {
+    let path = PathBuf::from(raw);
+
+    let safe = !raw.is_empty()
+        && path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+
+    if !safe {
+        return Err(UnpackError::UnsafePath(path));
+    }
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod unpack_tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_multiple_files() -> anyhow::Result<()> {
+        let dump = "==> src/main.rs\nfn main() {}\n\n==> README.md\n# Title\n\nBody\n";
+        let files = parse(dump)?;
+
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, PathBuf::from("src/main.rs"));
+        assert_eq!(files[0].content, "fn main() {}\n");
+        assert_eq!(files[1].path, PathBuf::from("README.md"));
+        assert_eq!(files[1].content, "# Title\n\nBody\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_splits_small_files_block() -> anyhow::Result<()> {
+        let dump = "==> big.rs\nfn big() {}\n\n==> (small files)\n--- a.rs\npub mod a;\n--- b/c.rs\npub mod c;\n";
+        let files = parse(dump)?;
+
+        let paths: Vec<_> = files.iter().map(|file| file.path.clone()).collect();
+        assert_eq!(
+            paths,
+            [
+                PathBuf::from("big.rs"),
+                PathBuf::from("a.rs"),
+                PathBuf::from("b/c.rs")
+            ]
+        );
+        assert_eq!(files[0].content, "fn big() {}\n");
+        assert_eq!(files[2].content, "pub mod c;\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_skips_output_marker() -> anyhow::Result<()> {
+        let files = parse("# treeclip-output v2\n==> a.txt\nalpha\n")?;
+
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, PathBuf::from("a.txt"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_skips_directory_tree() -> anyhow::Result<()> {
+        let dump = "Directory Tree (1 file)\n.\n└── a.txt\n\n==> a.txt\nalpha\n";
+        let files = parse(dump)?;
+
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].content, "alpha\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_skips_table_of_contents() -> anyhow::Result<()> {
+        let dump = "Table of Contents (1 file)\n1. a.txt (5 B)\n\n==> a.txt\nalpha\n";
+        let files = parse(dump)?;
+
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].content, "alpha\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_restores_collapsed_paths() -> anyhow::Result<()> {
+        let dump = "Path prefix: src/main/\n\n==> a.rs\nalpha\n\n==> util/b.rs\nbeta\n";
+        let files = parse(dump)?;
+
+        assert_eq!(files[0].path, PathBuf::from("src/main/a.rs"));
+        assert_eq!(files[1].path, PathBuf::from("src/main/util/b.rs"));
+
+        let result = parse("Path prefix: ../\n==> a.rs\nalpha\n");
+        assert!(matches!(result, Err(UnpackError::UnsafePath(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_drops_footer_stats() -> anyhow::Result<()> {
+        let dump = "==> a.txt\nalpha\n\n==> (stats)\n┌──┐\n│ 1 │\n└──┘\n";
+        let files = parse(dump)?;
+
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].content, "alpha\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_rejects_plain_text() {
+        let result = parse("just some text someone copied\n");
+        assert!(matches!(result, Err(UnpackError::NotATreeclipDump)));
+
+        let result = parse("");
+        assert!(matches!(result, Err(UnpackError::NotATreeclipDump)));
+    }
+
+    #[test]
+    fn test_parse_rejects_unsafe_paths() {
+        let result = parse("==> ../escape.txt\nnope\n");
+        assert!(matches!(result, Err(UnpackError::UnsafePath(_))));
+
+        let result = parse("==> /etc/passwd\nnope\n");
+        assert!(matches!(result, Err(UnpackError::UnsafePath(_))));
+    }
+
+    #[test]
+    fn test_write_all_creates_files() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let files = parse("==> a/b/c.txt\nhello\n\n==> top.txt\nworld\n")?;
+
+        write_all(&files, temp_dir.path())?;
+
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join("a/b/c.txt"))?,
+            "hello\n"
+        );
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join("top.txt"))?,
+            "world\n"
+        );
+
+        Ok(())
+    }
+}
@@ -2,7 +2,8 @@
 
 use crate::core::errors::FileSystemError;
 use anyhow::Context;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use unicode_normalization::{UnicodeNormalization, is_nfc};
 
 /// Validates that a path exists on the filesystem.
 ///
@@ -16,6 +17,25 @@ pub fn validate_path_exists(path: &Path) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Validates that every path exists, reporting all missing ones together.
+///
+/// # Errors
+///
+/// Returns `FileSystemError::PathNotFound` if one path is missing, or
+/// `FileSystemError::PathsNotFound` listing each of them if several are.
+pub fn validate_paths(paths: &[PathBuf]) -> anyhow::Result<()> {
+    let mut missing: Vec<PathBuf> = paths
+        .iter()
+        .filter(|path| !path.exists())
+        .cloned()
+        .collect();
+    match missing.len() {
+        0 => Ok(()),
+        1 => Err(FileSystemError::PathNotFound(missing.remove(0)).into()),
+        _ => Err(FileSystemError::PathsNotFound(missing).into()),
+    }
+}
+
 /// Formats a number with thousand separators for improved readability.
 ///
 /// # Examples
@@ -34,7 +54,7 @@ pub fn format_number(n: i64) -> String {
 
     let mut result = String::new();
     for (i, char) in s.chars().enumerate() {
-        if i > 0 && ((s.len() - i) % 3 == 0) {
+        if i > 0 && (s.len() - i).is_multiple_of(3) {
             result.push(',');
         }
         result.push(char);
@@ -74,11 +94,21 @@ pub fn format_bytes(bytes: usize) -> String {
     }
 }
 
+/// Renders a number of files with the matching noun: `1 file`, `2 files`.
+pub fn format_file_count(count: usize) -> String {
+    if count == 1 {
+        "1 file".to_string()
+    } else {
+        format!("{count} files")
+    }
+}
+
 /// Canonicalizes a path and provides context on failure.
 ///
 /// # Errors
 ///
 /// Returns `FileSystemError::CanonicalizeFailed` if canonicalization fails.
+#[allow(dead_code)]
 pub fn canonicalize_path(path: &Path) -> anyhow::Result<std::path::PathBuf> {
     path.canonicalize()
         .map_err(|e| FileSystemError::CanonicalizeFailed {
@@ -88,12 +118,69 @@ pub fn canonicalize_path(path: &Path) -> anyhow::Result<std::path::PathBuf> {
         .with_context(|| format!("Failed to resolve absolute path for: {}", path.display()))
 }
 
+/// Returns `path` in Unicode NFC form (`--normalize-unicode`).
+///
+/// macOS stores many filenames decomposed (NFD), so `é` may be `e` plus a combining
+/// accent; NFC turns both spellings into the same path. Paths that are not valid
+/// UTF-8 are returned unchanged.
+pub fn nfc_path(path: &Path) -> PathBuf {
+    match path.to_str() {
+        Some(text) if !is_nfc(text) => PathBuf::from(text.nfc().collect::<String>()),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Looks `program` up the way a shell would: as given if it contains a path
+/// separator, otherwise in each `PATH` directory (trying `.exe` on Windows).
+pub fn find_executable(program: &str) -> Option<PathBuf> {
+    let program = Path::new(program);
+    if program.components().count() > 1 {
+        return program.is_file().then(|| program.to_path_buf());
+    }
+
+    let path = std::env::var_os("PATH")?;
+    std::env::split_paths(&path).find_map(|dir| {
+        let candidate = dir.join(program);
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+        let exe = candidate.with_extension("exe");
+        (cfg!(windows) && exe.is_file()).then_some(exe)
+    })
+}
+
 #[cfg(test)]
 mod utils_tests {
     use super::*;
     use std::path::Path;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_find_executable() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let tool = temp_dir.path().join("tool");
+        std::fs::write(&tool, "")?;
+
+        assert_eq!(find_executable(&tool.display().to_string()), Some(tool));
+        assert_eq!(
+            find_executable(&temp_dir.path().join("missing").display().to_string()),
+            None
+        );
+        assert_eq!(find_executable("treeclip-surely-not-installed"), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_nfc_path_unifies_nfd_and_nfc() {
+        let composed = Path::new("caf\u{e9}/r\u{e9}sum\u{e9}.md");
+        let decomposed = Path::new("cafe\u{301}/re\u{301}sume\u{301}.md");
+
+        assert_ne!(composed, decomposed);
+        assert_eq!(nfc_path(decomposed), composed);
+        assert_eq!(nfc_path(composed), composed);
+    }
+
     #[test]
     fn test_validate_path_exists_valid() -> anyhow::Result<()> {
         let temp_dir = TempDir::new()?;
@@ -111,6 +198,32 @@ mod utils_tests {
         assert!(error_msg.contains("does not exist") || error_msg.contains("PathNotFound"));
     }
 
+    #[test]
+    fn test_validate_paths_lists_every_missing_path() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let valid = temp_dir.path().to_path_buf();
+        let missing_a = temp_dir.path().join("missing_a");
+        let missing_b = temp_dir.path().join("missing_b");
+
+        assert!(validate_paths(std::slice::from_ref(&valid)).is_ok());
+
+        let error = validate_paths(&[missing_a.clone(), valid.clone()]).unwrap_err();
+        assert!(matches!(
+            error.downcast_ref(),
+            Some(FileSystemError::PathNotFound(path)) if *path == missing_a
+        ));
+
+        let error = validate_paths(&[missing_a.clone(), valid, missing_b.clone()]).unwrap_err();
+        match error.downcast_ref() {
+            Some(FileSystemError::PathsNotFound(paths)) => {
+                assert_eq!(paths, &[missing_a, missing_b])
+            }
+            other => panic!("expected PathsNotFound, got {other:?}"),
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn test_format_number_small() {
         assert_eq!(format_number(0), "0");
@@ -173,6 +286,13 @@ mod utils_tests {
         assert_eq!(format_bytes(1_572_864), "1.5 MB");
     }
 
+    #[test]
+    fn test_format_file_count_pluralizes() {
+        assert_eq!(format_file_count(0), "0 files");
+        assert_eq!(format_file_count(1), "1 file");
+        assert_eq!(format_file_count(2), "2 files");
+    }
+
     #[test]
     fn test_canonicalize_path_valid() -> anyhow::Result<()> {
         let temp_dir = TempDir::new()?;
@@ -0,0 +1,130 @@
+//! watch - Polls the input paths for changes (`--watch-clipboard-only`).
+//!
+//! A snapshot is a hash of every file's path, size and modification time, so any
+//! save, addition or removal changes it. Only what the run would walk is hashed,
+//! through the walkers' own exclusion rules. Polling needs no platform file-watching
+//! API, and at the default interval costs one directory walk every half second.
+
+use crate::commands::args::RunArgs;
+use crate::core::traversal::walker::Walker;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::thread;
+use std::time::Duration;
+
+/// Time between two snapshots.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Returns a hash of the path, size and modification time of every file the
+/// `walkers` would consider (`Walker::watched_files`).
+///
+/// An input whose exclusion matcher fails adds nothing, so the refresh that follows
+/// reports the error.
+pub fn snapshot(walkers: &[Walker], run_args: &RunArgs) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for walker in walkers {
+        let paths = walker.watched_files(run_args).unwrap_or_default();
+        for path in &paths {
+            path.hash(&mut hasher);
+            if let Ok(metadata) = path.metadata() {
+                metadata.len().hash(&mut hasher);
+                metadata.modified().ok().hash(&mut hasher);
+            }
+        }
+    }
+    hasher.finish()
+}
+
+/// Blocks until the snapshot differs from `last` and has then settled, and returns it.
+///
+/// Settled means two polls in a row agree, so a save that touches several files (a
+/// formatter, a branch switch) causes one refresh instead of a burst.
+pub fn wait_for_change(walkers: &[Walker], run_args: &RunArgs, last: u64) -> u64 {
+    let mut previous = last;
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let current = snapshot(walkers, run_args);
+        if current != last && current == previous {
+            return current;
+        }
+        previous = current;
+    }
+}
+
+#[cfg(test)]
+mod watch_tests {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    /// Creates `root` and returns one walker over it, excluding `excludes`.
+    ///
+    /// Temp dirs are named `.tmp...`, hidden themselves, so tests walk a subdirectory.
+    fn walkers_for(root: &Path, excludes: &[String]) -> anyhow::Result<Vec<Walker>> {
+        fs::create_dir(root)?;
+        Ok(vec![Walker::new(root, root, Path::new(""), excludes)])
+    }
+
+    #[test]
+    fn test_snapshot_changes_with_the_files() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path().join("project");
+        let walkers = walkers_for(&root, &[])?;
+        let args = RunArgs::default();
+        fs::write(root.join("main.rs"), "fn main() {}")?;
+
+        let before = snapshot(&walkers, &args);
+        assert_eq!(snapshot(&walkers, &args), before);
+
+        fs::write(root.join("main.rs"), "fn main() { run(); }")?;
+        let edited = snapshot(&walkers, &args);
+        assert_ne!(edited, before);
+
+        fs::write(root.join("lib.rs"), "")?;
+        assert_ne!(snapshot(&walkers, &args), edited);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_snapshot_ignores_git_directory() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path().join("project");
+        let walkers = walkers_for(&root, &[])?;
+        let args = RunArgs {
+            hidden: true,
+            ..Default::default()
+        };
+        fs::create_dir(root.join(".git"))?;
+
+        let before = snapshot(&walkers, &args);
+        fs::write(root.join(".git").join("index"), "changed")?;
+        assert_eq!(snapshot(&walkers, &args), before);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_snapshot_ignores_what_the_run_excludes() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path().join("project");
+        let walkers = walkers_for(&root, &["target".to_string()])?;
+        let args = RunArgs::default();
+        fs::create_dir(root.join("target"))?;
+        fs::write(root.join(".ignore"), "node_modules/\n")?;
+        fs::create_dir(root.join("node_modules"))?;
+        fs::write(root.join("main.rs"), "fn main() {}")?;
+
+        let before = snapshot(&walkers, &args);
+        fs::write(root.join("target").join("build.log"), "built")?;
+        fs::write(root.join("node_modules").join("dep.js"), "")?;
+        fs::write(root.join(".env"), "KEY=1")?;
+        assert_eq!(snapshot(&walkers, &args), before);
+
+        fs::write(root.join("main.rs"), "fn main() { run(); }")?;
+        assert_ne!(snapshot(&walkers, &args), before);
+
+        Ok(())
+    }
+}
@@ -1,25 +1,57 @@
 //! main - Entry point for the TreeClip CLI application.
 
-use crate::commands::run;
+use crate::commands::{doctor, run, unpack, version};
 use clap::Parser;
 use cli::*;
+use std::{panic, process};
 
 mod cli;
 mod commands;
 mod core;
 
 fn main() -> anyhow::Result<()> {
+    exit_quietly_on_broken_pipe();
+
     // NOTE: Small delay for dramatic effect - consider removing in production
     std::thread::sleep(std::time::Duration::from_millis(100));
 
     let cli = Cli::parse();
     match cli.command {
         Commands::Run(run_args) => run::execute(run_args)?,
+        Commands::Unpack(unpack_args) => unpack::execute(unpack_args)?,
+        Commands::Version(version_args) => version::execute(&version_args),
+        Commands::Doctor => doctor::execute()?,
     }
 
     Ok(())
 }
 
+/// Exits with status 0 instead of panicking when stdout is closed early.
+///
+/// `println!` panics on a broken pipe (e.g. `treeclip run | head`); the conventional
+/// CLI behavior is to stop quietly once nobody is reading.
+fn exit_quietly_on_broken_pipe() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let message = info
+            .payload()
+            .downcast_ref::<String>()
+            .map(String::as_str)
+            .or_else(|| info.payload().downcast_ref::<&str>().copied())
+            .unwrap_or_default();
+
+        if is_broken_pipe_message(message) {
+            process::exit(0);
+        }
+        default_hook(info);
+    }));
+}
+
+/// Returns whether a panic message comes from printing to a closed stdout.
+fn is_broken_pipe_message(message: &str) -> bool {
+    message.starts_with("failed printing to stdout") && message.contains("Broken pipe")
+}
+
 #[cfg(test)]
 mod main_tests {
     use super::*;
@@ -38,4 +70,12 @@ mod main_tests {
         assert!(result.is_ok());
         Ok(())
     }
+
+    #[test]
+    fn test_is_broken_pipe_message() {
+        assert!(is_broken_pipe_message(
+            "failed printing to stdout: Broken pipe (os error 32)"
+        ));
+        assert!(!is_broken_pipe_message("index out of bounds"));
+    }
 }